@@ -0,0 +1,352 @@
+title = Tic Tac Toe
+
+## Results
+
+result-draw = It's a draw!
+result-win = { $winner ->
+    [you] You won!
+   *[other] { $winner } won!
+}
+result-timeout = { $winner ->
+    [you] { $loser } ran out of time, you won!
+   *[other] { $loser ->
+        [you] You ran out of time, { $winner } won!
+       *[other] { $loser } ran out of time, { $winner } won!
+    }
+}
+result-accuracy = Accuracy: { $percent }
+session-accuracy = Accuracy this session: { $percent }
+result-length = { $moves } moves in { $duration }
+streak-wins = { $count ->
+    [one] First win of a streak
+   *[other] Win streak: { $count } in a row
+}
+streak-lost = { $count ->
+    [one] Your winning streak ended
+   *[other] Your streak of { $count } wins ended
+}
+rematch = Rematch
+analyze = Analyze
+export-replay = Export
+share = Share
+score = { $you } { $human } - { $computer } Computer ({ $draws ->
+    [one] one draw
+   *[other] { $draws } draws
+})
+
+## Board actions
+
+reset = reset
+undo = undo
+redo = redo
+hint = hint
+move-out-of-bounds = That cell isn't on the board.
+move-cell-occupied = That cell is already taken.
+move-game-finished = The game is over, start a new one.
+move-floating = Marks fall to the bottom: play on top of the column.
+move-must-slide = All your marks are placed, slide one of them.
+move-illegal-slide = That mark can't slide there.
+move-closed-board = Play in one of the highlighted boards.
+move-must-rotate = Your mark is placed, rotate a quadrant.
+move-must-place = Place a mark before rotating.
+overlay = Overlay
+mini-leave = Full window
+screenshot = Screenshot
+settings = Settings
+pondering = Pondering...
+opener-human = You play first
+computer-thinking = The computer is thinking…
+opener-computer = The computer plays first
+
+## Files
+
+replay-exported = Replay exported to { $path }
+replay-export-failed = Couldn't export the replay: { $error }
+screenshot-saved = Screenshot saved to { $path }
+screenshot-failed = Couldn't save the screenshot: { $error }
+card-players = { $you } (O) against { $opponent } (X)
+card-saved = Result card saved to { $path }
+record-failed = Couldn't record to { $path }: { $error }
+record-input-failed = Couldn't record the input: { $error }
+replay-failed = Couldn't replay { $path }: { $error }
+settings-load-failed = Couldn't load the settings: { $error }
+settings-reloaded = Settings reloaded from their file
+settings-reload-failed = Ignored the edited settings: { $error }
+checkpoint-restored = Welcome back, your last game was restored
+checkpoint-restore-failed = Couldn't restore your last game: { $error }
+
+## Menus
+
+menu-file = File
+menu-game = Game
+menu-view = View
+command-new-game = New game
+command-open-replay = Open replay...
+command-export = Export
+command-feedback = Send feedback
+command-screenshot = Screenshot
+command-new-tab = New tab
+command-undo = Undo
+command-redo = Redo
+command-hint = Hint
+command-resign = Resign
+command-analyse-game = Analyze this game
+command-analysis = Analysis board
+command-ultimate = Ultimate
+command-cube = 3D
+command-pentago = Pentago
+command-order-chaos = Order and Chaos
+command-ladder = Ladder
+command-lessons = Lessons
+command-trainer = Opening trainer
+command-spectate = Spectate the computer
+command-settings = Settings
+command-theme = Switch theme
+command-zoom-in = Zoom in
+command-zoom-out = Zoom out
+command-zoom-reset = Actual size
+command-mini-mode = Mini mode
+command-palette = Command palette
+palette-placeholder = Type a command
+palette-empty = No command matches
+replays = Replays
+replays-empty = No saved replay yet
+date = { $month ->
+    [1] Jan
+    [2] Feb
+    [3] Mar
+    [4] Apr
+    [5] May
+    [6] Jun
+    [7] Jul
+    [8] Aug
+    [9] Sep
+    [10] Oct
+    [11] Nov
+   *[12] Dec
+} { $day }, { $year }
+replay-saved = { $date }, { $time } UTC
+replay-open-failed = Couldn't open the replay: { $error }
+
+## Chat plays
+
+chat-voting = #{ $channel } is voting, { $seconds ->
+    [one] one second left
+   *[other] { $seconds } seconds left
+}
+chat-disconnected = Lost connection to the chat, reconnecting...
+
+## Closing
+
+close-prompt = Save the game in progress before leaving?
+close-save = Save and quit
+close-discard = Quit without saving
+close-cancel = Cancel
+
+## Settings
+
+settings-blunder-alarm = Warn me before a losing move
+settings-practice = Practice: offer to take back losing moves once punished
+settings-tutor = Tutor: explain moves which give away a better result
+settings-autosave = Save the replay of
+autosave-all = Every game
+autosave-wins = Only my wins
+autosave-off = No game
+settings-advanced = Advanced
+settings-language = Language
+settings-name = Your name
+settings-mark = Your mark
+player-you = You
+player-computer = Computer
+color-plain = Plain
+color-red = Red
+color-orange = Orange
+color-green = Green
+color-blue = Blue
+color-purple = Purple
+settings-board-size = Board size (next game)
+settings-gravity = Gravity: marks fall to the bottom of their column
+settings-morris = Three Men's Morris: once placed, marks slide to an adjacent cell
+line-length-side = A whole side in a row
+line-length = { $count } in a row
+clock-off = No clock
+clock-minutes = { $count ->
+    [one] One minute each
+   *[other] { $count } minutes each
+}
+settings-low-time = Clock turns red under { $seconds } s
+settings-ticking-time = Clock ticks under { $seconds } s
+starter-human = I always play first
+starter-alternate = Sides take turns playing first
+starter-loser = The loser plays first next, sides take turns after a draw
+settings-mistake-rate = Computer mistakes: { $percent } of its moves
+difficulty-easy = Easy
+difficulty-medium = Medium
+difficulty-hard = Hard
+settings-scramble = Start games with a few random marks already placed
+settings-mute = Mute sounds
+settings-reduce-motion = Reduce motion
+settings-low-spec = Low-spec mode: square corners, no animations
+settings-dwell-off = Dwell to click: off
+settings-dwell-time = Dwell to click: hover a cell { $seconds } s
+rules-classic = Completing a line wins
+rules-misere = Completing a line loses (misère)
+rules-notakto = Both sides play X, completing a line loses (Notakto)
+settings-engine-memory = Engine memory: { $kib } KiB
+settings-ponder = Ponder on your time
+settings-perf-hud = Show the performance HUD
+perf-fps = { $fps } FPS
+perf-update = update { $ms } ms (max { $max } ms)
+perf-view = view { $ms } ms (max { $max } ms)
+perf-search = last search { $ms } ms
+perf-search-none = no search yet
+perf-table = engine table { $kib } of { $max } KiB
+perf-tablebases = tablebases { $kib } KiB
+settings-summon-key = Keys bringing the window to the front (Enter to apply)
+summon-failed = Couldn't register the keys bringing the window to the front: { $error }
+settings-voice-command = Speech recognizer for voice commands (Enter to start)
+voice-unknown = Heard "{ $phrase }", which isn't a cell nor a command
+voice-stopped = The speech recognizer stopped: { $reason }
+privacy = Privacy
+feedback = Send feedback
+feedback-intro = Describe what went wrong, then pick what to send along. Nothing is included without its box checked.
+feedback-description = What happened, and what did you expect?
+feedback-logs = The recent logs, with names removed
+feedback-settings = The settings, without your name
+feedback-replay = The moves of the game on the board
+feedback-about = The version of the app and the name of the OS are always included.
+feedback-save = Save
+feedback-email = Email
+feedback-saved = Feedback saved to { $path }.
+feedback-failed = Couldn't save the feedback: { $error }
+feedback-subject = Feedback on tic-tac-toe-iced
+feedback-attach = Please attach { $path } to this email.
+feedback-email-failed = Couldn't open the mail app: { $error }
+calibration = Difficulty calibration
+calibration-games = { $count } games per tier and bot, half of them opened by the bot. Each cell is the share the bot won.
+calibration-running = Playing the games…
+calibration-tier = { $tier } ({ $percent })
+calibration-expected = Expected
+calibration-run = Run
+privacy-checkpoints = Offer to save the game in progress when closing
+privacy-progress = Remember the ladder and the completed lessons
+privacy-wipe = Delete all my data
+privacy-wipe-prompt = Delete the settings, the progress, the saved game, every saved replay and your own lessons?
+privacy-wipe-confirm = Delete everything
+privacy-wipe-cancel = Keep my data
+privacy-wiped = All your data was deleted
+privacy-wipe-failed = Couldn't delete your data: { $error }
+back = back
+
+## Session history
+
+chip-win = W
+chip-loss = L
+chip-draw = D
+chip-details = { $result ->
+    [chip-win] Won
+    [chip-loss] Lost
+   *[chip-draw] Draw
+} against { $opponent } in { $moves } moves ({ $duration }), { $accuracy } accuracy, click for a rematch
+
+## Ladder
+
+ladder = Ladder
+ladder-current = { $bot } (playing)
+ladder-locked = { $bot } (locked)
+ladder-opponent = Playing against { $bot }
+ladder-unlocked = { $bot } is now unlocked
+ladder-completed = The whole ladder is beaten, only the engine is left
+ladder-load-failed = Couldn't load the ladder: { $error }
+bot-engine = Engine
+bot-rookie = Rookie
+bot-copycat = Copycat
+bot-blocker = Blocker
+bot-sleepy = Sleepy
+bot-network = Network
+
+## Lessons
+
+lessons = Lessons
+lesson-next = Next
+lesson-finish = Finish
+lesson-leave = Leave the lesson
+lesson-score = { $right } of { $questions } questions right on the first try
+lesson-completed = { $title } (done)
+lesson-completed-quiz = { $title } (done, { $right }/{ $questions })
+
+trainer = Opening trainer
+spectate = Spectate
+spectate-x = Playing X
+spectate-o = Playing O
+spectate-delay = Pause between moves: { $seconds } s
+spectate-watch = Watch
+spectate-again = Watch again
+spectate-stop = Stop
+spectate-matchup = { $x } (X) against { $o } (O)
+spectate-side = { $name } ({ $mark })
+trainer-stats = { $due } to review today, { $learned } of { $total } learned, { $retention } answered right
+trainer-ask = The computer plays X. Your move?
+trainer-right = Right, that move holds.
+trainer-wrong = That one loses. The moves which hold are highlighted, it comes back later today.
+trainer-done = Nothing left to review today, come back tomorrow.
+
+## Blunder alarm
+
+blunder-warning = Playing { $cell } loses by force, another move doesn't. Play it anyway?
+blunder-play = Play it
+blunder-cancel = Take it back
+
+## Practice
+
+practice-lost = { $move } loses the game.
+practice-forced = { $move } loses: after { $reply }, the computer forces a win.
+practice-completed = { $move } leaves { $reply } open, which completes a line.
+practice-threat = { $move } loses: { $reply } threatens { $cell }, and the attack goes on after the block.
+practice-fork = { $move } loses: { $reply } threatens both { $first } and { $second }, only one can be blocked.
+practice-take-back = Take it back
+practice-play-on = Play on
+practice-retry = Taken back. Find a move other than { $move } which doesn't lose.
+practice-found = { $move } holds, well found.
+tutor-missed-win = { $move } only draws, { $best } wins: { $line }.
+tutor-completed = { $move } leaves { $reply } open, which completes a line.
+tutor-threat = { $move } allows { $reply }, threatening { $cell }, and the attack goes on after the block.
+tutor-fork = { $move } allows a fork at { $reply }, threatening both { $first } and { $second }.
+tutor-line = { $move } loses to { $line }.
+
+ultimate = Ultimate
+ultimate-rules = Win three small boards in a row. Your move picks the board the computer plays next.
+
+cube = 3D
+cube-layer = Layer { $layer }
+cube-rules = Lines also run across the layers, straight down or diagonally through the cube.
+
+pentago = Pentago
+pentago-rules = Place a mark, then turn a quadrant. Five in a row wins.
+pentago-rotate = Now turn one of the quadrants.
+pentago-turn-left = Turn left
+pentago-turn-right = Turn right
+
+order-chaos = Order and Chaos
+order-chaos-mark = Next mark
+order-chaos-order = You play Order: make five of the same mark in a row, X or O.
+order-chaos-chaos = You play Chaos: fill the board before five of a mark line up.
+order-chaos-swap = Switch roles
+
+tab = Game { $number }
+
+analysis = Analysis
+analysis-side = Next mark
+analysis-wins = { $mark } wins with best play
+analysis-even = No side forces a win
+analysis-searching = Searching the position…
+analysis-best = Best move for { $mark }: { $cell }
+analysis-clear = Clear
+analysis-save = Save
+analysis-saved = Analysis saved to { $path }
+analysis-save-failed = Couldn't save the analysis: { $error }
+analysis-load-failed = Couldn't open the analysis { $path }: { $error }
+analysis-cell-known = Known result, searched to the end
+analysis-cell-estimate = Estimate, the search stops before the end
+analysis-cell-library = Played in { $played } of { $games } saved games from here
+analysis-cell-unseen = No saved game reached this position
@@ -0,0 +1,352 @@
+title = Tres en raya
+
+## Results
+
+result-draw = ¡Empate!
+result-win = { $winner ->
+    [you] ¡Ganaste!
+   *[other] ¡Ganó { $winner }!
+}
+result-timeout = { $winner ->
+    [you] A { $loser } se le acabó el tiempo, ¡ganaste!
+   *[other] { $loser ->
+        [you] Se te acabó el tiempo, ¡ganó { $winner }!
+       *[other] A { $loser } se le acabó el tiempo, ¡ganó { $winner }!
+    }
+}
+result-accuracy = Precisión: { $percent }
+session-accuracy = Precisión en la sesión: { $percent }
+result-length = { $moves } movimientos en { $duration }
+streak-wins = { $count ->
+    [one] Primera victoria de una racha
+   *[other] Racha de victorias: { $count } seguidas
+}
+streak-lost = { $count ->
+    [one] Tu racha de victorias terminó
+   *[other] Tu racha de { $count } victorias terminó
+}
+rematch = Revancha
+analyze = Analizar
+export-replay = Exportar
+share = Compartir
+score = { $you } { $human } - { $computer } Computadora ({ $draws ->
+    [one] un empate
+   *[other] { $draws } empates
+})
+
+## Board actions
+
+reset = reiniciar
+undo = deshacer
+redo = rehacer
+hint = pista
+move-out-of-bounds = Esa casilla no está en el tablero.
+move-cell-occupied = Esa casilla ya está ocupada.
+move-game-finished = La partida terminó, empieza otra.
+move-floating = Las marcas caen al fondo: juega encima de la columna.
+move-must-slide = Ya colocaste todas tus marcas, desliza una.
+move-illegal-slide = Esa marca no puede deslizarse ahí.
+move-closed-board = Juega en uno de los tableros resaltados.
+move-must-rotate = Ya colocaste tu marca, gira un cuadrante.
+move-must-place = Coloca una marca antes de girar.
+overlay = Superposición
+mini-leave = Ventana completa
+screenshot = Captura
+settings = Ajustes
+pondering = Pensando...
+opener-human = Juegas primero
+computer-thinking = La computadora está pensando…
+opener-computer = La computadora juega primero
+
+## Files
+
+replay-exported = Repetición exportada a { $path }
+replay-export-failed = No se pudo exportar la repetición: { $error }
+screenshot-saved = Captura guardada en { $path }
+screenshot-failed = No se pudo guardar la captura: { $error }
+card-players = { $you } (O) contra { $opponent } (X)
+card-saved = Tarjeta del resultado guardada en { $path }
+record-failed = No se pudo grabar en { $path }: { $error }
+record-input-failed = No se pudo grabar la entrada: { $error }
+replay-failed = No se pudo reproducir { $path }: { $error }
+settings-load-failed = No se pudieron cargar los ajustes: { $error }
+settings-reloaded = Ajustes recargados desde su archivo
+settings-reload-failed = Se ignoraron los ajustes editados: { $error }
+checkpoint-restored = Bienvenido de nuevo, tu última partida fue restaurada
+checkpoint-restore-failed = No se pudo restaurar tu última partida: { $error }
+
+## Menus
+
+menu-file = Archivo
+menu-game = Partida
+menu-view = Ver
+command-new-game = Nueva partida
+command-open-replay = Abrir repetición...
+command-export = Exportar
+command-feedback = Enviar comentarios
+command-screenshot = Captura
+command-new-tab = Nueva pestaña
+command-undo = Deshacer
+command-redo = Rehacer
+command-hint = Pista
+command-resign = Rendirse
+command-analyse-game = Analizar esta partida
+command-analysis = Tablero de análisis
+command-ultimate = Ultimate
+command-cube = 3D
+command-pentago = Pentago
+command-order-chaos = Orden y Caos
+command-ladder = Escalera
+command-lessons = Lecciones
+command-trainer = Entrenador de aperturas
+command-spectate = Ver jugar a la computadora
+command-settings = Ajustes
+command-theme = Cambiar tema
+command-zoom-in = Acercar
+command-zoom-out = Alejar
+command-zoom-reset = Tamaño real
+command-mini-mode = Modo mini
+command-palette = Paleta de comandos
+palette-placeholder = Escribe un comando
+palette-empty = Ningún comando coincide
+replays = Repeticiones
+replays-empty = Todavía no hay repeticiones guardadas
+date = { $day } { $month ->
+    [1] ene
+    [2] feb
+    [3] mar
+    [4] abr
+    [5] may
+    [6] jun
+    [7] jul
+    [8] ago
+    [9] sept
+    [10] oct
+    [11] nov
+   *[12] dic
+} { $year }
+replay-saved = { $date }, { $time } UTC
+replay-open-failed = No se pudo abrir la repetición: { $error }
+
+## Chat plays
+
+chat-voting = #{ $channel } está votando, { $seconds ->
+    [one] queda un segundo
+   *[other] quedan { $seconds } segundos
+}
+chat-disconnected = Se perdió la conexión con el chat, reconectando...
+
+## Closing
+
+close-prompt = ¿Guardar la partida en curso antes de salir?
+close-save = Guardar y salir
+close-discard = Salir sin guardar
+close-cancel = Cancelar
+
+## Settings
+
+settings-blunder-alarm = Avisarme antes de una jugada perdedora
+settings-practice = Práctica: ofrecer deshacer las jugadas perdedoras una vez castigadas
+settings-tutor = Tutor: explicar las jugadas que regalan un mejor resultado
+settings-autosave = Guardar la repetición de
+autosave-all = Todas las partidas
+autosave-wins = Solo mis victorias
+autosave-off = Ninguna partida
+settings-advanced = Avanzado
+settings-language = Idioma
+settings-name = Tu nombre
+settings-mark = Tu marca
+player-you = Tú
+player-computer = Computadora
+color-plain = Normal
+color-red = Rojo
+color-orange = Naranja
+color-green = Verde
+color-blue = Azul
+color-purple = Morado
+settings-board-size = Tamaño del tablero (próxima partida)
+settings-gravity = Gravedad: las marcas caen al fondo de su columna
+settings-morris = Molino de tres: una vez colocadas, las marcas se deslizan a una casilla vecina
+line-length-side = Un lado entero en línea
+line-length = { $count } en línea
+clock-off = Sin reloj
+clock-minutes = { $count ->
+    [one] Un minuto cada uno
+   *[other] { $count } minutos cada uno
+}
+settings-low-time = El reloj se pone rojo bajo { $seconds } s
+settings-ticking-time = El reloj hace tictac bajo { $seconds } s
+starter-human = Siempre juego primero
+starter-alternate = Los lados se turnan para jugar primero
+starter-loser = El perdedor juega primero la siguiente, se turnan tras un empate
+settings-mistake-rate = Errores de la computadora: { $percent } de sus jugadas
+difficulty-easy = Fácil
+difficulty-medium = Media
+difficulty-hard = Difícil
+settings-scramble = Empezar las partidas con algunas marcas al azar ya puestas
+settings-mute = Silenciar los sonidos
+settings-reduce-motion = Reducir las animaciones
+settings-low-spec = Modo para equipos modestos: esquinas rectas, sin animaciones
+settings-dwell-off = Clic por permanencia: desactivado
+settings-dwell-time = Clic por permanencia: pasar { $seconds } s sobre una casilla
+rules-classic = Completar una línea gana
+rules-misere = Completar una línea pierde (misère)
+rules-notakto = Ambos lados juegan X, completar una línea pierde (Notakto)
+settings-engine-memory = Memoria del motor: { $kib } KiB
+settings-ponder = Pensar durante tu turno
+settings-perf-hud = Mostrar el rendimiento
+perf-fps = { $fps } FPS
+perf-update = actualización { $ms } ms (máx. { $max } ms)
+perf-view = vista { $ms } ms (máx. { $max } ms)
+perf-search = última búsqueda { $ms } ms
+perf-search-none = sin búsquedas todavía
+perf-table = tabla del motor { $kib } de { $max } KiB
+perf-tablebases = tablebases { $kib } KiB
+settings-summon-key = Teclas que traen la ventana al frente (Intro para aplicar)
+summon-failed = No se pudieron registrar las teclas que traen la ventana al frente: { $error }
+settings-voice-command = Reconocedor de voz para los comandos de voz (Intro para iniciar)
+voice-unknown = Se oyó "{ $phrase }", que no es una casilla ni un comando
+voice-stopped = El reconocedor de voz se detuvo: { $reason }
+privacy = Privacidad
+feedback = Enviar comentarios
+feedback-intro = Describe qué salió mal y elige qué enviar con la descripción. Nada se incluye sin marcar su casilla.
+feedback-description = ¿Qué pasó y qué esperabas?
+feedback-logs = Los registros recientes, sin nombres
+feedback-settings = La configuración, sin tu nombre
+feedback-replay = Las jugadas de la partida del tablero
+feedback-about = La versión de la aplicación y el nombre del sistema operativo se incluyen siempre.
+feedback-save = Guardar
+feedback-email = Correo
+feedback-saved = Comentarios guardados en { $path }.
+feedback-failed = No se pudieron guardar los comentarios: { $error }
+feedback-subject = Comentarios sobre tic-tac-toe-iced
+feedback-attach = Adjunta { $path } a este correo, por favor.
+feedback-email-failed = No se pudo abrir la aplicación de correo: { $error }
+calibration = Calibración de la dificultad
+calibration-games = { $count } partidas por nivel y bot, la mitad abiertas por el bot. Cada celda es la parte que ganó el bot.
+calibration-running = Jugando las partidas…
+calibration-tier = { $tier } ({ $percent })
+calibration-expected = Esperado
+calibration-run = Ejecutar
+privacy-checkpoints = Ofrecer guardar la partida en curso al cerrar
+privacy-progress = Recordar la escalera y las lecciones completadas
+privacy-wipe = Borrar todos mis datos
+privacy-wipe-prompt = ¿Borrar los ajustes, el progreso, la partida guardada, todas las repeticiones guardadas y tus propias lecciones?
+privacy-wipe-confirm = Borrar todo
+privacy-wipe-cancel = Conservar mis datos
+privacy-wiped = Se borraron todos tus datos
+privacy-wipe-failed = No se pudieron borrar tus datos: { $error }
+back = volver
+
+## Session history
+
+chip-win = V
+chip-loss = D
+chip-draw = E
+chip-details = { $result ->
+    [chip-win] Victoria
+    [chip-loss] Derrota
+   *[chip-draw] Empate
+} contra { $opponent } en { $moves } movimientos ({ $duration }), { $accuracy } de precisión, haz clic para la revancha
+
+## Ladder
+
+ladder = Escalera
+ladder-current = { $bot } (jugando)
+ladder-locked = { $bot } (bloqueado)
+ladder-opponent = Jugando contra { $bot }
+ladder-unlocked = { $bot } ya está desbloqueado
+ladder-completed = Superaste toda la escalera, solo queda el motor
+ladder-load-failed = No se pudo cargar la escalera: { $error }
+bot-engine = Motor
+bot-rookie = Novato
+bot-copycat = Imitador
+bot-blocker = Bloqueador
+bot-sleepy = Dormilón
+bot-network = Red neuronal
+
+## Lessons
+
+lessons = Lecciones
+lesson-next = Siguiente
+lesson-finish = Terminar
+lesson-leave = Salir de la lección
+lesson-score = { $right } de { $questions } preguntas bien al primer intento
+lesson-completed = { $title } (hecha)
+lesson-completed-quiz = { $title } (hecha, { $right }/{ $questions })
+
+trainer = Entrenador de aperturas
+spectate = Espectador
+spectate-x = Juega con X
+spectate-o = Juega con O
+spectate-delay = Pausa entre jugadas: { $seconds } s
+spectate-watch = Ver
+spectate-again = Ver otra vez
+spectate-stop = Detener
+spectate-matchup = { $x } (X) contra { $o } (O)
+spectate-side = { $name } ({ $mark })
+trainer-stats = { $due } por repasar hoy, { $learned } de { $total } aprendidas, { $retention } de aciertos
+trainer-ask = La computadora juega X. ¿Tu jugada?
+trainer-right = Bien, esa jugada aguanta.
+trainer-wrong = Esa pierde. Las jugadas que aguantan están resaltadas, volverá más tarde hoy.
+trainer-done = No queda nada por repasar hoy, vuelve mañana.
+
+## Blunder alarm
+
+blunder-warning = Jugar { $cell } pierde sin remedio y hay otra jugada que no. ¿Jugarla igualmente?
+blunder-play = Jugarla
+blunder-cancel = Pensarlo otra vez
+
+## Practice
+
+practice-lost = { $move } pierde la partida.
+practice-forced = { $move } pierde: tras { $reply }, la computadora fuerza la victoria.
+practice-completed = { $move } deja libre { $reply }, que completa una línea.
+practice-threat = { $move } pierde: { $reply } amenaza { $cell }, y el ataque sigue tras el bloqueo.
+practice-fork = { $move } pierde: { $reply } amenaza a la vez { $first } y { $second }, solo se puede bloquear una.
+practice-take-back = Deshacerla
+practice-play-on = Seguir jugando
+practice-retry = Deshecha. Busca una jugada distinta de { $move } que no pierda.
+practice-found = { $move } aguanta, bien visto.
+tutor-missed-win = { $move } solo empata, { $best } gana: { $line }.
+tutor-completed = { $move } deja libre { $reply }, que completa una línea.
+tutor-threat = { $move } permite { $reply }, que amenaza { $cell }, y el ataque sigue tras el bloqueo.
+tutor-fork = { $move } permite un doble ataque en { $reply }, que amenaza a la vez { $first } y { $second }.
+tutor-line = { $move } pierde ante { $line }.
+
+ultimate = Ultimate
+ultimate-rules = Gana tres tableros pequeños en línea. Tu jugada elige el tablero donde juega la computadora.
+
+cube = 3D
+cube-layer = Capa { $layer }
+cube-rules = Las líneas también cruzan las capas, en vertical o en diagonal a través del cubo.
+
+pentago = Pentago
+pentago-rules = Coloca una marca y luego gira un cuadrante. Cinco en línea gana.
+pentago-rotate = Ahora gira uno de los cuadrantes.
+pentago-turn-left = Girar a la izquierda
+pentago-turn-right = Girar a la derecha
+
+order-chaos = Orden y Caos
+order-chaos-mark = Siguiente marca
+order-chaos-order = Juegas Orden: alinea cinco marcas iguales, X u O.
+order-chaos-chaos = Juegas Caos: llena el tablero antes de que se alineen cinco marcas iguales.
+order-chaos-swap = Cambiar de papel
+
+tab = Partida { $number }
+
+analysis = Análisis
+analysis-side = Siguiente marca
+analysis-wins = { $mark } gana con el mejor juego
+analysis-even = Ningún lado fuerza la victoria
+analysis-searching = Analizando la posición…
+analysis-best = Mejor jugada para { $mark }: { $cell }
+analysis-clear = Vaciar
+analysis-save = Guardar
+analysis-saved = Análisis guardado en { $path }
+analysis-save-failed = No se pudo guardar el análisis: { $error }
+analysis-load-failed = No se pudo abrir el análisis { $path }: { $error }
+analysis-cell-known = Resultado conocido, analizado hasta el final
+analysis-cell-estimate = Estimación, el análisis se detiene antes del final
+analysis-cell-library = Jugada en { $played } de { $games } partidas guardadas desde aquí
+analysis-cell-unseen = Ninguna partida guardada llegó a esta posición
@@ -0,0 +1,58 @@
+//! Keeps track of what needs the app to wake up on its own.
+//!
+//! iced redraws the window after every message, so a timer which keeps ticking while nothing
+//! changes burns CPU and GPU time for nothing. Timers are only subscribed to while their
+//! activity is running.
+
+use std::time::Duration;
+
+use iced::Subscription;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Timer {
+    /// The countdown of a chat vote.
+    Voting,
+    /// Playing back a recording.
+    Replay,
+    /// The clocks of a timed game.
+    Clock,
+}
+
+impl Timer {
+    fn interval(self) -> Duration {
+        match self {
+            Timer::Voting => Duration::from_secs(1),
+            Timer::Replay => Duration::from_millis(16),
+            Timer::Clock => Duration::from_millis(100),
+        }
+    }
+}
+
+/// The timers which are currently running.
+#[derive(Default)]
+pub struct Tracker {
+    active: Vec<Timer>,
+}
+
+impl Tracker {
+    /// Marks `timer` as running when `active` is true.
+    pub fn with(mut self, timer: Timer, active: bool) -> Self {
+        if active && !self.active.contains(&timer) {
+            self.active.push(timer);
+        }
+        self
+    }
+
+    /// Ticks every running timer at its own pace, nothing at all when idle.
+    pub fn subscription(&self) -> Subscription<Timer> {
+        Subscription::batch(self.active.iter().map(|timer| {
+            let every = iced::time::every(timer.interval());
+            // `Subscription::map` only takes function pointers, so no capturing `timer`.
+            match timer {
+                Timer::Voting => every.map(|_| Timer::Voting),
+                Timer::Replay => every.map(|_| Timer::Replay),
+                Timer::Clock => every.map(|_| Timer::Clock),
+            }
+        }))
+    }
+}
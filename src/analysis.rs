@@ -0,0 +1,533 @@
+//! A sandbox board detached from any game: marks of both sides are placed freely, every
+//! position is assessed by the engine and each line tried is kept in a tree of variations.
+//! A finished game can be opened in it to explore what else could have been played.
+
+use std::{collections::HashSet, io, path::Path};
+
+use iced::{
+    widget::{button, column, horizontal_space, radio, row, scrollable, text, tooltip},
+    Alignment, Element, Length,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{cell_name, has_line, Assessment, Board, Computer, Entity, Game, Rules},
+    i18n::I18n,
+    library::Library,
+    storage,
+    ui::{
+        components::{button_style, panel, panel_style, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_L, SPACE_M, SPACE_S, TEXT_TITLE},
+    },
+    Message,
+};
+
+const CELL: u16 = 48;
+/// Indentation of each level of variations in the tree.
+const INDENT: f32 = 24.0;
+/// Width of the buttons folding the variations.
+const FOLD: f32 = 28.0;
+
+/// A position of the tree.
+struct Node {
+    board: Board,
+    /// The mark placed to reach this position, `None` for the starting one.
+    played: Option<(Entity, usize, usize)>,
+    /// Positions reached from this one, the first being the main line.
+    children: Vec<usize>,
+    /// Reached by the game the analysis was opened from.
+    canonical: bool,
+}
+
+/// An analysis as saved in the library.
+#[derive(Serialize, Deserialize)]
+struct Saved {
+    size: usize,
+    line: usize,
+    #[serde(default)]
+    gravity: bool,
+    #[serde(default)]
+    rules: Rules,
+    /// Marks of the starting position, from a scrambled start.
+    #[serde(default)]
+    start: Vec<SavedMark>,
+    /// Every position but the starting one, each after its parent.
+    moves: Vec<SavedMove>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedMark {
+    x: usize,
+    y: usize,
+    human: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedMove {
+    /// Index of the position the move is played from, 0 being the starting one.
+    parent: usize,
+    x: usize,
+    y: usize,
+    /// Played by the human rather than by the computer.
+    human: bool,
+    #[serde(default)]
+    canonical: bool,
+}
+
+impl storage::Versioned for Saved {
+    const VERSION: u32 = 1;
+}
+
+pub struct Analysis {
+    /// Every position reached, the starting one first, then those of the game if any.
+    nodes: Vec<Node>,
+    current: usize,
+    /// The mark placed by the next click.
+    side: Entity,
+    rules: Rules,
+    engine: Computer,
+    /// What the engine makes of the current position, `None` while it searches it.
+    assessment: Option<Assessment>,
+    /// The saved games, to tell how often each move was played.
+    library: Library,
+    /// Positions whose alternatives are hidden in the tree.
+    folded: HashSet<usize>,
+    /// Outcome of the last save.
+    notice: String,
+}
+
+impl Analysis {
+    /// Starts analysing from `board`, searched by `rules`.
+    pub fn new(board: Board, rules: Rules) -> Analysis {
+        let mut engine = Computer::default();
+        engine.new_game(rules);
+        Analysis {
+            nodes: vec![Node {
+                board,
+                played: None,
+                children: vec![],
+                canonical: false,
+            }],
+            current: 0,
+            side: Entity::Human,
+            rules,
+            engine,
+            assessment: None,
+            library: Library::load(),
+            folded: HashSet::new(),
+            notice: String::new(),
+        }
+    }
+
+    /// Opens the moves of `game` as the canonical line. Slides of marks can't be analysed,
+    /// the line stops before the first one.
+    pub fn from_game(game: &Game) -> Analysis {
+        // The marks of a scrambled start are kept, the number of pieces isn't analysed.
+        let start = game.initial().with_pieces(None);
+        let mut analysis = Analysis::new(start, game.rules());
+        for played in game.moves() {
+            if played.from.is_some() {
+                break;
+            }
+            analysis.push(analysis.current, played.player, played.x, played.y, true);
+            analysis.current = analysis.nodes.len() - 1;
+        }
+        analysis.current = 0;
+        analysis
+    }
+
+    /// Opens an analysis saved with [`Analysis::save`].
+    pub fn load(path: &Path) -> io::Result<Analysis> {
+        let saved: Saved = storage::load_file(path)?;
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+        if !(3..=crate::game::MAX_SIZE).contains(&saved.size) {
+            return Err(invalid("unsupported board size"));
+        }
+        let mut board = Board::new(saved.size)
+            .with_line(saved.line)
+            .with_gravity(saved.gravity);
+        for mark in &saved.start {
+            if mark.x >= saved.size || mark.y >= saved.size {
+                return Err(invalid("invalid mark"));
+            }
+            board[mark.x][mark.y] = if mark.human {
+                Entity::Human
+            } else {
+                Entity::Computer
+            };
+        }
+        let mut analysis = Analysis::new(board, saved.rules);
+        for played in saved.moves {
+            let playable = analysis.nodes.get(played.parent).is_some_and(|parent| {
+                played.x < saved.size
+                    && played.y < saved.size
+                    && parent.board.is_playable(played.x, played.y)
+            });
+            if !playable {
+                return Err(invalid("invalid move"));
+            }
+            let side = if played.human {
+                Entity::Human
+            } else {
+                Entity::Computer
+            };
+            analysis.push(played.parent, side, played.x, played.y, played.canonical);
+        }
+        Ok(analysis)
+    }
+
+    /// Saves the whole tree in the library, keeping the result in the notice shown below it.
+    pub fn save(&mut self, i18n: &I18n) {
+        // Positions are saved in the order they were reached, so they keep their index.
+        let mut parents = vec![0; self.nodes.len()];
+        for (parent, node) in self.nodes.iter().enumerate() {
+            for &child in &node.children {
+                parents[child] = parent;
+            }
+        }
+        let start = &self.nodes[0].board;
+        let saved = Saved {
+            size: start.size(),
+            line: start.line(),
+            gravity: start.has_gravity(),
+            rules: self.rules,
+            start: start
+                .cells()
+                .filter(|&(_, entity)| entity != Entity::Empty)
+                .map(|((x, y), entity)| SavedMark {
+                    x,
+                    y,
+                    human: entity == Entity::Human,
+                })
+                .collect(),
+            moves: self
+                .nodes
+                .iter()
+                .zip(parents)
+                .filter_map(|(node, parent)| {
+                    let (side, x, y) = node.played?;
+                    Some(SavedMove {
+                        parent,
+                        x,
+                        y,
+                        human: side == Entity::Human,
+                        canonical: node.canonical,
+                    })
+                })
+                .collect(),
+        };
+        let path = storage::library_file("analysis", "toml");
+        self.notice = match path.and_then(|path| storage::save_file(&path, &saved).map(|_| path)) {
+            Ok(path) => i18n.tr_args(
+                "analysis-saved",
+                [("path", path.display().to_string().into())],
+            ),
+            Err(err) => i18n.tr_args("analysis-save-failed", [("error", err.to_string().into())]),
+        };
+    }
+
+    /// Adds the position reached by `side` playing `(x, y)` from `parent`.
+    fn push(&mut self, parent: usize, side: Entity, x: usize, y: usize, canonical: bool) {
+        let mut board = self.nodes[parent].board;
+        board[x][y] = self.rules.mark(side);
+        self.nodes.push(Node {
+            board,
+            played: Some((side, x, y)),
+            children: vec![],
+            canonical,
+        });
+        let child = self.nodes.len() - 1;
+        self.nodes[parent].children.push(child);
+    }
+
+    fn board(&self) -> &Board {
+        &self.nodes[self.current].board
+    }
+
+    fn is_over(&self) -> bool {
+        let board = self.board();
+        has_line(board, Entity::Human) || has_line(board, Entity::Computer) || board.is_full()
+    }
+
+    /// The search of the current position with the side to play, to run off the UI thread
+    /// whenever the position changes, its result going to [`Analysis::assessed`].
+    pub fn search(&mut self) -> (Board, Entity, impl FnOnce() -> Assessment + Send + 'static) {
+        self.assessment = None;
+        let (board, side) = (*self.board(), self.side);
+        let mut engine = self.engine.clone();
+        (board, side, move || engine.assess(board, side))
+    }
+
+    /// Shows the `assessment` of `board` with `side` to play, unless another position was
+    /// picked meanwhile.
+    pub fn assessed(&mut self, board: Board, side: Entity, assessment: Assessment) {
+        if board == *self.board() && side == self.side {
+            self.assessment = Some(assessment);
+        }
+    }
+
+    /// Places the mark of the side to play at `(x, y)`, following the variation which already
+    /// does if any, or starting a new one.
+    pub fn place(&mut self, x: usize, y: usize) {
+        if self.is_over() || !self.board().is_playable(x, y) {
+            return;
+        }
+        let played = Some((self.side, x, y));
+        let existing = self.nodes[self.current]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].played == played);
+        let next = match existing {
+            Some(child) => child,
+            None => {
+                self.push(self.current, self.side, x, y, false);
+                self.nodes.len() - 1
+            }
+        };
+        self.select(next);
+    }
+
+    /// Goes back to a position of the tree, the other side of its last move playing next.
+    pub fn select(&mut self, node: usize) {
+        let Some(selected) = self.nodes.get(node) else {
+            return;
+        };
+        self.side = match selected.played {
+            Some((entity, _, _)) => !entity,
+            None => Entity::Human,
+        };
+        self.current = node;
+    }
+
+    /// Picks which mark the next click places, whoever played last.
+    pub fn set_side(&mut self, side: Entity) {
+        self.side = side;
+    }
+
+    /// Forgets every variation, keeping the game the analysis was opened from, and goes back
+    /// to the starting position.
+    pub fn clear(&mut self) {
+        let kept = 1 + self.nodes[1..]
+            .iter()
+            .take_while(|node| node.canonical)
+            .count();
+        self.nodes.truncate(kept);
+        for node in &mut self.nodes {
+            node.children.retain(|&child| child < kept);
+        }
+        self.folded.clear();
+        self.select(0);
+    }
+
+    /// Shows or hides the alternatives branching off the line starting at `node`.
+    pub fn fold(&mut self, node: usize) {
+        if !self.folded.remove(&node) {
+            self.folded.insert(node);
+        }
+    }
+
+    /// Lays out the variations starting at `first` as rows of moves: the main line on one row,
+    /// each alternative to one of its moves below it, one level deeper unless the line is
+    /// folded. Rows tell whether their line has alternatives.
+    fn lines(&self, first: usize, depth: usize, rows: &mut Vec<(usize, Vec<usize>, bool)>) {
+        let mut line = vec![];
+        let mut alternatives = vec![];
+        let mut node = Some(first);
+        while let Some(current) = node {
+            line.push(current);
+            let children = &self.nodes[current].children;
+            alternatives.extend(children.iter().skip(1));
+            node = children.first().copied();
+        }
+        rows.push((depth, line, !alternatives.is_empty()));
+        if self.folded.contains(&first) {
+            return;
+        }
+        for alternative in alternatives {
+            self.lines(alternative, depth + 1, rows);
+        }
+    }
+
+    fn tree(&self) -> Element<'_, Message> {
+        let mut rows = vec![];
+        for &child in &self.nodes[0].children {
+            self.lines(child, 0, &mut rows);
+        }
+        let rows = rows.into_iter().map(|(depth, line, branches)| {
+            let indent = horizontal_space(Length::Fixed(depth as f32 * INDENT));
+            let first = line[0];
+            let fold: Element<'_, Message> = if branches {
+                let label = if self.folded.contains(&first) {
+                    "+"
+                } else {
+                    "-"
+                };
+                focusable(
+                    button(text(label).width(Length::Fill))
+                        .style(button_style(iced::theme::Button::Text))
+                        .width(FOLD)
+                        .on_press(Message::AnalysisFold(first)),
+                )
+            } else {
+                horizontal_space(FOLD).into()
+            };
+            let moves = line.into_iter().filter_map(|node| {
+                let (entity, x, y) = self.nodes[node].played?;
+                // The game itself stands out from the variations tried on top of it.
+                let style = if node == self.current {
+                    button_style(iced::theme::Button::Primary)
+                } else if self.nodes[node].canonical {
+                    button_style(iced::theme::Button::Secondary)
+                } else {
+                    button_style(iced::theme::Button::Text)
+                };
+                Some(focusable(
+                    button(text(format!("{} {}", entity.as_str(), cell_name(x, y))))
+                        .style(style)
+                        .on_press(Message::AnalysisSelect(node)),
+                ))
+            });
+            row([indent.into(), fold].into_iter().chain(moves).collect())
+                .spacing(SPACE_S)
+                .into()
+        });
+        scrollable(column(rows.collect()).spacing(SPACE_S))
+            .height(Length::Fixed(160.0))
+            .into()
+    }
+
+    /// What's known about the side to play placing a mark at `cell`: how it ends, whether
+    /// that's solved or estimated, and how often it was played in the saved games which
+    /// reached this position.
+    fn cell_stats(&self, i18n: &I18n, cell: (usize, usize), reached: u32, times: u32) -> String {
+        let history = if reached == 0 {
+            i18n.tr("analysis-cell-unseen")
+        } else {
+            i18n.tr_args(
+                "analysis-cell-library",
+                [("played", times.into()), ("games", reached.into())],
+            )
+        };
+        let Some(assessment) = &self.assessment else {
+            return format!("{}\n{}", i18n.tr("analysis-searching"), history);
+        };
+        let winner = assessment
+            .moves
+            .iter()
+            .find(|(played, _)| *played == cell)
+            .and_then(|(_, winner)| *winner);
+        let outcome = match winner {
+            Some(winner) => i18n.tr_args("analysis-wins", [("mark", winner.as_str().into())]),
+            None => i18n.tr("analysis-even"),
+        };
+        // A forced win is found at the end of a line even when the search stops short of
+        // the others.
+        let certainty = if assessment.exact || winner.is_some() {
+            i18n.tr("analysis-cell-known")
+        } else {
+            i18n.tr("analysis-cell-estimate")
+        };
+        format!("{}\n{}\n{}", outcome, certainty, history)
+    }
+
+    pub fn view(&self, i18n: &I18n) -> Element<'_, Message> {
+        let board = self.board();
+        let over = self.is_over();
+        let (reached, played) = self.library.continuations(board);
+        let best = self
+            .assessment
+            .as_ref()
+            .and_then(|assessment| assessment.best);
+        let cells = column(
+            (0..board.size())
+                .map(|x| {
+                    row((0..board.size())
+                        .map(|y| {
+                            let style = if best == Some((x, y)) {
+                                button_style(iced::theme::Button::Secondary)
+                            } else {
+                                button_style(iced::theme::Button::Text)
+                            };
+                            let mut cell = button(
+                                text(board[x][y].as_str())
+                                    .width(Length::Fill)
+                                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                            )
+                            .style(style)
+                            .width(CELL)
+                            .height(CELL);
+                            if over || !board.is_playable(x, y) {
+                                return focusable(cell);
+                            }
+                            cell = cell.on_press(Message::AnalysisClicked(x, y));
+                            let times = played.get(&(x, y)).copied().unwrap_or(0);
+                            tooltip(
+                                focusable(cell),
+                                self.cell_stats(i18n, (x, y), reached, times),
+                                tooltip::Position::FollowCursor,
+                            )
+                            .style(panel_style())
+                            .into()
+                        })
+                        .collect())
+                    .spacing(SPACE_S)
+                    .into()
+                })
+                .collect(),
+        )
+        .spacing(SPACE_S);
+
+        let side = row![text(i18n.tr("analysis-side"))]
+            .push(focusable(radio(
+                Entity::Human.as_str(),
+                Entity::Human,
+                Some(self.side),
+                Message::AnalysisSide,
+            )))
+            .push(focusable(radio(
+                Entity::Computer.as_str(),
+                Entity::Computer,
+                Some(self.side),
+                Message::AnalysisSide,
+            )))
+            .align_items(Alignment::Center)
+            .spacing(SPACE_L);
+
+        let outcome = match self.assessment.as_ref().map(|assessment| assessment.winner) {
+            Some(Some(winner)) => i18n.tr_args("analysis-wins", [("mark", winner.as_str().into())]),
+            Some(None) => i18n.tr("analysis-even"),
+            None => i18n.tr("analysis-searching"),
+        };
+        let best = match best {
+            Some((x, y)) => i18n.tr_args(
+                "analysis-best",
+                [
+                    ("mark", self.side.as_str().into()),
+                    ("cell", cell_name(x, y).into()),
+                ],
+            ),
+            None => String::new(),
+        };
+
+        column![
+            text(i18n.tr("analysis")).size(TEXT_TITLE),
+            panel(cells).padding(SPACE_S),
+            side,
+            text(outcome),
+            text(best),
+            self.tree(),
+            text(&self.notice),
+            row![
+                focusable(primary(i18n.tr("analysis-clear")).on_press(Message::AnalysisClear)),
+                focusable(primary(i18n.tr("analysis-save")).on_press(Message::AnalysisSave)),
+                focusable(secondary(i18n.tr("back")).on_press(Message::ToggleAnalysis))
+            ]
+            .spacing(SPACE_M)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M)
+        .into()
+    }
+}
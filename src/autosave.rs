@@ -0,0 +1,54 @@
+//! Keeps the replay of finished games in the library.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{Event, Subscriber},
+    export,
+    game::{Entity, GameState},
+    storage,
+};
+
+/// Which finished games get their replay saved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Autosave {
+    #[default]
+    All,
+    Wins,
+    Off,
+}
+
+impl Autosave {
+    pub const ALL: [Autosave; 3] = [Autosave::All, Autosave::Wins, Autosave::Off];
+
+    /// Id of the translated description of the option.
+    pub fn label(self) -> &'static str {
+        match self {
+            Autosave::All => "autosave-all",
+            Autosave::Wins => "autosave-wins",
+            Autosave::Off => "autosave-off",
+        }
+    }
+
+    fn keeps(self, result: &GameState) -> bool {
+        match self {
+            Autosave::All => result.is_finished(),
+            Autosave::Wins => result.winner() == Some(Entity::Human),
+            Autosave::Off => false,
+        }
+    }
+}
+
+impl Subscriber for Autosave {
+    fn notify(&mut self, event: &Event) {
+        let Event::Finished { game, .. } = event;
+        if !self.keeps(&game.state()) {
+            return;
+        }
+        let saved = storage::library_file("replay", "html")
+            .and_then(|path| export::web::export(game, path));
+        if let Err(err) = saved {
+            tracing::error!(code = "autosave", "couldn't autosave the replay: {}", err);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! A hidden screen for tuning the difficulty tiers before a release, opened with
+//! `--calibration`: the computer plays each tier against the bots of the ladder, scripted
+//! stand-ins for humans of every level, and the share of games they win is the win rate a
+//! human of that level can expect.
+
+use iced::{
+    widget::{column, row, text},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    game::{Board, Computer, Entity, Game, GameState, Rules},
+    i18n::I18n,
+    ladder::Bot,
+    settings::Difficulty,
+    ui::{
+        components::{primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_M, TEXT_SMALL, TEXT_TITLE},
+    },
+    Message,
+};
+
+/// Games of each tier against each bot, half of them opened by the bot.
+const GAMES: u32 = 200;
+
+/// Width of each column of the report.
+const COLUMN: f32 = 110.0;
+
+/// How the games of a tier against a bot went, from the bot's side.
+#[derive(Debug, Clone, Copy)]
+pub struct Matchup {
+    pub difficulty: Difficulty,
+    pub bot: Bot,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Matchup {
+    /// Share of the games the bot won, in percent.
+    fn win_rate(&self) -> u32 {
+        self.wins * 100 / (self.wins + self.draws + self.losses).max(1)
+    }
+}
+
+/// Plays every tier against every bot on the classic board. Takes a while, meant for a
+/// thread.
+pub fn run() -> Vec<Matchup> {
+    // Bots which search play their best, only the tiers make mistakes.
+    let referee = Computer::default();
+    let mut engine = Computer::default();
+    let mut matchups = vec![];
+    for difficulty in Difficulty::ALL {
+        engine.set_mistake_rate(difficulty.mistake_rate());
+        for bot in Bot::ALL {
+            let mut matchup = Matchup {
+                difficulty,
+                bot,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+            };
+            for game in 0..GAMES {
+                let starter = if game % 2 == 0 {
+                    Entity::Human
+                } else {
+                    Entity::Computer
+                };
+                match play(&mut engine, &referee, bot, starter) {
+                    Some(Entity::Human) => matchup.wins += 1,
+                    Some(_) => matchup.losses += 1,
+                    None => matchup.draws += 1,
+                }
+            }
+            tracing::info!(
+                ?difficulty,
+                ?bot,
+                wins = matchup.wins,
+                draws = matchup.draws,
+                losses = matchup.losses,
+                "calibrated"
+            );
+            matchups.push(matchup);
+        }
+    }
+    matchups
+}
+
+/// A game of `engine` against `bot`, the bot playing the human's side. The winner, `None` for
+/// a draw.
+fn play(engine: &mut Computer, referee: &Computer, bot: Bot, starter: Entity) -> Option<Entity> {
+    let mut game = Game::new(Board::default(), Rules::Classic).with_starter(starter);
+    // Every game starts from an empty table, as in the app.
+    engine.new_game(game.rules());
+    game.start(None);
+    let mut last = None;
+    while let GameState::Playing(side) = game.state() {
+        let played = match side {
+            // Bots pick moves for the computer's side, so they're shown the board the other
+            // way around.
+            Entity::Human => Some(bot.play(referee, game.board().swapped(), last)),
+            _ => engine.best_play(*game.board()),
+        };
+        let Some((x, y)) = played else {
+            break;
+        };
+        if game.try_play(x, y).is_err() {
+            break;
+        }
+        // The bots answer the last move of their opponent.
+        if side == Entity::Computer {
+            last = Some((x, y));
+        }
+    }
+    game.state().winner()
+}
+
+/// The calibration screen, its report once a run finished.
+#[derive(Default)]
+pub struct Calibration {
+    pub matchups: Vec<Matchup>,
+    pub running: bool,
+}
+
+impl Calibration {
+    pub fn view<'a>(&self, i18n: &I18n) -> Element<'a, Message> {
+        let cell = |content: String| text(content).width(Length::Fixed(COLUMN));
+        let mut header = row![cell(String::new())];
+        for bot in Bot::ALL {
+            header = header.push(cell(i18n.tr(bot.name())));
+        }
+        header = header.push(cell(i18n.tr("calibration-expected")));
+        let mut report = column![header].spacing(SPACE_M);
+        for difficulty in Difficulty::ALL {
+            let matchups: Vec<&Matchup> = Bot::ALL
+                .into_iter()
+                .filter_map(|bot| {
+                    self.matchups
+                        .iter()
+                        .find(|matchup| matchup.difficulty == difficulty && matchup.bot == bot)
+                })
+                .collect();
+            if matchups.len() < Bot::ALL.len() {
+                continue;
+            }
+            let label = i18n.tr_args(
+                "calibration-tier",
+                [
+                    ("tier", i18n.tr(difficulty.label()).into()),
+                    ("percent", i18n.percent(difficulty.mistake_rate()).into()),
+                ],
+            );
+            let mut line = row![cell(label)];
+            for matchup in &matchups {
+                line = line.push(cell(i18n.percent(matchup.win_rate())));
+            }
+            // Every bot counts the same, as if the humans were spread evenly over their levels.
+            let expected = matchups
+                .iter()
+                .map(|matchup| matchup.win_rate())
+                .sum::<u32>()
+                / matchups.len() as u32;
+            report = report.push(line.push(cell(i18n.percent(expected))));
+        }
+
+        let status = if self.running {
+            i18n.tr("calibration-running")
+        } else {
+            i18n.tr_args("calibration-games", [("count", GAMES.into())])
+        };
+        let mut run = primary(i18n.tr("calibration-run"));
+        if !self.running {
+            run = run.on_press(Message::RunCalibration);
+        }
+        column![
+            text(i18n.tr("calibration")).size(TEXT_TITLE),
+            text(status).size(TEXT_SMALL),
+            report,
+            row![
+                focusable(run),
+                focusable(secondary(i18n.tr("back")).on_press(Message::CloseCalibration))
+            ]
+            .spacing(SPACE_M)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M)
+        .into()
+    }
+}
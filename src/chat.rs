@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use iced::Subscription;
+
+use crate::game::{cell_name, Board};
+
+#[cfg(feature = "twitch")]
+mod twitch;
+
+/// Seconds chat has to vote before the most voted cell is played.
+pub const VOTING_TIME: u64 = 20;
+
+// Without the `twitch` feature nothing listens to the chat.
+#[cfg_attr(not(feature = "twitch"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub enum Event {
+    Connected,
+    Disconnected,
+    /// A chat message which names a cell, e.g. `b2`.
+    Vote {
+        user: String,
+        cell: (usize, usize),
+    },
+}
+
+/// The votes cast during the current voting window, one per user.
+#[derive(Default)]
+pub struct Tally {
+    votes: HashMap<String, (usize, usize)>,
+    pub remaining: u64,
+}
+
+impl Tally {
+    pub fn open(&mut self) {
+        self.votes.clear();
+        self.remaining = VOTING_TIME;
+    }
+
+    /// Records the vote of `user`, replacing any previous one.
+    pub fn vote(&mut self, user: String, cell: (usize, usize)) {
+        self.votes.insert(user, cell);
+    }
+
+    pub fn count(&self, cell: (usize, usize)) -> usize {
+        self.votes.values().filter(|c| **c == cell).count()
+    }
+
+    /// Lists every voted cell with its vote count, most voted first.
+    pub fn summary(&self) -> String {
+        let mut counts: Vec<((usize, usize), usize)> = vec![];
+        for cell in self.votes.values() {
+            match counts.iter_mut().find(|(c, _)| c == cell) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*cell, 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+            .iter()
+            .map(|((x, y), count)| format!("{}: {}", cell_name(*x, *y), count))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    /// Returns the legal cell with the most votes, ties go to the first cell in reading order.
+    pub fn winner(&self, board: &Board) -> Option<(usize, usize)> {
+        let mut best = None;
+        let mut best_count = 0;
+        for (x, y) in board.playable() {
+            let count = self.count((x, y));
+            if count > best_count {
+                best = Some((x, y));
+                best_count = count;
+            }
+        }
+        best
+    }
+}
+
+/// Listens to the chat of the given Twitch `channel`, which only builds with the `twitch`
+/// feature do.
+pub fn listen(channel: String) -> Subscription<Event> {
+    #[cfg(feature = "twitch")]
+    return twitch::twitch(channel);
+    #[cfg(not(feature = "twitch"))]
+    {
+        let _ = channel;
+        Subscription::none()
+    }
+}
@@ -0,0 +1,87 @@
+//! The connection to the chat of a Twitch channel, through its IRC gateway.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+    thread,
+    time::Duration,
+};
+
+use iced::{
+    futures::{channel::mpsc, SinkExt, StreamExt},
+    subscription, Subscription,
+};
+
+use super::Event;
+use crate::game::parse_cell;
+
+const TWITCH_IRC: &str = "irc.chat.twitch.tv:6667";
+/// Anonymous, read-only login accepted by the Twitch IRC gateway.
+const ANONYMOUS_NICK: &str = "justinfan31415";
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Listens to the chat of the given Twitch `channel`.
+pub fn twitch(channel: String) -> Subscription<Event> {
+    subscription::channel(channel.clone(), 100, move |mut output| {
+        let channel = channel.clone();
+        async move {
+            loop {
+                let (sender, mut receiver) = mpsc::unbounded();
+                let channel = channel.clone();
+                thread::spawn(move || {
+                    if let Err(err) = read_chat(&channel, &sender) {
+                        tracing::error!(
+                            code = "twitch-chat",
+                            "couldn't read the twitch chat: {}",
+                            err
+                        );
+                    }
+                    thread::sleep(RECONNECT_DELAY);
+                });
+
+                while let Some(event) = receiver.next().await {
+                    let _ = output.send(event).await;
+                }
+                let _ = output.send(Event::Disconnected).await;
+            }
+        }
+    })
+}
+
+fn read_chat(channel: &str, sender: &mpsc::UnboundedSender<Event>) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect(TWITCH_IRC)?;
+    write!(
+        stream,
+        "NICK {}\r\nJOIN #{}\r\n",
+        ANONYMOUS_NICK,
+        channel.to_lowercase()
+    )?;
+    let _ = sender.unbounded_send(Event::Connected);
+
+    for line in BufReader::new(stream.try_clone()?).lines() {
+        let line = line?;
+        if let Some(server) = line.strip_prefix("PING ") {
+            write!(stream, "PONG {}\r\n", server)?;
+        } else if let Some((user, message)) = parse_privmsg(&line) {
+            if let Some(cell) = parse_cell(message.trim()) {
+                let _ = sender.unbounded_send(Event::Vote {
+                    user: user.to_string(),
+                    cell,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses `:user!user@user.tmi.twitch.tv PRIVMSG #channel :message`.
+fn parse_privmsg(line: &str) -> Option<(&str, &str)> {
+    let (prefix, rest) = line.strip_prefix(':')?.split_once(' ')?;
+    let (command, rest) = rest.split_once(' ')?;
+    if command != "PRIVMSG" {
+        return None;
+    }
+    let (_, message) = rest.split_once(" :")?;
+    let user = prefix.split('!').next()?;
+    Some((user, message))
+}
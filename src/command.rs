@@ -0,0 +1,343 @@
+//! The commands of the app, registered once with their id, their name and their default
+//! shortcut. The menu bar, the command palette, the keyboard, lesson scripts and recorded inputs
+//! all name a [`Command`], which runs by sending its [`Command::message`].
+
+use std::collections::BTreeMap;
+
+use iced::keyboard::{KeyCode, Modifiers};
+
+use crate::Message;
+
+/// Something the user can do from a menu, the palette or with a shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    NewGame,
+    /// Opens a replay page of the library on the analysis board.
+    OpenReplay,
+    /// Packs a description of a problem with what helps reproduce it, to send.
+    Feedback,
+    Export,
+    Screenshot,
+    NewTab,
+    Undo,
+    Redo,
+    /// Shows the move the engine would play for the human.
+    Hint,
+    Resign,
+    /// Opens the game in progress on the analysis board.
+    AnalyseGame,
+    /// Opens an empty analysis board.
+    Analysis,
+    Ultimate,
+    Cube,
+    Pentago,
+    OrderChaos,
+    Ladder,
+    Lessons,
+    /// Drills the answers to the openings of the computer.
+    Trainer,
+    /// Lets two computer players play each other.
+    Spectate,
+    Settings,
+    /// Switches between the dark and the light theme.
+    Theme,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+    /// Shrinks the window to the board, kept above the other windows.
+    MiniMode,
+    /// Opens the command palette.
+    Palette,
+}
+
+/// What the registry knows of a command.
+pub struct Entry {
+    pub command: Command,
+    /// Stable name of the command in lesson scripts, recordings and the settings file.
+    pub id: &'static str,
+    /// Id of the translated name.
+    pub label: &'static str,
+    /// The key running the command along with Ctrl (Cmd on macOS), unless the settings change it.
+    pub binding: Option<KeyCode>,
+}
+
+/// Every command, in the order the palette lists them.
+pub const REGISTRY: [Entry; 27] = [
+    entry(
+        Command::NewGame,
+        "new-game",
+        "command-new-game",
+        Some(KeyCode::N),
+    ),
+    entry(
+        Command::OpenReplay,
+        "open-replay",
+        "command-open-replay",
+        Some(KeyCode::O),
+    ),
+    entry(
+        Command::Export,
+        "export",
+        "command-export",
+        Some(KeyCode::E),
+    ),
+    entry(Command::Feedback, "feedback", "command-feedback", None),
+    entry(
+        Command::Screenshot,
+        "screenshot",
+        "command-screenshot",
+        None,
+    ),
+    entry(Command::NewTab, "new-tab", "command-new-tab", None),
+    entry(Command::Undo, "undo", "command-undo", Some(KeyCode::Z)),
+    entry(Command::Redo, "redo", "command-redo", Some(KeyCode::Y)),
+    entry(Command::Hint, "hint", "command-hint", Some(KeyCode::H)),
+    // Too easy to press by mistake for what it does.
+    entry(Command::Resign, "resign", "command-resign", None),
+    entry(
+        Command::AnalyseGame,
+        "analyse-game",
+        "command-analyse-game",
+        None,
+    ),
+    entry(Command::Analysis, "analysis", "command-analysis", None),
+    entry(Command::Ultimate, "ultimate", "command-ultimate", None),
+    entry(Command::Cube, "cube", "command-cube", None),
+    entry(Command::Pentago, "pentago", "command-pentago", None),
+    entry(
+        Command::OrderChaos,
+        "order-chaos",
+        "command-order-chaos",
+        None,
+    ),
+    entry(Command::Ladder, "ladder", "command-ladder", None),
+    entry(Command::Lessons, "lessons", "command-lessons", None),
+    entry(Command::Trainer, "trainer", "command-trainer", None),
+    entry(Command::Spectate, "spectate", "command-spectate", None),
+    entry(
+        Command::Settings,
+        "settings",
+        "command-settings",
+        Some(KeyCode::Comma),
+    ),
+    entry(Command::Theme, "theme", "command-theme", Some(KeyCode::T)),
+    entry(
+        Command::ZoomIn,
+        "zoom-in",
+        "command-zoom-in",
+        Some(KeyCode::Equals),
+    ),
+    entry(
+        Command::ZoomOut,
+        "zoom-out",
+        "command-zoom-out",
+        Some(KeyCode::Minus),
+    ),
+    entry(
+        Command::ZoomReset,
+        "zoom-reset",
+        "command-zoom-reset",
+        Some(KeyCode::Key0),
+    ),
+    entry(
+        Command::MiniMode,
+        "mini-mode",
+        "command-mini-mode",
+        Some(KeyCode::M),
+    ),
+    entry(
+        Command::Palette,
+        "palette",
+        "command-palette",
+        Some(KeyCode::P),
+    ),
+];
+
+const fn entry(
+    command: Command,
+    id: &'static str,
+    label: &'static str,
+    binding: Option<KeyCode>,
+) -> Entry {
+    Entry {
+        command,
+        id,
+        label,
+        binding,
+    }
+}
+
+impl Command {
+    pub fn all() -> impl Iterator<Item = Command> {
+        REGISTRY.iter().map(|entry| entry.command)
+    }
+
+    fn entry(self) -> &'static Entry {
+        REGISTRY
+            .iter()
+            .find(|entry| entry.command == self)
+            .expect("every command is registered")
+    }
+
+    pub fn id(self) -> &'static str {
+        self.entry().id
+    }
+
+    pub fn label(self) -> &'static str {
+        self.entry().label
+    }
+
+    pub fn from_id(id: &str) -> Option<Command> {
+        Command::all().find(|command| command.id() == id)
+    }
+
+    /// What running the command does.
+    pub fn message(self) -> Message {
+        match self {
+            Command::NewGame => Message::Reset,
+            Command::OpenReplay => Message::BrowseReplays,
+            Command::Feedback => Message::ToggleFeedback,
+            Command::Export => Message::ExportReplay,
+            Command::Screenshot => Message::Screenshot,
+            Command::NewTab => Message::NewTab,
+            Command::Undo => Message::Undo,
+            Command::Redo => Message::Redo,
+            Command::Hint => Message::Hint,
+            Command::Resign => Message::Resign,
+            Command::AnalyseGame => Message::AnalyseGame,
+            Command::Analysis => Message::ToggleAnalysis,
+            Command::Ultimate => Message::ToggleUltimate,
+            Command::Cube => Message::ToggleCube,
+            Command::Pentago => Message::TogglePentago,
+            Command::OrderChaos => Message::ToggleOrderChaos,
+            Command::Ladder => Message::ToggleLadder,
+            Command::Lessons => Message::ToggleLessons,
+            Command::Trainer => Message::ToggleTrainer,
+            Command::Spectate => Message::ToggleSpectate,
+            Command::Settings => Message::ToggleSettings,
+            Command::Theme => Message::ToggleTheme,
+            Command::ZoomIn => Message::ZoomIn,
+            Command::ZoomOut => Message::ZoomOut,
+            Command::ZoomReset => Message::ZoomReset,
+            Command::MiniMode => Message::ToggleMiniMode,
+            Command::Palette => Message::OpenPalette,
+        }
+    }
+}
+
+/// Keys which can be bound, by the name the settings and the menus use for them.
+const KEYS: [(KeyCode, &str); 40] = [
+    (KeyCode::A, "A"),
+    (KeyCode::B, "B"),
+    (KeyCode::C, "C"),
+    (KeyCode::D, "D"),
+    (KeyCode::E, "E"),
+    (KeyCode::F, "F"),
+    (KeyCode::G, "G"),
+    (KeyCode::H, "H"),
+    (KeyCode::I, "I"),
+    (KeyCode::J, "J"),
+    (KeyCode::K, "K"),
+    (KeyCode::L, "L"),
+    (KeyCode::M, "M"),
+    (KeyCode::N, "N"),
+    (KeyCode::O, "O"),
+    (KeyCode::P, "P"),
+    (KeyCode::Q, "Q"),
+    (KeyCode::R, "R"),
+    (KeyCode::S, "S"),
+    (KeyCode::T, "T"),
+    (KeyCode::U, "U"),
+    (KeyCode::V, "V"),
+    (KeyCode::W, "W"),
+    (KeyCode::X, "X"),
+    (KeyCode::Y, "Y"),
+    (KeyCode::Z, "Z"),
+    (KeyCode::Key0, "0"),
+    (KeyCode::Key1, "1"),
+    (KeyCode::Key2, "2"),
+    (KeyCode::Key3, "3"),
+    (KeyCode::Key4, "4"),
+    (KeyCode::Key5, "5"),
+    (KeyCode::Key6, "6"),
+    (KeyCode::Key7, "7"),
+    (KeyCode::Key8, "8"),
+    (KeyCode::Key9, "9"),
+    (KeyCode::Equals, "+"),
+    (KeyCode::Minus, "-"),
+    (KeyCode::Comma, ","),
+    (KeyCode::Period, "."),
+];
+
+fn key_name(key: KeyCode) -> &'static str {
+    KEYS.iter()
+        .find(|(bound, _)| *bound == key)
+        .map_or("?", |(_, name)| name)
+}
+
+/// The key bound to each command: its default, unless `bindings` in the settings names another
+/// key for its id, or none with an empty name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    keys: Vec<(Command, KeyCode)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::new(&BTreeMap::new())
+    }
+}
+
+impl Keymap {
+    /// Bindings to unknown commands or keys are skipped with a warning.
+    pub fn new(bindings: &BTreeMap<String, String>) -> Keymap {
+        let mut keys: Vec<_> = REGISTRY
+            .iter()
+            .filter_map(|entry| Some((entry.command, entry.binding?)))
+            .collect();
+        for (id, name) in bindings {
+            let Some(command) = Command::from_id(id) else {
+                tracing::warn!("ignored the binding of the unknown command {:?}", id);
+                continue;
+            };
+            let key = KEYS
+                .iter()
+                .find(|(_, key)| key.eq_ignore_ascii_case(name))
+                .map(|&(key, _)| key);
+            if key.is_none() && !name.is_empty() {
+                tracing::warn!(
+                    "ignored the binding of {} to the unknown key {:?}",
+                    id,
+                    name
+                );
+                continue;
+            }
+            // A key runs a single command, the one it was bound to last.
+            keys.retain(|&(bound, code)| bound != command && Some(code) != key);
+            keys.extend(key.map(|key| (command, key)));
+        }
+        Keymap { keys }
+    }
+
+    /// The command bound to `key` pressed with `modifiers`.
+    pub fn command(&self, key: KeyCode, modifiers: Modifiers) -> Option<Command> {
+        if !modifiers.command() {
+            return None;
+        }
+        self.keys
+            .iter()
+            .find(|(_, bound)| *bound == key)
+            .map(|&(command, _)| command)
+    }
+
+    /// The shortcut of `command` as shown in the menus, e.g. `Ctrl+N`.
+    pub fn shortcut(&self, command: Command) -> Option<String> {
+        let &(_, key) = self.keys.iter().find(|(bound, _)| *bound == command)?;
+        let modifier = if cfg!(target_os = "macos") {
+            "Cmd"
+        } else {
+            "Ctrl"
+        };
+        Some(format!("{modifier}+{}", key_name(key)))
+    }
+}
@@ -0,0 +1,88 @@
+//! The screen of the 3D variant, the three layers of the cube drawn side by side.
+
+use iced::{
+    widget::{button, column, row, text},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    game::{cube::Cube, Entity, GameState, MoveError},
+    i18n::I18n,
+    player::Players,
+    ui::{
+        components::{button_style, panel, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_L, SPACE_M, SPACE_S, TEXT_TITLE},
+    },
+    Message,
+};
+
+const CELL: u16 = 48;
+
+pub fn view<'a>(
+    game: &'a Cube,
+    refused: Option<MoveError>,
+    players: &Players,
+    i18n: &I18n,
+) -> Element<'a, Message> {
+    let playable = game.state() == GameState::Playing(Entity::Human);
+    let layer = |z: usize| -> Element<'a, Message> {
+        let cells = column(
+            (0..3)
+                .map(|x| {
+                    row((0..3)
+                        .map(|y| {
+                            let entity = game.get((z, x, y));
+                            let mut cell = button(
+                                text(entity.as_str())
+                                    .width(Length::Fill)
+                                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                            )
+                            .style(button_style(iced::theme::Button::Text))
+                            .width(CELL)
+                            .height(CELL);
+                            if playable && entity == Entity::Empty {
+                                cell = cell.on_press(Message::CubeClicked((z, x, y)));
+                            }
+                            focusable(cell)
+                        })
+                        .collect())
+                    .spacing(SPACE_S)
+                    .into()
+                })
+                .collect(),
+        )
+        .spacing(SPACE_S);
+        column![
+            text(i18n.tr_args("cube-layer", [("layer", (z + 1).into())])),
+            panel(cells).padding(SPACE_S)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_S)
+        .into()
+    };
+
+    let status = match game.state() {
+        GameState::Draw => i18n.tr("result-draw"),
+        GameState::Win(winner) => {
+            i18n.tr_args("result-win", [("winner", players.addressed(winner).into())])
+        }
+        _ => match refused {
+            Some(err) => i18n.tr(err.label()),
+            None => i18n.tr("cube-rules"),
+        },
+    };
+    column![
+        text(i18n.tr("cube")).size(TEXT_TITLE),
+        row((0..3).map(layer).collect()).spacing(SPACE_L),
+        text(status),
+        row![
+            focusable(primary(i18n.tr("reset")).on_press(Message::CubeReset)),
+            focusable(secondary(i18n.tr("back")).on_press(Message::ToggleCube))
+        ]
+        .spacing(SPACE_M)
+    ]
+    .align_items(Alignment::Center)
+    .spacing(SPACE_M)
+    .into()
+}
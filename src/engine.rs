@@ -0,0 +1,5 @@
+//! Engines playing the computer's side besides the built-in one, which lives in [`crate::game`].
+
+pub mod external;
+#[cfg(feature = "rl")]
+pub mod network;
@@ -0,0 +1,180 @@
+//! A bot of the human's own, an executable started for each of its moves with `--engine`. The
+//! position is written to its standard input, one field per line, after which the input is
+//! closed:
+//!
+//! ```text
+//! tictactoe 1
+//! rules classic
+//! size 3
+//! line 3
+//! gravity
+//! board X.O/.X./..O
+//! turn X
+//! ```
+//!
+//! `tictactoe` gives the version of the protocol. `rules` is `classic`, `misere` or `notakto`,
+//! `line` the number of marks in a row which complete a line, and `gravity` only shows up when
+//! marks fall to the bottom of their column. `board` lists the rows from the top, `.` for the
+//! empty cells, and the bot always plays the mark of `turn`. The bot answers with a line such
+//! as `move b2`, the column letter then the row number, `a1` being the top left corner. Other
+//! lines are ignored, and what it writes to its standard error shows up in the terminal.
+//!
+//! A bot which fails to start, answers late, answers with a cell it can't play or no cell at
+//! all loses its move to the built-in engine, with a warning in the logs.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc,
+    time::Duration,
+};
+
+use crate::game::{
+    parse_cell,
+    side::{MinimaxPlayer, Player},
+    Board, Computer, Entity, Rules,
+};
+
+/// Version of the protocol, the first line of each position.
+const VERSION: u32 = 1;
+
+/// Time a bot is given for a move when `--engine-timeout` doesn't say otherwise.
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The executable of a bot, and how long it may think.
+#[derive(Debug, Clone)]
+pub struct External {
+    pub program: PathBuf,
+    pub timeout: Duration,
+}
+
+impl External {
+    pub fn new(program: PathBuf) -> External {
+        External {
+            program,
+            timeout: TIMEOUT,
+        }
+    }
+
+    /// Asks the bot for its move on `board`, playing the computer's side under `rules`.
+    pub fn choose_move(&self, board: &Board, rules: Rules) -> io::Result<(usize, usize)> {
+        let mut child = Command::new(&self.program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let mut stdin = child.stdin.take().expect("the input is piped");
+        let stdout = child.stdout.take().expect("the output is piped");
+
+        // Read on a thread of its own, so a bot which never answers can be given up on.
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if let Some(cell) = line.trim().strip_prefix("move ") {
+                    let _ = sender.send(cell.trim().to_string());
+                    return;
+                }
+                tracing::debug!(line, "external engine");
+            }
+        });
+
+        // A bot which exits without reading the position still gets to answer.
+        if let Err(err) = stdin.write_all(request(board, rules).as_bytes()) {
+            if err.kind() != io::ErrorKind::BrokenPipe {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(err);
+            }
+        }
+        drop(stdin);
+
+        let answer = receiver.recv_timeout(self.timeout);
+        // Done with the bot either way, those which keep running after their move included.
+        let _ = child.kill();
+        let status = child.wait()?;
+        let cell = match answer {
+            Ok(cell) => cell,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("no move after {:.1} s", self.timeout.as_secs_f32()),
+                ));
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("exited without a move ({status})"),
+                ));
+            }
+        };
+        parse_cell(&cell)
+            .filter(|&(x, y)| x < board.size() && y < board.size() && board.is_playable(x, y))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("can't play {cell:?} there"),
+                )
+            })
+    }
+}
+
+/// The position as written to the bot.
+fn request(board: &Board, rules: Rules) -> String {
+    let rules = match rules {
+        Rules::Classic => "classic",
+        Rules::Misere => "misere",
+        Rules::Notakto => "notakto",
+    };
+    let rows: Vec<String> = board
+        .rows()
+        .map(|row| row.iter().map(|&entity| mark(entity)).collect())
+        .collect();
+    let mut request = format!(
+        "tictactoe {VERSION}\nrules {rules}\nsize {}\nline {}\n",
+        board.size(),
+        board.line()
+    );
+    if board.has_gravity() {
+        request.push_str("gravity\n");
+    }
+    request.push_str(&format!(
+        "board {}\nturn {}\n",
+        rows.join("/"),
+        mark(Entity::Computer)
+    ));
+    request
+}
+
+fn mark(entity: Entity) -> char {
+    match entity {
+        Entity::Empty => '.',
+        Entity::Computer => 'X',
+        Entity::Human => 'O',
+    }
+}
+
+/// A bot as a side of the game, the engine standing in for it whenever it fails.
+pub struct ExternalPlayer {
+    pub engine: External,
+    pub rules: Rules,
+    pub ia: Computer,
+}
+
+impl Player for ExternalPlayer {
+    fn choose_move(&self, board: &Board) -> Option<(usize, usize)> {
+        match self.engine.choose_move(board, self.rules) {
+            Ok(cell) => Some(cell),
+            Err(err) => {
+                tracing::warn!(
+                    code = "external-engine",
+                    "{} failed, the engine plays instead: {}",
+                    self.engine.program.display(),
+                    err
+                );
+                MinimaxPlayer(self.ia.clone()).choose_move(board)
+            }
+        }
+    }
+}
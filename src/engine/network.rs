@@ -0,0 +1,300 @@
+//! An opponent which doesn't search: a small value network judges the position each of its
+//! moves would leave and it plays the one which looks best. The network learned the classic
+//! 3×3 game by playing itself, with the `train` command. The weights of `assets/network.toml`
+//! are bundled, those written by `train` to the config directory replace them.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{has_line, Board, Computer, Entity, Rules},
+    storage,
+};
+
+const BUNDLED: &str = include_str!("../../assets/network.toml");
+
+/// Two inputs for each cell, whether it holds a mark of the side which just moved, and whether
+/// it holds one of the other side.
+const INPUTS: usize = 18;
+
+const HIDDEN: usize = 32;
+
+/// Step of each correction made while learning.
+const LEARNING_RATE: f32 = 0.02;
+
+/// Share of the moves played at random while learning, to try lines the network doesn't
+/// fancy yet. Fewer leave it stuck on the first lines it liked, losing to the engine.
+const EXPLORATION: f64 = 0.5;
+
+/// The weights, which the network's value of a position is computed from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Network {
+    /// Weights of the inputs, a row for each hidden unit.
+    hidden: Vec<Vec<f32>>,
+    hidden_bias: Vec<f32>,
+    output: Vec<f32>,
+    output_bias: f32,
+}
+
+impl Network {
+    /// Small random weights, to start learning from.
+    fn random(rng: &mut impl Rng) -> Network {
+        let mut weight = |inputs: usize| {
+            let bound = 1.0 / (inputs as f32).sqrt();
+            rng.gen_range(-bound..bound)
+        };
+        Network {
+            hidden: (0..HIDDEN)
+                .map(|_| (0..INPUTS).map(|_| weight(INPUTS)).collect())
+                .collect(),
+            hidden_bias: vec![0.0; HIDDEN],
+            output: (0..HIDDEN).map(|_| weight(HIDDEN)).collect(),
+            output_bias: 0.0,
+        }
+    }
+
+    /// The network in use: the trained one of the config directory, else the bundled one.
+    pub fn get() -> &'static Network {
+        static NETWORK: OnceLock<Network> = OnceLock::new();
+        NETWORK.get_or_init(|| {
+            let trained = file().and_then(|path| match Network::load(&path) {
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+                loaded => loaded.map(Some),
+            });
+            match trained {
+                Ok(Some(network)) => network,
+                Ok(None) => Network::bundled(),
+                Err(err) => {
+                    tracing::warn!("skipped the trained network: {}", err);
+                    Network::bundled()
+                }
+            }
+        })
+    }
+
+    fn bundled() -> Network {
+        let network: Network = toml::from_str(BUNDLED).expect("the bundled network is valid");
+        assert!(network.is_valid(), "the bundled network is valid");
+        network
+    }
+
+    pub fn load(path: &Path) -> io::Result<Network> {
+        let network: Network = toml::from_str(&fs::read_to_string(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if !network.is_valid() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "weights of the wrong shape",
+            ));
+        }
+        Ok(network)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents =
+            toml::to_string(self).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(path, contents)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.hidden.len() == HIDDEN
+            && self.hidden.iter().all(|row| row.len() == INPUTS)
+            && self.hidden_bias.len() == HIDDEN
+            && self.output.len() == HIDDEN
+    }
+
+    /// The hidden units and the value, between -1 for a loss and 1 for a win.
+    fn forward(&self, inputs: &[f32; INPUTS]) -> ([f32; HIDDEN], f32) {
+        let mut hidden = [0.0; HIDDEN];
+        for (unit, (weights, bias)) in hidden
+            .iter_mut()
+            .zip(self.hidden.iter().zip(&self.hidden_bias))
+        {
+            let sum: f32 = weights.iter().zip(inputs).map(|(w, i)| w * i).sum();
+            *unit = (sum + bias).tanh();
+        }
+        let sum: f32 = self.output.iter().zip(&hidden).map(|(w, h)| w * h).sum();
+        (hidden, (sum + self.output_bias).tanh())
+    }
+
+    /// What `board` promises to `mover`, who just moved.
+    pub fn value(&self, board: &Board, mover: Entity) -> f32 {
+        self.forward(&inputs(board, mover)).1
+    }
+
+    /// Moves the value of `board` for `mover` towards `target`.
+    fn learn(&mut self, board: &Board, mover: Entity, target: f32) {
+        let inputs = inputs(board, mover);
+        let (hidden, value) = self.forward(&inputs);
+        let delta = (value - target) * (1.0 - value * value);
+        for (unit, activation) in hidden.into_iter().enumerate() {
+            let hidden_delta = delta * self.output[unit] * (1.0 - activation * activation);
+            self.output[unit] -= LEARNING_RATE * delta * activation;
+            for (weight, input) in self.hidden[unit].iter_mut().zip(&inputs) {
+                *weight -= LEARNING_RATE * hidden_delta * input;
+            }
+            self.hidden_bias[unit] -= LEARNING_RATE * hidden_delta;
+        }
+        self.output_bias -= LEARNING_RATE * delta;
+    }
+
+    /// The value of playing `(x, y)` for `player`: a line ends the game, and so does a full
+    /// board, otherwise the network judges.
+    fn judge(&self, board: &mut Board, player: Entity, (x, y): (usize, usize)) -> f32 {
+        board[x][y] = player;
+        let value = if has_line(board, player) {
+            1.0
+        } else if board.is_full() {
+            0.0
+        } else {
+            self.value(board, player)
+        };
+        board[x][y] = Entity::Empty;
+        value
+    }
+
+    /// The move of `player` the network likes best on `board`, which has one left.
+    pub fn best_move(&self, mut board: Board, player: Entity) -> (usize, usize) {
+        let cells: Vec<_> = board.playable().collect();
+        cells
+            .into_iter()
+            .map(|cell| (cell, self.judge(&mut board, player, cell)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(cell, _)| cell)
+            .expect("players only move on unfinished boards")
+    }
+}
+
+/// `board` as the network sees it, from the side of `mover`. Positions differing only by a
+/// rotation or a mirror are seen the same, so what's learned of one holds for the others.
+fn inputs(board: &Board, mover: Entity) -> [f32; INPUTS] {
+    let mut inputs = [0.0; INPUTS];
+    for ((x, y), entity) in board.canonical().cells() {
+        let cell = x * 3 + y;
+        if entity == mover {
+            inputs[cell * 2] = 1.0;
+        } else if entity != Entity::Empty {
+            inputs[cell * 2 + 1] = 1.0;
+        }
+    }
+    inputs
+}
+
+/// Whether the network knows the game on `board`: the classic 3×3 one.
+fn knows(board: &Board) -> bool {
+    board.size() == 3 && board.line() == 3 && !board.has_gravity() && board.pieces().is_none()
+}
+
+/// The move of the network for the computer's side, the engine's on boards it doesn't know.
+pub fn play(ia: &Computer, board: Board) -> Option<(usize, usize)> {
+    if knows(&board) {
+        Some(Network::get().best_move(board, Entity::Computer))
+    } else {
+        ia.clone().best_play(board)
+    }
+}
+
+/// Where `train` writes the network and where it's read from.
+pub fn file() -> io::Result<PathBuf> {
+    Ok(storage::folder("network")?.with_extension("toml"))
+}
+
+/// Learns the classic game from scratch over `games` games against itself. Each position a
+/// side leaves is pulled towards the value of the next one it leaves, seen from the other
+/// side, or towards the result when the other side ends the game. `progress` is called with
+/// the games played every tenth of the way.
+pub fn train(games: u32, rng: &mut impl Rng, mut progress: impl FnMut(u32)) -> Network {
+    let mut network = Network::random(rng);
+    for game in 0..games {
+        let mut board = Board::default();
+        let mut player = if game % 2 == 0 {
+            Entity::Computer
+        } else {
+            Entity::Human
+        };
+        // The position the other side left, which the move being played answers.
+        let mut answered: Option<Board> = None;
+        loop {
+            let cells: Vec<_> = board.playable().collect();
+            let explore = rng.gen_bool(EXPLORATION);
+            let cell = if explore {
+                *cells.choose(rng).expect("the game isn't over")
+            } else {
+                network.best_move(board, player)
+            };
+            board[cell.0][cell.1] = player;
+            let won = has_line(&board, player);
+            let target = if won {
+                Some(-1.0)
+            } else if board.is_full() {
+                Some(0.0)
+            } else if !explore {
+                Some(-network.value(&board, player))
+            } else {
+                // A move played at random says nothing of the position it answers.
+                None
+            };
+            if let (Some(previous), Some(target)) = (answered, target) {
+                network.learn(&previous, !player, target);
+            }
+            if won || board.is_full() {
+                break;
+            }
+            answered = Some(board);
+            player = !player;
+        }
+        if (game + 1) % (games / 10).max(1) == 0 {
+            progress(game + 1);
+        }
+    }
+    network
+}
+
+/// Games played against the engine to see how the network holds up, half of them opened by
+/// the engine.
+const MATCH_GAMES: u32 = 100;
+
+/// The wins, draws and losses of `network` against `engine`, the network playing the human's
+/// side.
+pub fn against_engine(network: &Network, engine: &mut Computer) -> (u32, u32, u32) {
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+    for game in 0..MATCH_GAMES {
+        engine.new_game(Rules::Classic);
+        let mut board = Board::default();
+        let mut player = if game % 2 == 0 {
+            Entity::Human
+        } else {
+            Entity::Computer
+        };
+        loop {
+            let Some((x, y)) = (match player {
+                Entity::Human => Some(network.best_move(board, player)),
+                _ => engine.best_play(board),
+            }) else {
+                draws += 1;
+                break;
+            };
+            board[x][y] = player;
+            if has_line(&board, player) {
+                if player == Entity::Human {
+                    wins += 1;
+                } else {
+                    losses += 1;
+                }
+                break;
+            }
+            if board.is_full() {
+                draws += 1;
+                break;
+            }
+            player = !player;
+        }
+    }
+    (wins, draws, losses)
+}
@@ -0,0 +1,17 @@
+//! What happens during play, published to whoever keeps track of it instead of being handled
+//! inline by `App::update`.
+
+use crate::{game::Game, ladder::Bot};
+
+pub enum Event<'a> {
+    /// A game ended, won or drawn.
+    Finished {
+        game: &'a Game,
+        /// The bot played against, `None` for the engine.
+        opponent: Option<Bot>,
+    },
+}
+
+pub trait Subscriber {
+    fn notify(&mut self, event: &Event);
+}
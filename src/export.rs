@@ -0,0 +1,74 @@
+use serde::Serialize;
+
+use crate::game::{Entity, Game, GameState};
+
+pub mod web;
+pub mod zip;
+
+/// A game as [`game_json`] writes it.
+#[derive(Serialize)]
+struct Exported {
+    size: usize,
+    /// How an empty cell shows.
+    empty: &'static str,
+    /// Marks placed before the first move, from a scrambled start.
+    start: Vec<ExportedMove>,
+    moves: Vec<ExportedMove>,
+    /// `None` while the game goes on.
+    result: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ExportedMove {
+    player: &'static str,
+    x: usize,
+    y: usize,
+    /// The cell the mark slid from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<[usize; 2]>,
+    /// The mark put down when it isn't the player's own, which only Notakto does.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mark: Option<&'static str>,
+}
+
+/// Serializes the moves and the result of a [`Game`] as a JSON object.
+pub fn game_json(game: &Game) -> String {
+    let start = game
+        .initial()
+        .cells()
+        .filter(|&(_, entity)| entity != Entity::Empty)
+        .map(|((x, y), entity)| ExportedMove {
+            player: entity.as_str(),
+            x,
+            y,
+            from: None,
+            mark: None,
+        })
+        .collect();
+    let moves = game
+        .moves()
+        .iter()
+        .map(|played| ExportedMove {
+            player: played.player.as_str(),
+            x: played.x,
+            y: played.y,
+            from: played.from.map(|(x, y)| [x, y]),
+            mark: (played.mark != played.player).then(|| played.mark.as_str()),
+        })
+        .collect();
+    let result = match game.state() {
+        GameState::Win(winner) => Some(format!("{} won", winner.as_str())),
+        GameState::Draw => Some("draw".to_string()),
+        GameState::Timeout(loser) => Some(format!("{} won on time", (!loser).as_str())),
+        _ => None,
+    };
+
+    serde_json::to_string(&Exported {
+        size: game.board().size(),
+        empty: Entity::Empty.as_str(),
+        start,
+        moves,
+        result,
+    })
+    .expect("a game always serializes")
+}
@@ -0,0 +1,73 @@
+use std::{fs, io, path::Path};
+
+use crate::game::Game;
+
+use super::game_json;
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Tic Tac Toe replay</title>
+<style>
+  body { background: #202225; color: #e0e0e0; font-family: sans-serif; text-align: center; }
+  #board { display: inline-grid; gap: 8px; margin: 24px; }
+  #board div { width: 64px; height: 64px; line-height: 64px; font-size: 32px; background: #2f3136; border-radius: 4px; }
+  #board div.last { outline: 2px solid #5865f2; }
+  button { margin: 0 4px; padding: 8px 16px; }
+</style>
+</head>
+<body>
+<h1>Tic Tac Toe replay</h1>
+<div id="board"></div>
+<p id="status"></p>
+<div>
+  <button id="first">&laquo;</button>
+  <button id="prev">&lsaquo;</button>
+  <button id="next">&rsaquo;</button>
+  <button id="last">&raquo;</button>
+</div>
+<script>
+const game = __GAME_JSON__;
+let ply = game.moves.length;
+document.getElementById("board").style.gridTemplateColumns = "repeat(" + game.size + ", 64px)";
+
+function render() {
+  const cells = Array.from({ length: game.size * game.size }, () => game.empty);
+  (game.start || []).forEach(m => { cells[m.x * game.size + m.y] = m.player; });
+  game.moves.slice(0, ply).forEach(m => {
+    if (m.from) cells[m.from[0] * game.size + m.from[1]] = game.empty;
+    cells[m.x * game.size + m.y] = m.mark || m.player;
+  });
+  const last = ply > 0 ? game.moves[ply - 1] : null;
+  const board = document.getElementById("board");
+  board.innerHTML = "";
+  cells.forEach((cell, i) => {
+    const div = document.createElement("div");
+    div.textContent = cell;
+    if (last && i === last.x * game.size + last.y) div.className = "last";
+    board.appendChild(div);
+  });
+  const result = ply === game.moves.length && game.result ? " - " + game.result : "";
+  document.getElementById("status").textContent = "Move " + ply + " of " + game.moves.length + result;
+}
+
+document.getElementById("first").onclick = () => { ply = 0; render(); };
+document.getElementById("prev").onclick = () => { ply = Math.max(0, ply - 1); render(); };
+document.getElementById("next").onclick = () => { ply = Math.min(game.moves.length, ply + 1); render(); };
+document.getElementById("last").onclick = () => { ply = game.moves.length; render(); };
+render();
+</script>
+</body>
+</html>
+"#;
+
+/// Builds a self-contained HTML page that replays the given [`Game`].
+pub fn render(game: &Game) -> String {
+    TEMPLATE.replace("__GAME_JSON__", &game_json(game))
+}
+
+/// Writes the interactive replay of the game into `path`.
+pub fn export(game: &Game, path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, render(game))
+}
@@ -0,0 +1,86 @@
+use std::{fs, io, path::Path};
+
+/// Signatures of the records of a zip archive.
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+
+/// Version 2.0 of the format, the one every unzip reads.
+const VERSION: u16 = 20;
+
+/// The names of the files are in UTF-8.
+const UTF8_NAMES: u16 = 1 << 11;
+
+/// 1980-01-01 at midnight, the earliest date the format knows, for every file.
+const DOS_TIME: u16 = 0;
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Writes `files`, each a name and its contents, to a zip archive at `path`. The files are
+/// stored as they are, the few KiB they weigh not worth compressing.
+pub fn write(path: &Path, files: &[(&str, Vec<u8>)]) -> io::Result<()> {
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "too large for a zip");
+    let mut archive = vec![];
+    let mut directory = vec![];
+    for (name, contents) in files {
+        let offset = u32::try_from(archive.len()).map_err(|_| too_large())?;
+        let size = u32::try_from(contents.len()).map_err(|_| too_large())?;
+        let name_length = u16::try_from(name.len()).map_err(|_| too_large())?;
+        let crc = crc32fast::hash(contents);
+
+        push32(&mut archive, LOCAL_HEADER);
+        push16(&mut archive, VERSION);
+        describe(&mut archive, crc, size, name_length);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(contents);
+
+        push32(&mut directory, CENTRAL_HEADER);
+        push16(&mut directory, VERSION);
+        push16(&mut directory, VERSION);
+        describe(&mut directory, crc, size, name_length);
+        // No comment, on the first disk, without attributes.
+        push16(&mut directory, 0);
+        push16(&mut directory, 0);
+        push16(&mut directory, 0);
+        push32(&mut directory, 0);
+        push32(&mut directory, offset);
+        directory.extend_from_slice(name.as_bytes());
+    }
+    let entries = u16::try_from(files.len()).map_err(|_| too_large())?;
+    let directory_offset = u32::try_from(archive.len()).map_err(|_| too_large())?;
+    let directory_size = u32::try_from(directory.len()).map_err(|_| too_large())?;
+    archive.extend_from_slice(&directory);
+    push32(&mut archive, END_OF_CENTRAL_DIRECTORY);
+    // A single disk.
+    push16(&mut archive, 0);
+    push16(&mut archive, 0);
+    push16(&mut archive, entries);
+    push16(&mut archive, entries);
+    push32(&mut archive, directory_size);
+    push32(&mut archive, directory_offset);
+    // No comment.
+    push16(&mut archive, 0);
+    fs::write(path, archive)
+}
+
+/// The fields local and central headers share, from the flags to the length of the extra
+/// field.
+fn describe(bytes: &mut Vec<u8>, crc: u32, size: u32, name_length: u16) {
+    push16(bytes, UTF8_NAMES);
+    // Stored, not compressed.
+    push16(bytes, 0);
+    push16(bytes, DOS_TIME);
+    push16(bytes, DOS_DATE);
+    push32(bytes, crc);
+    push32(bytes, size);
+    push32(bytes, size);
+    push16(bytes, name_length);
+    push16(bytes, 0);
+}
+
+fn push16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
@@ -0,0 +1,50 @@
+//! The optional parts of the app, picked with cargo features when building. The screens check
+//! these to hide what the build lacks rather than offering options which do nothing.
+
+/// Screenshots, result cards and `--render`.
+pub const RENDER: bool = cfg!(feature = "render");
+
+/// The `tablebase` command and the tablebases looked up by the engine.
+pub const TABLEBASES: bool = cfg!(feature = "tablebases");
+
+/// Chat plays over Twitch.
+pub const TWITCH: bool = cfg!(feature = "twitch");
+
+/// The key bringing the window to the front from any app.
+pub const HOTKEY: bool = cfg!(feature = "hotkey");
+
+/// Voice commands heard by a local speech recognizer.
+pub const VOICE: bool = cfg!(feature = "voice");
+
+/// Sound cues of the clock.
+pub const SOUND: bool = cfg!(feature = "sound");
+
+/// The opponent playing by a learned value network, and the `train` command.
+pub const RL: bool = cfg!(feature = "rl");
+
+/// The names of the features this build has, e.g. for bug reports.
+pub fn enabled() -> Vec<&'static str> {
+    [
+        ("render", RENDER),
+        ("tablebases", TABLEBASES),
+        ("twitch", TWITCH),
+        ("hotkey", HOTKEY),
+        ("voice", VOICE),
+        ("sound", SOUND),
+        ("rl", RL),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
+
+/// The feature the command line argument `arg` needs, when the build lacks it.
+pub fn missing_for(arg: &str) -> Option<&'static str> {
+    match arg {
+        "--render" | "--scale" if !RENDER => Some("render"),
+        "tablebase" if !TABLEBASES => Some("tablebases"),
+        "train" if !RL => Some("rl"),
+        "--twitch" if !TWITCH => Some("twitch"),
+        _ => None,
+    }
+}
@@ -0,0 +1,158 @@
+//! The feedback dialog: a description of the problem and, with the consent of the human for
+//! each of them, the recent logs, the settings and the game on the board, packed into a zip to
+//! save or attach to an email.
+
+use std::path::Path;
+
+use iced::{
+    widget::{column, row, text, text_input},
+    Alignment, Element,
+};
+
+use crate::{
+    export, features,
+    game::Game,
+    i18n::I18n,
+    json_log,
+    settings::Settings,
+    ui::{
+        components::{primary, secondary, toggle},
+        focus::focusable,
+        tokens::{SPACE_M, TEXT_TITLE},
+    },
+    Message,
+};
+
+/// What the feedback may include besides its description, each only once allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+    /// The last lines of the JSON log, names redacted.
+    Logs,
+    Settings,
+    /// The moves of the game on the board.
+    Replay,
+}
+
+impl Item {
+    pub const ALL: [Item; 3] = [Item::Logs, Item::Settings, Item::Replay];
+
+    /// Id of the translated label of its checkbox.
+    fn label(self) -> &'static str {
+        match self {
+            Item::Logs => "feedback-logs",
+            Item::Settings => "feedback-settings",
+            Item::Replay => "feedback-replay",
+        }
+    }
+}
+
+/// The feedback being written.
+#[derive(Debug, Default)]
+pub struct Feedback {
+    pub description: String,
+    /// The items allowed in, none until checked.
+    included: Vec<Item>,
+}
+
+impl Feedback {
+    pub fn includes(&self, item: Item) -> bool {
+        self.included.contains(&item)
+    }
+
+    pub fn set(&mut self, item: Item, included: bool) {
+        self.included.retain(|&other| other != item);
+        if included {
+            self.included.push(item);
+        }
+    }
+
+    /// Writes the zip to `path`: the description, the items allowed in, and the version of
+    /// the app and the OS.
+    pub fn save(&self, path: &Path, settings: &Settings, game: &Game) -> std::io::Result<()> {
+        let about = format!(
+            "tic-tac-toe-iced {}\n{} {}\nfeatures: {}\n",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            features::enabled().join(", ")
+        );
+        let mut files = vec![("about.txt", about.into_bytes())];
+        if !self.description.trim().is_empty() {
+            files.push(("description.txt", self.description.clone().into_bytes()));
+        }
+        if self.includes(Item::Logs) {
+            files.push(("log.jsonl", json_log::recent().into_bytes()));
+        }
+        if self.includes(Item::Settings) {
+            // The name is the human's, not something the settings need to reproduce a bug.
+            let mut settings = settings.clone();
+            if !settings.player.name.is_empty() {
+                settings.player.name = "[redacted]".to_string();
+            }
+            let snapshot = toml::to_string_pretty(&settings)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            files.push(("settings.toml", snapshot.into_bytes()));
+        }
+        if self.includes(Item::Replay) {
+            files.push(("replay.json", export::game_json(game).into_bytes()));
+        }
+        export::zip::write(path, &files)
+    }
+
+    pub fn view<'a>(&self, i18n: &I18n) -> Element<'a, Message> {
+        let items = Item::ALL.into_iter().map(|item| {
+            toggle(
+                i18n.tr(item.label()),
+                self.includes(item),
+                move |included| Message::FeedbackConsent(item, included),
+            )
+        });
+        column![
+            text(i18n.tr("feedback")).size(TEXT_TITLE),
+            text(i18n.tr("feedback-intro")),
+            text_input(&i18n.tr("feedback-description"), &self.description)
+                .on_input(Message::FeedbackDescription)
+                .width(500),
+            column(items.collect()).spacing(SPACE_M),
+            text(i18n.tr("feedback-about")),
+            row![
+                focusable(primary(i18n.tr("feedback-save")).on_press(Message::SaveFeedback)),
+                focusable(secondary(i18n.tr("feedback-email")).on_press(Message::EmailFeedback)),
+                focusable(secondary(i18n.tr("back")).on_press(Message::ToggleFeedback))
+            ]
+            .spacing(SPACE_M)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M)
+        .into()
+    }
+}
+
+/// Opens a new email in the mail app with `subject` and `body`, which can't attach the zip
+/// itself.
+pub fn email(subject: &str, body: &str) -> std::io::Result<()> {
+    let url = format!("mailto:?subject={}&body={}", encode(subject), encode(body));
+    let mut opener = if cfg!(target_os = "windows") {
+        // `start` through cmd would cut the URL at its `&`.
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    opener.arg(url).spawn().map(drop)
+}
+
+/// `text` percent-encoded for a URL, every byte but letters, digits and `-._~` escaped.
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
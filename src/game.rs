@@ -1,4 +1,29 @@
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+pub mod book;
+pub mod cube;
+pub mod order_chaos;
+pub mod pentago;
+pub mod side;
+#[cfg(feature = "tablebases")]
+pub mod tablebase;
+pub mod ultimate;
+
+#[cfg(feature = "tablebases")]
+use tablebase::Tablebase;
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Entity {
     #[default]
     Empty, // ""
@@ -14,40 +39,938 @@ pub enum GameState {
     Ready,
     /// Players movements.
     Playing(Entity),
-    /// If the player select an incorrect cell, this member is used.
-    /// So the game understands which entity use in next turn.
-    Repeat(Entity),
     /// Only for finals (Someone win | Draw)
     Win(Entity),
     Draw,
+    /// The clock of this player ran out, they lost.
+    Timeout(Entity),
+}
+
+/// What completing a line does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rules {
+    /// Completing a line wins.
+    #[default]
+    Classic,
+    /// Completing a line loses.
+    Misere,
+    /// Both sides play X and completing a line loses.
+    Notakto,
+}
+
+impl Rules {
+    pub const ALL: [Rules; 3] = [Rules::Classic, Rules::Misere, Rules::Notakto];
+
+    /// Id of the translated description of the rules.
+    pub fn label(self) -> &'static str {
+        match self {
+            Rules::Classic => "rules-classic",
+            Rules::Misere => "rules-misere",
+            Rules::Notakto => "rules-notakto",
+        }
+    }
+
+    /// Who wins once `entity` completes a line.
+    pub fn winner(self, entity: Entity) -> Entity {
+        match self {
+            Rules::Classic => entity,
+            Rules::Misere | Rules::Notakto => !entity,
+        }
+    }
+
+    /// The mark `entity` plays.
+    pub fn mark(self, entity: Entity) -> Entity {
+        match self {
+            Rules::Notakto => Entity::Computer,
+            _ => entity,
+        }
+    }
 }
 
-#[derive(Default)]
 pub struct Game {
     board: Board,
+    rules: Rules,
     state: GameState,
+    /// The side which plays the first move.
+    starter: Entity,
+    /// The board before the first move, with the marks of a scrambled start if any.
+    initial: Board,
+    /// Every accepted move, in the order it was played, the sides taking turns.
+    moves: Vec<Move>,
+    /// Moves taken back by [`Game::undo`], the last one first to be played again.
+    undone: Vec<Move>,
+    /// Time left to each side, in timed games.
+    clock: Option<Clock>,
+}
+
+/// The countdowns of a timed game, only the one of the side to move runs.
+#[derive(Debug, Clone, Copy)]
+struct Clock {
+    human: Duration,
+    computer: Duration,
+    /// When the clock of the side to move started running, `None` until the game starts.
+    since: Option<Instant>,
+}
+
+impl Clock {
+    fn left(&mut self, entity: Entity) -> &mut Duration {
+        match entity {
+            Entity::Human => &mut self.human,
+            _ => &mut self.computer,
+        }
+    }
+
+    /// Time left to `entity` at `now`, `running` when it's their turn.
+    fn remaining(mut self, entity: Entity, running: bool, now: Instant) -> Duration {
+        let elapsed = match self.since {
+            Some(since) if running => now.saturating_duration_since(since),
+            _ => Duration::ZERO,
+        };
+        self.left(entity).saturating_sub(elapsed)
+    }
+
+    /// Stops the clock of `entity` at `now` and starts the other one.
+    fn press(&mut self, entity: Entity, now: Instant) {
+        *self.left(entity) = self.remaining(entity, true, now);
+        self.since = Some(now);
+    }
+}
+
+/// A move accepted by a [`Game`], `player` putting `mark` on `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub player: Entity,
+    /// The mark put down, the same as `player` but under Notakto, where both sides play X.
+    pub mark: Entity,
+    /// The cell the mark slid from, `None` when it was placed.
+    pub from: Option<(usize, usize)>,
+    pub x: usize,
+    pub y: usize,
+    /// State of the game once the move was played.
+    pub state: GameState,
+    /// The cells the move changed, undone and redone as they are.
+    pub changes: Vec<CellChange>,
+}
+
+/// Which way a line runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Along a row, left to right.
+    Row,
+    /// Along a column, top to bottom.
+    Column,
+    /// From the top left towards the bottom right.
+    Diagonal,
+    /// From the top right towards the bottom left.
+    AntiDiagonal,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Row,
+        Direction::Column,
+        Direction::Diagonal,
+        Direction::AntiDiagonal,
+    ];
+
+    /// The change of `(x, y)` from a cell of the line to the next.
+    pub fn step(self) -> (isize, isize) {
+        match self {
+            Direction::Row => (0, 1),
+            Direction::Column => (1, 0),
+            Direction::Diagonal => (1, 1),
+            Direction::AntiDiagonal => (1, -1),
+        }
+    }
+}
+
+/// The marks in a row which ended a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WinLine {
+    /// The first cell of the line.
+    pub start: (usize, usize),
+    pub direction: Direction,
+    /// How many marks the line has, at least the length needed to win.
+    pub length: usize,
+}
+
+impl WinLine {
+    pub fn cells(self) -> impl Iterator<Item = (usize, usize)> {
+        let (dx, dy) = self.direction.step();
+        (0..self.length as isize).map(move |i| {
+            (
+                (self.start.0 as isize + dx * i) as usize,
+                (self.start.1 as isize + dy * i) as usize,
+            )
+        })
+    }
+
+    pub fn contains(self, cell: (usize, usize)) -> bool {
+        self.cells().any(|c| c == cell)
+    }
+}
+
+/// What a move led to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveOutcome {
+    /// The game goes on, with this player to move.
+    Next(Entity),
+    Won(Entity),
+    Draw,
+}
+
+/// Why a move was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The cell isn't on the board.
+    OutOfBounds,
+    CellOccupied,
+    GameFinished,
+    /// With gravity, the cell below is still empty.
+    Floating,
+    /// Every mark of the player is placed, they have to slide one instead.
+    MustSlide,
+    /// The mark can't slide there, or the player still has marks to place.
+    IllegalSlide,
+    /// In Ultimate, the small board is closed or isn't the one the move must be played in.
+    ClosedBoard,
+    /// In Pentago, the mark of the turn is placed, a quadrant has to be rotated.
+    MustRotate,
+    /// In Pentago, a mark has to be placed before rotating.
+    MustPlace,
+}
+
+impl MoveError {
+    /// Key of the message explaining the error.
+    pub fn label(self) -> &'static str {
+        match self {
+            MoveError::OutOfBounds => "move-out-of-bounds",
+            MoveError::CellOccupied => "move-cell-occupied",
+            MoveError::GameFinished => "move-game-finished",
+            MoveError::Floating => "move-floating",
+            MoveError::MustSlide => "move-must-slide",
+            MoveError::IllegalSlide => "move-illegal-slide",
+            MoveError::ClosedBoard => "move-closed-board",
+            MoveError::MustRotate => "move-must-rotate",
+            MoveError::MustPlace => "move-must-place",
+        }
+    }
+}
+
+/// Clones share the same transposition table, so a search running in the background warms it
+/// up for the next move.
+#[derive(Clone)]
+pub struct Computer {
+    /// Transposition table, positions already searched during the current game, keyed on
+    /// their canonical board.
+    table: Arc<Mutex<LruCache<(Board, Entity), Entry>>>,
+    /// Raised to abort the search, only set on the copies which ponder.
+    cancel: Option<Arc<AtomicBool>>,
+    rules: Rules,
+    /// Picks among the moves which are equally good, shared by the copies.
+    rng: Arc<Mutex<StdRng>>,
+    /// Percent of its moves the computer plays worse than it could, on purpose.
+    mistake_rate: u32,
+    weights: Weights,
+    /// Solved boards, looked up instead of searched.
+    #[cfg(feature = "tablebases")]
+    tablebases: Arc<Vec<Tablebase>>,
+}
+
+/// What the engine expects the human to play next, and its answer to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prediction {
+    /// The position the human is thinking about.
+    pub board: Board,
+    pub human: (usize, usize),
+    pub reply: (usize, usize),
+}
+
+/// A move of the human and the computer's score for it, with best play from both sides after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveScore {
+    pub cell: (usize, usize),
+    pub score: i32,
+}
+
+impl MoveScore {
+    /// Below zero when the human wins, above zero when the computer does, zero otherwise.
+    pub fn outcome(&self) -> i32 {
+        outcome(self.score)
+    }
+}
+
+/// How the game ends after each mark the human may place on a board, searched once to judge
+/// whichever they play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcomes(Vec<MoveScore>);
+
+impl Outcomes {
+    /// The scores the outcomes come from, in the order the engine searched them.
+    pub fn scores(&self) -> &[MoveScore] {
+        &self.0
+    }
+
+    fn of(&self, cell: (usize, usize)) -> Option<i32> {
+        self.0
+            .iter()
+            .find(|score| score.cell == cell)
+            .map(MoveScore::outcome)
+    }
+
+    /// Whether playing `cell` keeps the best result the board allows, winning, drawing or
+    /// losing, however fast.
+    pub fn is_best(&self, cell: (usize, usize)) -> bool {
+        self.of(cell) == self.0.iter().map(MoveScore::outcome).min()
+    }
+
+    /// Whether playing `cell` loses by force while another move doesn't.
+    pub fn is_blunder(&self, cell: (usize, usize)) -> bool {
+        self.of(cell).is_some_and(|outcome| outcome > 0)
+            && self.0.iter().any(|score| score.outcome() <= 0)
+    }
+}
+
+/// Plies of a principal variation shown to the human, enough for a threat and its follow-up.
+const LINE_LENGTH: usize = 4;
+
+/// How a position ends with best play from both sides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Assessment {
+    /// `None` for a draw, or when no side forces a win within the horizon.
+    pub winner: Option<Entity>,
+    /// The best move of the side to play, `None` when the game is over.
+    pub best: Option<(usize, usize)>,
+    /// How each move of the side to play ends, in the same terms as `winner`.
+    pub moves: Vec<((usize, usize), Option<Entity>)>,
+    /// Every line was searched to the end, so the results are known rather than estimated.
+    pub exact: bool,
+}
+
+/// Memory given to the transposition table unless configured otherwise.
+pub const DEFAULT_TABLE_MEMORY: usize = 1 << 20;
+
+/// Rough memory footprint of one table entry, including the bookkeeping of the LRU list.
+const ENTRY_SIZE: usize = std::mem::size_of::<(Board, Entity)>()
+    + std::mem::size_of::<Entry>()
+    + 4 * std::mem::size_of::<usize>();
+
+/// Memory taken by the caches of the engine, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    /// The positions of the transposition table, roughly.
+    pub table: usize,
+    /// The most the table takes before evicting positions.
+    pub table_limit: usize,
+    /// The results of the tablebases in use.
+    pub tablebases: usize,
+}
+
+/// Scores beyond this are games decided with best play, within it estimates of positions past
+/// the horizon.
+const DECISIVE: i32 = i32::MAX / 2;
+
+/// The score of `child` for the position it was reached from, a move further from the win or
+/// loss it announces so the fastest win and the slowest loss score the best.
+fn from_child(child: i32) -> i32 {
+    if child > DECISIVE {
+        child - 1
+    } else if child < -DECISIVE {
+        child + 1
+    } else {
+        child
+    }
+}
+
+/// The bound `bound` of a position as seen from the position after a move, the reverse of
+/// [`from_child`].
+fn to_child(bound: i32) -> i32 {
+    if bound > DECISIVE {
+        bound.saturating_add(1)
+    } else if bound < -DECISIVE {
+        bound.saturating_sub(1)
+    } else {
+        bound
+    }
+}
+
+/// Estimate of a position past the horizon which is all but decided, above any sum of runs.
+const NEARLY_DECIDED: i32 = 1 << 24;
+
+/// Worth of a row, column or diagonal open to one side only, by how many of its marks it holds.
+const RUN_WORTH: [i32; MAX_SIZE] = [0, 1, 4, 16, 64, 256];
+
+/// Highest worth of a run, keeping the sum of every run of the board below [`NEARLY_DECIDED`].
+pub const MAX_RUN_WORTH: i32 = 1 << 16;
+
+/// What the estimate of a position past the horizon makes of it, as adjusted by the `tune`
+/// command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Weights {
+    /// Worth of a row, column or diagonal open to one side only, by how many of its marks it
+    /// holds.
+    pub runs: [i32; MAX_SIZE],
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights { runs: RUN_WORTH }
+    }
+}
+
+impl Weights {
+    /// Brings the worth of each run back within `0..=MAX_RUN_WORTH`, e.g. after the file was
+    /// edited by hand.
+    pub fn validate(&mut self) {
+        for worth in &mut self.runs {
+            *worth = (*worth).clamp(0, MAX_RUN_WORTH);
+        }
+    }
+}
+
+/// Whether a score means a win for the computer (1), for the human (-1) or neither (0).
+fn outcome(score: i32) -> i32 {
+    if score > DECISIVE {
+        1
+    } else if score < -DECISIVE {
+        -1
+    } else {
+        0
+    }
+}
+
+/// A move searched by the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Place(usize, usize),
+    Slide((usize, usize), (usize, usize)),
+}
+
+impl Action {
+    /// Whether the move places a mark on `cell`, or slides one from or to it.
+    pub fn touches(self, cell: (usize, usize)) -> bool {
+        match self {
+            Action::Place(x, y) => (x, y) == cell,
+            Action::Slide(from, to) => from == cell || to == cell,
+        }
+    }
+
+    /// The cell of a placed mark, e.g. `b2`, or both cells of a slide, e.g. `a1-b2`.
+    pub fn name(self) -> String {
+        match self {
+            Action::Place(x, y) => cell_name(x, y),
+            Action::Slide((fx, fy), (x, y)) => format!("{}-{}", cell_name(fx, fy), cell_name(x, y)),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Bound {
+    Exact,
+    /// The search was cut off, the score is at least this.
+    Lower,
+    /// Every move failed low, the score is at most this.
+    Upper,
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+    score: i32,
+    bound: Bound,
+    /// How many moves deep the position was searched.
+    remaining: usize,
+}
+
+/// Largest board the game supports.
+pub const MAX_SIZE: usize = 6;
+
+/// A square grid of cells, indexed as `board[x][y]` with `x` the row and `y` the column.
+///
+/// The cells live in an array big enough for every size so boards stay `Copy`, the ones
+/// outside of the board are always empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Board {
+    size: usize,
+    /// How many marks in a row make a line.
+    line: usize,
+    /// Marks fall to the lowest empty cell of their column, the last row being the bottom.
+    gravity: bool,
+    /// Marks each side places before moving them by sliding, as in Three Men's Morris.
+    pieces: Option<usize>,
+    cells: [[Entity; MAX_SIZE]; MAX_SIZE],
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new(3)
+    }
+}
+
+impl Board {
+    /// An empty board whose lines span a whole side.
+    pub fn new(size: usize) -> Board {
+        assert!(
+            (1..=MAX_SIZE).contains(&size),
+            "boards are 1 to {} cells wide",
+            MAX_SIZE
+        );
+        Board {
+            size,
+            line: size,
+            gravity: false,
+            pieces: None,
+            cells: Default::default(),
+        }
+    }
+
+    /// The same board where `line` marks in a row are enough, a whole side at most.
+    pub fn with_line(mut self, line: usize) -> Board {
+        self.line = line.clamp(1, self.size);
+        self
+    }
+
+    /// The same board where marks fall down their column.
+    pub fn with_gravity(mut self, gravity: bool) -> Board {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn has_gravity(&self) -> bool {
+        self.gravity
+    }
+
+    /// The same board where each side places at most `pieces` marks, then slides them to an
+    /// adjacent cell instead.
+    pub fn with_pieces(mut self, pieces: Option<usize>) -> Board {
+        self.pieces = pieces;
+        self
+    }
+
+    pub fn pieces(&self) -> Option<usize> {
+        self.pieces
+    }
+
+    /// Whether `entity` placed all its marks and moves by sliding them.
+    pub fn must_slide(&self, entity: Entity) -> bool {
+        self.pieces.is_some_and(|pieces| {
+            self.cells().filter(|(_, mark)| *mark == entity).count() >= pieces
+        })
+    }
+
+    /// Every slide of a mark of `entity` to an adjacent empty cell, as `(from, to)`. Marks move
+    /// along rows and columns, and diagonally along the two main diagonals only.
+    pub fn slides(&self, entity: Entity) -> Vec<((usize, usize), (usize, usize))> {
+        let last = self.size - 1;
+        let mut slides = vec![];
+        for ((x, y), mark) in self.cells() {
+            if mark != entity {
+                continue;
+            }
+            for (tx, ty) in self
+                .cells()
+                .filter(|(_, mark)| *mark == Entity::Empty)
+                .map(|(cell, _)| cell)
+            {
+                let (dx, dy) = (x.abs_diff(tx), y.abs_diff(ty));
+                let adjacent = match (dx, dy) {
+                    (0, 1) | (1, 0) => true,
+                    (1, 1) => (x == y && tx == ty) || (x + y == last && tx + ty == last),
+                    _ => false,
+                };
+                if adjacent {
+                    slides.push(((x, y), (tx, ty)));
+                }
+            }
+        }
+        slides
+    }
+
+    /// Whether a mark can be played at `(x, y)`: the cell is empty and, with gravity, it
+    /// rests on the bottom or on another mark.
+    pub fn is_playable(&self, x: usize, y: usize) -> bool {
+        self[x][y] == Entity::Empty
+            && (!self.gravity || x + 1 == self.size || self[x + 1][y] != Entity::Empty)
+    }
+
+    /// Every cell where a mark can be played.
+    pub fn playable(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells()
+            .map(|(cell, _)| cell)
+            .filter(|&(x, y)| self.is_playable(x, y))
+    }
+
+    /// The board with each of `marks` in turn on a random playable cell where it doesn't
+    /// complete a line. Marks with no such cell left are skipped.
+    pub fn scrambled(mut self, marks: &[Entity], rng: &mut impl Rng) -> Board {
+        for &mark in marks {
+            let cells: Vec<_> = self
+                .playable()
+                .filter(|&(x, y)| {
+                    let mut after = self;
+                    after[x][y] = mark;
+                    !after.has_line_through(mark, x, y)
+                })
+                .collect();
+            if let Some(&(x, y)) = cells.choose(rng) {
+                self[x][y] = mark;
+            }
+        }
+        self
+    }
+
+    /// The row a mark dropped in `column` lands on, `None` when the column is full.
+    pub fn drop_row(&self, column: usize) -> Option<usize> {
+        (0..self.size)
+            .rev()
+            .find(|&x| self[x][column] == Entity::Empty)
+    }
+
+    /// Number of cells on each side.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// How many marks in a row make a line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Length of the run of `entity` through `(x, y)` along `(dx, dy)` and its opposite.
+    fn run(&self, entity: Entity, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> usize {
+        let count = |dx: isize, dy: isize| {
+            (1..)
+                .map(|i| (x as isize + dx * i, y as isize + dy * i))
+                .take_while(|&(x, y)| {
+                    (0..self.size as isize).contains(&x)
+                        && (0..self.size as isize).contains(&y)
+                        && self[x as usize][y as usize] == entity
+                })
+                .count()
+        };
+        1 + count(dx, dy) + count(-dx, -dy)
+    }
+
+    /// Whether the mark of `entity` at `(x, y)` is part of a line.
+    pub fn has_line_through(&self, entity: Entity, x: usize, y: usize) -> bool {
+        self.line_through(entity, x, y).is_some()
+    }
+
+    /// The line the mark of `entity` at `(x, y)` is part of, the whole run of marks when it's
+    /// longer than needed.
+    pub fn line_through(&self, entity: Entity, x: usize, y: usize) -> Option<WinLine> {
+        if self[x][y] != entity {
+            return None;
+        }
+        Direction::ALL.into_iter().find_map(|direction| {
+            let (dx, dy) = direction.step();
+            let length = self.run(entity, (x, y), (dx, dy));
+            if length < self.line {
+                return None;
+            }
+            // Walk back to the first mark of the run.
+            let mut start = (x as isize, y as isize);
+            while (0..self.size as isize).contains(&(start.0 - dx))
+                && (0..self.size as isize).contains(&(start.1 - dy))
+                && self[(start.0 - dx) as usize][(start.1 - dy) as usize] == entity
+            {
+                start = (start.0 - dx, start.1 - dy);
+            }
+            Some(WinLine {
+                start: (start.0 as usize, start.1 as usize),
+                direction,
+                length,
+            })
+        })
+    }
+
+    /// Every stretch of [`Board::line`] cells along a row, column or diagonal, i.e. everywhere a
+    /// line could be made.
+    pub fn windows(&self) -> impl Iterator<Item = WinLine> + '_ {
+        let size = self.size as isize;
+        let length = self.line;
+        (0..self.size)
+            .flat_map(move |x| (0..self.size).map(move |y| (x, y)))
+            .flat_map(move |start| {
+                Direction::ALL.into_iter().filter_map(move |direction| {
+                    let (dx, dy) = direction.step();
+                    let last = length as isize - 1;
+                    let end = (start.0 as isize + dx * last, start.1 as isize + dy * last);
+                    ((0..size).contains(&end.0) && (0..size).contains(&end.1)).then_some(WinLine {
+                        start,
+                        direction,
+                        length,
+                    })
+                })
+            })
+    }
+
+    /// Whether `mark` can still make a line by placing at most `moves` more marks, i.e. some
+    /// window holds no other mark and no more empty cells than that.
+    pub fn can_complete(&self, mark: Entity, moves: usize) -> bool {
+        self.windows().any(|window| {
+            let mut empty = 0;
+            for (x, y) in window.cells() {
+                match self[x][y] {
+                    Entity::Empty => empty += 1,
+                    other if other != mark => return false,
+                    _ => {}
+                }
+            }
+            empty <= moves
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Entity]> {
+        self.cells[..self.size].iter().map(|row| &row[..self.size])
+    }
+
+    /// Every cell with its `(x, y)` coordinates, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), Entity)> + '_ {
+        self.rows().enumerate().flat_map(|(x, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(y, entity)| ((x, y), *entity))
+        })
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.cells().all(|(_, entity)| entity != Entity::Empty)
+    }
+
+    /// The board with the marks of the human and the computer exchanged, for the computer's
+    /// players to pick moves for the human's side.
+    pub fn swapped(&self) -> Board {
+        let mut swapped = *self;
+        for ((x, y), entity) in self.cells() {
+            swapped.cells[x][y] = match entity {
+                Entity::Human => Entity::Computer,
+                Entity::Computer => Entity::Human,
+                Entity::Empty => Entity::Empty,
+            };
+        }
+        swapped
+    }
+
+    /// The same position whichever way the board is turned: the smallest of its rotations and
+    /// reflections. Under gravity only the left-right mirror keeps marks falling the same way.
+    pub fn canonical(&self) -> Board {
+        type Symmetry = fn(usize, usize, usize) -> (usize, usize);
+        let symmetries: &[Symmetry] = if self.gravity {
+            &[|x, y, _| (x, y), |x, y, last| (x, last - y)]
+        } else {
+            &[
+                |x, y, _| (x, y),
+                |x, y, last| (y, last - x),
+                |x, y, last| (last - x, last - y),
+                |x, y, last| (last - y, x),
+                |x, y, last| (x, last - y),
+                |x, y, last| (last - x, y),
+                |x, y, _| (y, x),
+                |x, y, last| (last - y, last - x),
+            ]
+        };
+        let last = self.size - 1;
+        symmetries
+            .iter()
+            .map(|symmetry| {
+                let mut image = *self;
+                for ((x, y), entity) in self.cells() {
+                    let (x, y) = symmetry(x, y, last);
+                    image.cells[x][y] = entity;
+                }
+                image
+            })
+            .min_by(|a, b| a.cells.cmp(&b.cells))
+            .expect("the identity is always there")
+    }
+}
+
+impl std::ops::Index<usize> for Board {
+    type Output = [Entity];
+
+    fn index(&self, x: usize) -> &[Entity] {
+        &self.cells[..self.size][x][..self.size]
+    }
+}
+
+impl std::ops::IndexMut<usize> for Board {
+    fn index_mut(&mut self, x: usize) -> &mut [Entity] {
+        &mut self.cells[..self.size][x][..self.size]
+    }
+}
+
+/// Names a cell using a column letter and a row number, e.g. `b2` for the center of a 3×3
+/// board.
+pub fn cell_name(x: usize, y: usize) -> String {
+    format!("{}{}", (b'a' + y as u8) as char, x + 1)
+}
+
+/// Parses a cell name such as `a1` or `C3` back into `(x, y)` coordinates. Any cell of the
+/// biggest board is accepted, callers check it fits theirs.
+pub fn parse_cell(name: &str) -> Option<(usize, usize)> {
+    let mut chars = name.chars();
+    let column = (chars.next()?.to_ascii_lowercase() as usize).checked_sub('a' as usize)?;
+    let row = (chars.next()?.to_digit(10)? as usize).checked_sub(1)?;
+    if chars.next().is_some() || column >= MAX_SIZE || row >= MAX_SIZE {
+        return None;
+    }
+    Some((row, column))
+}
+
+/// A cell which differs between two boards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub x: usize,
+    pub y: usize,
+    pub from: Entity,
+    pub to: Entity,
+}
+
+impl CellChange {
+    /// The change undoing this one.
+    pub fn reverse(self) -> CellChange {
+        CellChange {
+            from: self.to,
+            to: self.from,
+            ..self
+        }
+    }
 }
 
-#[derive(Default)]
-pub struct Computer;
+/// Why changes don't apply to a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffError {
+    /// The changed cell isn't on the board.
+    OutOfBounds(usize, usize),
+    /// The cell doesn't hold what the change starts from.
+    Mismatch(usize, usize),
+}
+
+/// Minimal updates between boards, to transfer or step through positions without copying
+/// whole boards.
+pub trait Diff {
+    /// The cells to change to turn `self` into `other`.
+    fn diff(&self, other: &Self) -> Vec<CellChange>;
+
+    /// Applies `changes`, the board is left untouched when one of them doesn't.
+    fn apply_diff(&mut self, changes: &[CellChange]) -> Result<(), DiffError>;
+}
 
-pub type Board = [[Entity; 3]; 3];
+impl Diff for Board {
+    fn diff(&self, other: &Board) -> Vec<CellChange> {
+        let mut changes = vec![];
+        for x in 0..self.size.max(other.size) {
+            for y in 0..self.size.max(other.size) {
+                if self.cells[x][y] != other.cells[x][y] {
+                    changes.push(CellChange {
+                        x,
+                        y,
+                        from: self.cells[x][y],
+                        to: other.cells[x][y],
+                    });
+                }
+            }
+        }
+        changes
+    }
+
+    fn apply_diff(&mut self, changes: &[CellChange]) -> Result<(), DiffError> {
+        for &CellChange { x, y, from, .. } in changes {
+            if x >= self.size || y >= self.size {
+                return Err(DiffError::OutOfBounds(x, y));
+            }
+            if self[x][y] != from {
+                return Err(DiffError::Mismatch(x, y));
+            }
+        }
+        for change in changes {
+            self[change.x][change.y] = change.to;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `entity` has a line anywhere on `board`, i.e. [`Board::line`] marks in a row,
+/// column or diagonal.
+pub fn has_line(board: &Board, entity: Entity) -> bool {
+    board
+        .cells()
+        .any(|((x, y), _)| board.has_line_through(entity, x, y))
+}
+
+/// The moves which win the game right away for `entity`.
+pub fn winning_moves(board: &Board, entity: Entity) -> Vec<(usize, usize)> {
+    let mut moves = vec![];
+    for (x, y) in board.playable() {
+        let mut after = *board;
+        after[x][y] = entity;
+        if has_line(&after, entity) {
+            moves.push((x, y));
+        }
+    }
+    moves
+}
 
 impl Game {
-    pub fn reset(&self) -> Game {
-        Game::default()
+    /// A game starting on `board`, which should be empty. The human plays first.
+    pub fn new(board: Board, rules: Rules) -> Game {
+        Game {
+            board,
+            rules,
+            state: GameState::default(),
+            starter: Entity::Human,
+            initial: board,
+            moves: vec![],
+            undone: vec![],
+            clock: None,
+        }
+    }
+
+    /// Lets `starter` play the first move instead of the human.
+    pub fn with_starter(mut self, starter: Entity) -> Game {
+        self.starter = starter;
+        self
+    }
+
+    pub fn starter(&self) -> Entity {
+        self.starter
+    }
+
+    /// The board before the first move.
+    pub fn initial(&self) -> &Board {
+        &self.initial
+    }
+
+    /// A random board to [`Game::start`] from: a mark of the side which doesn't open, and
+    /// half of the time one of the opener too, so the opener still moves first and is never
+    /// a mark behind.
+    pub fn scrambled_start(&self, rng: &mut impl Rng) -> Board {
+        let mut sides = vec![!self.starter];
+        if rng.gen() {
+            sides.push(self.starter);
+        }
+        let marks: Vec<_> = sides
+            .into_iter()
+            .map(|side| self.rules.mark(side))
+            .collect();
+        self.initial.scrambled(&marks, rng)
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
     }
 
     pub fn board(&self) -> &Board {
         &self.board
     }
 
-    fn is_valid_position(&self, x: usize, y: usize) -> bool {
-        self.board[x][y] == Entity::Empty
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
     }
 
-    fn update_board(&mut self, entity: Entity, x: usize, y: usize) {
-        self.board[x][y] = entity
+    fn is_valid_position(&self, x: usize, y: usize) -> bool {
+        self.board.is_playable(x, y)
     }
 
     fn set_state(&mut self, state: GameState) {
@@ -58,91 +981,648 @@ impl Game {
         self.state.clone()
     }
 
-    pub fn start(&mut self) {
-        self.set_state(GameState::Playing(Entity::Human));
+    /// Gives each side `time` to play all their moves, running out of it loses.
+    pub fn with_clock(self, time: Duration) -> Game {
+        self.with_remaining(time, time)
+    }
+
+    /// Like [`Game::with_clock`], each side having their own time left, e.g. in a game played
+    /// again from a checkpoint.
+    pub fn with_remaining(mut self, human: Duration, computer: Duration) -> Game {
+        self.clock = Some(Clock {
+            human,
+            computer,
+            since: None,
+        });
+        self
+    }
+
+    pub fn is_timed(&self) -> bool {
+        self.clock.is_some()
+    }
+
+    /// Time left to `entity` in a timed game.
+    pub fn remaining(&self, entity: Entity) -> Option<Duration> {
+        let running = self.state == GameState::Playing(entity);
+        Some(self.clock?.remaining(entity, running, Instant::now()))
+    }
+
+    /// Ends the game once the side to move ran out of time, returns whether it did.
+    pub fn tick(&mut self) -> bool {
+        let GameState::Playing(entity) = self.state else {
+            return false;
+        };
+        if self.remaining(entity) != Some(Duration::ZERO) {
+            return false;
+        }
+        self.set_state(GameState::Timeout(entity));
+        true
+    }
+
+    /// Starts the game, from `initial` rather than the board it was created with when given.
+    /// Its marks count as there before the first move, which the starter still plays, and
+    /// stay when every move is taken back.
+    pub fn start(&mut self, initial: Option<Board>) {
+        if let Some(initial) = initial.filter(|_| self.moves.is_empty()) {
+            self.board = initial;
+            self.initial = initial;
+        }
+        if let Some(clock) = &mut self.clock {
+            clock.since = Some(Instant::now());
+        }
+        self.set_state(GameState::Playing(self.starter));
     }
 
     fn is_winner(&self, entity: Entity, x: usize, y: usize) -> bool {
-        if (0..3).all(|i| self.board[x][i] == entity) | (0..3).all(|i| self.board[i][y] == entity) {
-            return true;
+        self.board.has_line_through(entity, x, y)
+    }
+
+    /// The player to move, or why nobody can move.
+    fn to_move(&self) -> Result<Entity, MoveError> {
+        match self.state {
+            GameState::Playing(s) => Ok(s),
+            GameState::Ready => Ok(self.starter),
+            GameState::Win(_) | GameState::Draw | GameState::Timeout(_) => {
+                Err(MoveError::GameFinished)
+            }
         }
+    }
 
-        if x == y && (0..3).all(|i| self.board[i][i] == entity) {
-            return true;
+    /// Places a mark of the player to move at `(x, y)`. A refused move leaves the game as it
+    /// was.
+    pub fn try_play(&mut self, x: usize, y: usize) -> Result<MoveOutcome, MoveError> {
+        self.tick();
+        let entity = self.to_move()?;
+        let size = self.board.size();
+        if x >= size || y >= size {
+            return Err(MoveError::OutOfBounds);
+        }
+        if self.board[x][y] != Entity::Empty {
+            return Err(MoveError::CellOccupied);
+        }
+        if !self.is_valid_position(x, y) {
+            return Err(MoveError::Floating);
+        }
+        if self.board.must_slide(entity) {
+            return Err(MoveError::MustSlide);
         }
 
-        if x + y == 2 && (0..3).all(|i| self.board[i][2 - i] == entity) {
-            return true;
+        Ok(self.play(entity, None, x, y))
+    }
+
+    /// Slides a mark of the player to move from `from` to the adjacent empty cell `to`, once
+    /// they placed all their marks.
+    pub fn slide(
+        &mut self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Result<MoveOutcome, MoveError> {
+        self.tick();
+        let entity = self.to_move()?;
+        let size = self.board.size();
+        if [from, to].iter().any(|&(x, y)| x >= size || y >= size) {
+            return Err(MoveError::OutOfBounds);
+        }
+        if self.board[to.0][to.1] != Entity::Empty {
+            return Err(MoveError::CellOccupied);
+        }
+        if !self.board.must_slide(entity) || !self.board.slides(entity).contains(&(from, to)) {
+            return Err(MoveError::IllegalSlide);
         }
 
-        false
+        Ok(self.play(entity, Some(from), to.0, to.1))
     }
 
-    pub fn update(&mut self, x: usize, y: usize) {
-        let entity = match self.state {
-            GameState::Playing(s) | GameState::Repeat(s) => s,
-            _ => return,
-        };
+    fn play(
+        &mut self,
+        entity: Entity,
+        from: Option<(usize, usize)>,
+        x: usize,
+        y: usize,
+    ) -> MoveOutcome {
+        if let Some(clock) = &mut self.clock {
+            clock.press(entity, Instant::now());
+        }
+        let mark = self.rules.mark(entity);
+        let before = self.board;
+        if let Some((fx, fy)) = from {
+            self.board[fx][fy] = Entity::Empty;
+        }
+        self.board[x][y] = mark;
+        let state = self.outcome(entity, x, y);
+        self.set_state(state.clone());
+        self.moves.push(Move {
+            player: entity,
+            mark,
+            from,
+            x,
+            y,
+            state: state.clone(),
+            changes: before.diff(&self.board),
+        });
+        self.undone.clear();
+        match state {
+            GameState::Win(winner) => MoveOutcome::Won(winner),
+            GameState::Draw => MoveOutcome::Draw,
+            _ => MoveOutcome::Next(!entity),
+        }
+    }
 
-        if !self.is_valid_position(x, y) {
-            return self.set_state(GameState::Repeat(entity));
-        };
+    /// The state after `entity` played at `(x, y)`.
+    fn outcome(&self, entity: Entity, x: usize, y: usize) -> GameState {
+        if self.is_winner(self.rules.mark(entity), x, y) {
+            return GameState::Win(self.rules.winner(entity));
+        }
+
+        if self.board.is_full() || self.is_dead_draw(entity) {
+            return GameState::Draw;
+        }
+
+        // A side which can't slide any of its marks loses.
+        if self.board.must_slide(!entity) && self.board.slides(!entity).is_empty() {
+            return GameState::Win(entity);
+        }
 
-        self.update_board(entity, x, y);
+        GameState::Playing(!entity)
+    }
 
-        if self.is_winner(entity, x, y) {
-            return self.set_state(GameState::Win(entity));
+    /// Whether neither side can make a line anymore once `entity` moved, whatever is played
+    /// next. Sliding marks can always make one later on.
+    fn is_dead_draw(&self, entity: Entity) -> bool {
+        if self.board.pieces().is_some() {
+            return false;
         }
+        let empty = self
+            .board
+            .cells()
+            .filter(|(_, cell)| *cell == Entity::Empty)
+            .count();
+        let (next, last) = (self.rules.mark(!entity), self.rules.mark(entity));
+        if next == last {
+            // Both sides play the same mark, any empty cell can go towards a line.
+            return !self.board.can_complete(next, empty);
+        }
+        // The side to move gets the odd cell out.
+        !self.board.can_complete(next, empty.div_ceil(2))
+            && !self.board.can_complete(last, empty / 2)
+    }
 
-        if self.board.iter().flatten().all(|e| *e != Entity::Empty) {
-            return self.set_state(GameState::Draw);
+    /// The line which ended the game, `None` while it goes on or when it ended otherwise. Under
+    /// misère rules that's the line of the loser.
+    pub fn winning_line(&self) -> Option<WinLine> {
+        if !matches!(self.state, GameState::Win(_)) {
+            return None;
         }
+        let last = self.moves.last()?;
+        self.board.line_through(last.mark, last.x, last.y)
+    }
+
+    /// The player to move gives the game up.
+    pub fn resign(&mut self) -> Result<(), MoveError> {
+        let entity = self.to_move()?;
+        self.set_state(GameState::Win(!entity));
+        Ok(())
+    }
 
-        self.set_state(GameState::Playing(!entity));
+    /// Takes back the last move, returns `false` when there's none.
+    pub fn undo(&mut self) -> bool {
+        let Some(played) = self.moves.pop() else {
+            return false;
+        };
+        let reverse: Vec<_> = played
+            .changes
+            .iter()
+            .rev()
+            .map(|change| change.reverse())
+            .collect();
+        self.board
+            .apply_diff(&reverse)
+            .expect("the board is as the last move left it");
+        let state = match self.moves.last() {
+            Some(previous) => previous.state.clone(),
+            None => GameState::Playing(self.starter),
+        };
+        self.set_state(state);
+        self.undone.push(played);
+        true
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Plays again the last move taken back, returns `false` when there's none.
+    pub fn redo(&mut self) -> bool {
+        let Some(played) = self.undone.pop() else {
+            return false;
+        };
+        self.board
+            .apply_diff(&played.changes)
+            .expect("the board is as the move taken back found it");
+        self.set_state(played.state.clone());
+        self.moves.push(played);
+        true
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new(Board::default(), Rules::default())
+    }
+}
+
+impl Default for Computer {
+    fn default() -> Self {
+        Computer {
+            table: Arc::new(Mutex::new(LruCache::new(Self::capacity(
+                DEFAULT_TABLE_MEMORY,
+            )))),
+            cancel: None,
+            rules: Rules::default(),
+            rng: Arc::new(Mutex::new(StdRng::from_entropy())),
+            mistake_rate: 0,
+            weights: Weights::default(),
+            #[cfg(feature = "tablebases")]
+            tablebases: Arc::default(),
+        }
     }
 }
 
 impl Computer {
-    fn set_move(&self, board: &mut Board, entity: Entity, x: usize, y: usize) {
-        board[x][y] = entity
+    fn capacity(memory: usize) -> NonZeroUsize {
+        NonZeroUsize::new(memory / ENTRY_SIZE).unwrap_or(NonZeroUsize::MIN)
     }
 
-    fn undo_move(&self, board: &mut Board, x: usize, y: usize) {
-        board[x][y] = Entity::Empty
+    /// Bounds the memory used by the transposition table, evicting the least recently used
+    /// positions when it's full.
+    pub fn set_memory_limit(&mut self, bytes: usize) {
+        self.table().resize(Self::capacity(bytes));
     }
 
-    fn is_winner(&self, entity: Entity, board: &Board) -> bool {
-        for i in 0..3 {
-            if (0..3).all(|j| board[i][j] == entity) || (0..3).all(|j| board[j][i] == entity) {
-                return true;
+    /// What the transposition table and the tablebases take up, for the performance HUD.
+    pub fn cache_usage(&self) -> CacheUsage {
+        let table = self.table();
+        CacheUsage {
+            table: table.len() * ENTRY_SIZE,
+            table_limit: table.cap().get() * ENTRY_SIZE,
+            #[cfg(feature = "tablebases")]
+            tablebases: self.tablebases.iter().map(Tablebase::bytes).sum(),
+            #[cfg(not(feature = "tablebases"))]
+            tablebases: 0,
+        }
+    }
+
+    fn table(&self) -> MutexGuard<'_, LruCache<(Board, Entity), Entry>> {
+        // The table is only a cache, whatever a panicking search left in it is still usable.
+        self.table
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Lets the computer play a worse move than its best `percent` of the time, so casual
+    /// players get a chance to win. The human's moves it searches, e.g. for hints, stay the
+    /// best.
+    pub fn set_mistake_rate(&mut self, percent: u32) {
+        self.mistake_rate = percent.min(100);
+    }
+
+    /// Estimates the positions past the horizon with `weights`. The positions searched so far
+    /// are forgotten, as they were estimated with the previous ones.
+    pub fn set_weights(&mut self, mut weights: Weights) {
+        weights.validate();
+        self.weights = weights;
+        self.table().clear();
+    }
+
+    /// Picks among equally good moves with a generator seeded by `seed` rather than by the
+    /// system, so the same games get the same moves.
+    pub fn with_seed(self, seed: u64) -> Computer {
+        *self
+            .rng
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Forgets every position searched so far, the positions of a new game are unrelated, and
+    /// plays it by `rules`.
+    pub fn new_game(&mut self, rules: Rules) {
+        self.table().clear();
+        self.rules = rules;
+    }
+
+    /// Returns a copy sharing the transposition table whose searches stop once `cancel` is
+    /// raised.
+    pub fn with_cancel(&self, cancel: Arc<AtomicBool>) -> Computer {
+        Computer {
+            table: Arc::clone(&self.table),
+            cancel: Some(cancel),
+            rules: self.rules,
+            rng: Arc::clone(&self.rng),
+            mistake_rate: self.mistake_rate,
+            weights: self.weights,
+            #[cfg(feature = "tablebases")]
+            tablebases: Arc::clone(&self.tablebases),
+        }
+    }
+
+    /// Looks the positions of the boards in `tablebases` up rather than searching them.
+    #[cfg(feature = "tablebases")]
+    pub fn set_tablebases(&mut self, tablebases: Vec<Tablebase>) {
+        self.tablebases = Arc::new(tablebases);
+    }
+
+    /// How many moves the engine looks ahead of `board`: to the end of the game on 3×3, only a
+    /// few moves on the bigger boards whose whole tree is out of reach.
+    /// Once marks slide a game never has to end, so even 3×3 is searched a bounded number of
+    /// moves ahead.
+    fn horizon(board: &Board) -> usize {
+        match board.size() {
+            0..=3 if board.pieces().is_some() => 10,
+            0..=3 => MAX_SIZE * MAX_SIZE,
+            4 => 6,
+            5 => 4,
+            _ => 3,
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    /// Guesses the best move of the human and searches the answer to it while they think.
+    ///
+    /// Returns `None` when cancelled or when the predicted move ends the game. Even then,
+    /// every position fully searched so far stays in the transposition table.
+    /// Positions where either side slides its marks aren't pondered.
+    pub fn ponder(&mut self, mut board: Board) -> Option<Prediction> {
+        if board.must_slide(Entity::Human) {
+            return None;
+        }
+        let position = board;
+        let mut best_score = i32::MAX;
+        let mut human = None;
+        let horizon = Self::horizon(&board);
+        for action in self.actions(&board, Entity::Human) {
+            let Action::Place(row, col) = action else {
+                return None;
+            };
+            self.set_move(&mut board, Entity::Human, action);
+            let score = self.minimax(&mut board, Entity::Computer, i32::MIN, i32::MAX, horizon);
+            self.undo_move(&mut board, Entity::Human, action);
+            if score < best_score {
+                best_score = score;
+                human = Some((row, col));
             }
         }
 
-        (0..3).all(|i| board[i][i] == entity) || (0..3).all(|i| board[i][2 - i] == entity)
+        let (row, col) = human?;
+        self.set_move(&mut board, Entity::Human, Action::Place(row, col));
+        if self.is_winner(Entity::Human, &board)
+            || self.is_winner(Entity::Computer, &board)
+            || board.must_slide(Entity::Computer)
+            || self.actions(&board, Entity::Computer).is_empty()
+        {
+            return None;
+        }
+        let reply = self.best_play(board)?;
+        (!self.is_cancelled()).then_some(Prediction {
+            board: position,
+            human: (row, col),
+            reply,
+        })
     }
 
-    pub fn best_play(&mut self, mut board: Board) -> (usize, usize) {
-        let mut best_score = i32::MIN;
-        let mut best_move = (0, 0);
+    fn set_move(&self, board: &mut Board, entity: Entity, action: Action) {
+        let entity = self.rules.mark(entity);
+        match action {
+            Action::Place(x, y) => board[x][y] = entity,
+            Action::Slide((fx, fy), (x, y)) => {
+                board[fx][fy] = Entity::Empty;
+                board[x][y] = entity;
+            }
+        }
+    }
 
-        let actions = self.actions(&board);
+    fn undo_move(&self, board: &mut Board, entity: Entity, action: Action) {
+        let entity = self.rules.mark(entity);
+        match action {
+            Action::Place(x, y) => board[x][y] = Entity::Empty,
+            Action::Slide((fx, fy), (x, y)) => {
+                board[x][y] = Entity::Empty;
+                board[fx][fy] = entity;
+            }
+        }
+    }
 
-        for (row, col) in actions {
-            if board[row][col] == Entity::Empty {
-                self.set_move(&mut board, Entity::Computer, row, col);
+    fn is_winner(&self, entity: Entity, board: &Board) -> bool {
+        has_line(board, entity)
+    }
 
-                let (score, _) = self.minimax(&mut board, Entity::Human, i32::MIN, i32::MAX, 0);
+    /// Scores each mark the human may place on `board`, in the order the engine searches them.
+    pub fn move_scores(&mut self, mut board: Board) -> Vec<MoveScore> {
+        let mut scores = vec![];
+        let horizon = Self::horizon(&board);
+        for action in self.actions(&board, Entity::Human) {
+            let Action::Place(row, col) = action else {
+                continue;
+            };
+            self.set_move(&mut board, Entity::Human, action);
+            let score = self.minimax(&mut board, Entity::Computer, i32::MIN, i32::MAX, horizon);
+            self.undo_move(&mut board, Entity::Human, action);
+            scores.push(MoveScore {
+                cell: (row, col),
+                score,
+            });
+        }
+        scores
+    }
 
-                self.undo_move(&mut board, row, col);
+    /// How the game ends after each move of the human on `board` with best play from both
+    /// sides.
+    pub fn outcomes(&mut self, board: Board) -> Outcomes {
+        Outcomes(self.move_scores(board))
+    }
 
-                if score > best_score {
-                    best_score = score;
-                    best_move = (row, col);
+    /// The first plies of best play once the human placed a mark at `(x, y)` on `board`,
+    /// starting with the computer's answer: the refutation of a losing move. Shorter when the
+    /// game ends first. Unlike the computer's own moves, it skips the book and keeps the first
+    /// of equal moves, so it's the line the score of the move comes from.
+    pub fn principal_line(&mut self, mut board: Board, x: usize, y: usize) -> Vec<Action> {
+        let mut line = vec![];
+        self.set_move(&mut board, Entity::Human, Action::Place(x, y));
+        let mut player = Entity::Computer;
+        while line.len() < LINE_LENGTH
+            && !self.is_winner(Entity::Human, &board)
+            && !self.is_winner(Entity::Computer, &board)
+        {
+            let horizon = Self::horizon(&board);
+            let mut best: Option<(i32, Action)> = None;
+            for action in self.actions(&board, player) {
+                self.set_move(&mut board, player, action);
+                let score = self.minimax(&mut board, !player, i32::MIN, i32::MAX, horizon);
+                self.undo_move(&mut board, player, action);
+                let better = match best {
+                    None => true,
+                    Some((best, _)) if player == Entity::Computer => score > best,
+                    Some((best, _)) => score < best,
+                };
+                if better {
+                    best = Some((score, action));
                 }
             }
+            let Some((_, action)) = best else {
+                break;
+            };
+            self.set_move(&mut board, player, action);
+            line.push(action);
+            player = !player;
+        }
+        line
+    }
+
+    /// Evaluates any position, not only those of a game, with `player` to place the next mark.
+    /// Both sides are searched the same way, "human" and "computer" only naming the marks.
+    pub fn assess(&mut self, mut board: Board, player: Entity) -> Assessment {
+        let winner = |score: i32| match outcome(score) {
+            1 => Some(Entity::Computer),
+            -1 => Some(Entity::Human),
+            _ => None,
+        };
+        if self.is_winner(Entity::Human, &board) || self.is_winner(Entity::Computer, &board) {
+            return Assessment {
+                winner: winner(self.evaluate(&board, player)),
+                exact: true,
+                ..Default::default()
+            };
+        }
+
+        let horizon = Self::horizon(&board);
+        let empty = board
+            .cells()
+            .filter(|(_, entity)| *entity == Entity::Empty)
+            .count();
+        let mut best: Option<(i32, (usize, usize))> = None;
+        let mut moves = vec![];
+        for (row, col) in board.playable().collect::<Vec<_>>() {
+            let action = Action::Place(row, col);
+            self.set_move(&mut board, player, action);
+            let score = self.minimax(&mut board, !player, i32::MIN, i32::MAX, horizon);
+            self.undo_move(&mut board, player, action);
+            moves.push(((row, col), winner(score)));
+            let better = match best {
+                None => true,
+                Some((best, _)) if player == Entity::Computer => score > best,
+                Some((best, _)) => score < best,
+            };
+            if better {
+                best = Some((score, (row, col)));
+            }
+        }
+        Assessment {
+            winner: best.and_then(|(score, _)| winner(score)),
+            best: best.map(|(_, cell)| cell),
+            moves,
+            exact: board.pieces().is_none() && horizon >= empty,
+        }
+    }
+
+    /// The cell the computer places its next mark on, `None` when it has to slide one or
+    /// can't move at all.
+    pub fn best_play(&mut self, board: Board) -> Option<(usize, usize)> {
+        match self.best_action(board, Entity::Computer) {
+            Some(Action::Place(row, col)) => Some((row, col)),
+            _ => None,
+        }
+    }
+
+    /// The move `player` plays next, placing a mark or sliding one once all are placed. Scores
+    /// are the computer's, the human picking the lowest. `None` when every mark is blocked.
+    pub fn best_action(&mut self, mut board: Board, player: Entity) -> Option<Action> {
+        // Not rolled at all by default, seeded games keep the moves they always had.
+        let mistake = player == Entity::Computer
+            && self.mistake_rate > 0
+            && self
+                .rng
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .gen_ratio(self.mistake_rate, 100);
+        // The first plies of the classic game are answered from the book, without searching.
+        if let Some(moves) =
+            book::moves(&board, self.rules).filter(|_| player == Entity::Computer && !mistake)
+        {
+            let mut rng = self
+                .rng
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Ok(&((row, col), _)) = moves.choose_weighted(&mut *rng, |(_, weight)| *weight) {
+                return Some(Action::Place(row, col));
+            }
         }
+        let actions = self.actions(&board, player);
 
-        best_move
+        // Every move may lose right away under misère rules, one of them is still played: the
+        // worst score still counts as the best so far.
+        let mut best_score = None;
+        let mut best_moves = vec![];
+        let mut scores = vec![];
+        let horizon = Self::horizon(&board);
+
+        for action in actions {
+            self.set_move(&mut board, player, action);
+
+            let score = self.minimax(&mut board, !player, i32::MIN, i32::MAX, horizon);
+
+            self.undo_move(&mut board, player, action);
+            scores.push((action, score));
+
+            let better = match best_score {
+                None => true,
+                Some(best) if player == Entity::Computer => score > best,
+                Some(best) => score < best,
+            };
+            if better {
+                best_score = Some(score);
+                best_moves.clear();
+            }
+            if best_score == Some(score) {
+                best_moves.push(action);
+            }
+        }
+
+        // Scores already tell a faster win from a slower one, moves scoring the same are as
+        // good as each other, so games against the engine don't all go the same way.
+        let mut rng = self
+            .rng
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // A mistake is any move scoring less than the best, the best still played when every
+        // move scores the same.
+        let worse: Vec<Action> = scores
+            .into_iter()
+            .filter(|&(_, score)| Some(score) != best_score)
+            .map(|(action, _)| action)
+            .collect();
+        match worse.choose(&mut *rng) {
+            Some(&action) if mistake => Some(action),
+            _ => best_moves.choose(&mut *rng).copied(),
+        }
+    }
+
+    /// Who wins `board` with `player` to move according to the tablebases, `Some(Entity::Empty)`
+    /// for a draw.
+    #[cfg(feature = "tablebases")]
+    fn solved(&self, board: &Board, player: Entity) -> Option<Entity> {
+        self.tablebases
+            .iter()
+            .find_map(|tablebase| tablebase.lookup(board, player, self.rules))
+    }
+
+    #[cfg(not(feature = "tablebases"))]
+    fn solved(&self, _board: &Board, _player: Entity) -> Option<Entity> {
+        None
     }
 
     fn minimax(
@@ -151,14 +1631,49 @@ impl Computer {
         player: Entity,
         mut alpha: i32,
         mut beta: i32,
-        mut depth: i32,
-    ) -> (i32, i32) /* (score, depth) */ {
+        remaining: usize,
+    ) -> i32 {
+        // Searching past the last empty cell is no deeper than searching up to it, unless
+        // marks keep sliding.
+        let empty = board
+            .cells()
+            .filter(|(_, entity)| *entity == Entity::Empty)
+            .count();
+        let remaining = match board.pieces() {
+            Some(_) => remaining,
+            None => remaining.min(empty),
+        };
+        // Rotated and mirrored boards are the same position, searched once.
+        let key = (board.canonical(), player);
+        let cached = self.table().get(&key).copied();
+        if let Some(entry) = cached.filter(|entry| entry.remaining >= remaining) {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower if entry.score >= beta => return entry.score,
+                Bound::Upper if entry.score <= alpha => return entry.score,
+                Bound::Lower => alpha = alpha.max(entry.score),
+                Bound::Upper => beta = beta.min(entry.score),
+            }
+        }
+        let window = (alpha, beta);
+
         // Check if the board is finished:
-        if self.is_winner(player, board)
-            | self.is_winner(!player, board)
-            | board.iter().flatten().all(|e| *e != Entity::Empty)
-        {
-            return (self.evaluate(board, depth), depth);
+        if self.is_winner(player, board) | self.is_winner(!player, board) | board.is_full() {
+            return self.evaluate(board, player);
+        }
+        // A solved position is as good as searched to the end. The tablebase doesn't say how
+        // far the end is, so the win counts as late as the board allows.
+        if let Some(winner) = self.solved(board, player) {
+            let late = empty as i32;
+            return match winner {
+                Entity::Computer => i32::MAX - late,
+                Entity::Human => i32::MIN + late,
+                Entity::Empty => 0,
+            };
+        }
+        // Past the horizon, the position is only estimated.
+        if remaining == 0 {
+            return self.heuristic(board, player);
         }
         // set the functions:
         let func: fn(i32, i32) -> i32;
@@ -171,63 +1686,179 @@ impl Computer {
             m = i32::MAX;
         }
 
-        for (row, col) in self.actions(board) {
-            self.set_move(board, player, row, col);
-            let (value, m_depth) = self.minimax(board, !player, alpha, beta, depth + 1);
-            depth = m_depth;
-            m = func(m, value);
-            self.undo_move(board, row, col);
-            if player == Entity::Computer {
+        // A side whose marks are all blocked keeps the worst score, it lost.
+        for action in self.actions(board, player) {
+            if self.is_cancelled() {
+                break;
+            }
+            self.set_move(board, player, action);
+            let value = self.minimax(
+                board,
+                !player,
+                to_child(alpha),
+                to_child(beta),
+                remaining - 1,
+            );
+            m = func(m, from_child(value));
+            self.undo_move(board, player, action);
+            // Saturated decisive bounds can hand down a window which is empty from the start,
+            // e.g. from `i32::MIN` to `i32::MIN`. Only a score past the bound of the other side
+            // stops the search, the bound its entry is then stored as, rather than any
+            // `beta <= alpha`.
+            let cutoff = if player == Entity::Computer {
                 alpha = func(alpha, m);
+                m >= beta
             } else {
                 beta = func(beta, m);
-            }
-            if beta <= alpha {
+                m <= alpha
+            };
+            if cutoff {
                 break;
             }
         }
 
-        (m, depth)
+        // An aborted search only explored part of the tree, its score is meaningless.
+        if self.is_cancelled() {
+            return m;
+        }
+
+        let bound = if m <= window.0 {
+            Bound::Upper
+        } else if m >= window.1 {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table().put(
+            key,
+            Entry {
+                score: m,
+                bound,
+                remaining,
+            },
+        );
+
+        m
     }
 
-    fn actions(&self, board: &Board) -> Vec<(usize, usize)> {
-        let mut positions = vec![];
-        for (col_index, col) in board.iter().enumerate() {
-            for (row_index, entity) in col.iter().enumerate() {
-                if *entity == Entity::Empty {
-                    positions.push((col_index, row_index))
+    fn actions(&self, board: &Board, player: Entity) -> Vec<Action> {
+        if board.must_slide(player) {
+            return board
+                .slides(player)
+                .into_iter()
+                .map(|(from, to)| Action::Slide(from, to))
+                .collect();
+        }
+        board
+            .playable()
+            .map(|(row, col)| Action::Place(row, col))
+            .collect()
+    }
+
+    /// Scores `board` with `player` to move.
+    /// Estimates an unfinished position from the computer's side, far from the scores of decided
+    /// games. Each window of [`Board::line`] cells open to one side only counts for it, more the
+    /// more of its marks it holds, so marks on cells crossed by many lines weigh the most, e.g.
+    /// the center and the corners of 3×3. A side to move with a line to complete, or facing two
+    /// it can't both block, has all but won.
+    ///
+    /// Only the classic rules have lines worth building, the others count as even.
+    fn heuristic(&self, board: &Board, player: Entity) -> i32 {
+        if self.rules != Rules::Classic {
+            return 0;
+        }
+        let mut score = 0;
+        let mut threats = (vec![], vec![]);
+        for window in board.windows() {
+            let (mut computer, mut human, mut empty) = (0, 0, None);
+            for (x, y) in window.cells() {
+                match board[x][y] {
+                    Entity::Computer => computer += 1,
+                    Entity::Human => human += 1,
+                    Entity::Empty => empty = Some((x, y)),
                 }
             }
+            let (marks, sign, threats) = match (computer, human) {
+                (0, 0) | (1.., 1..) => continue,
+                (marks, 0) => (marks, 1, &mut threats.0),
+                (0, marks) => (marks, -1, &mut threats.1),
+            };
+            score += sign * self.weights.runs[marks.min(MAX_SIZE - 1)];
+            // The last cell of the window completes a line once a mark can go there.
+            if let Some((x, y)) = empty.filter(|&(x, y)| board.is_playable(x, y)) {
+                if marks + 1 == board.line() && !threats.contains(&(x, y)) {
+                    threats.push((x, y));
+                }
+            }
+        }
+        // Marks which slide can't go straight to the cell completing a line.
+        if board.pieces().is_some() {
+            return score;
+        }
+        let (own, other) = match player {
+            Entity::Computer => (threats.0.len(), threats.1.len()),
+            _ => (threats.1.len(), threats.0.len()),
+        };
+        let winner = if own > 0 {
+            player
+        } else if other >= 2 {
+            !player
+        } else {
+            return score;
+        };
+        match winner {
+            Entity::Computer => NEARLY_DECIDED + score,
+            _ => -NEARLY_DECIDED + score,
         }
-        positions
     }
 
-    fn evaluate(&self, board: &Board, depth: i32) -> i32 {
-        if self.is_winner(Entity::Computer, board) {
-            return i32::MAX - depth;
+    /// Scores a finished position, wins and losses as far from the others as scores go.
+    fn evaluate(&self, board: &Board, player: Entity) -> i32 {
+        let line = if self.is_winner(Entity::Computer, board) {
+            Entity::Computer
         } else if self.is_winner(Entity::Human, board) {
-            return i32::MIN + depth;
+            Entity::Human
+        } else {
+            return 0;
+        };
+        // Under Notakto every mark is an X, the line was completed by the side which just
+        // moved rather than by the owner of the marks.
+        let line = match self.rules {
+            Rules::Notakto => !player,
+            _ => line,
+        };
+        match self.rules.winner(line) {
+            Entity::Computer => i32::MAX,
+            _ => i32::MIN,
         }
-        0
     }
 }
 
 #[allow(dead_code)]
 impl GameState {
     pub fn is_finished(&self) -> bool {
-        matches!(self, GameState::Draw | GameState::Win(_))
-    }
-
-    pub fn is_playable(&self) -> bool {
         matches!(
             self,
-            GameState::Playing(_) | GameState::Repeat(_) | GameState::Ready
+            GameState::Draw | GameState::Win(_) | GameState::Timeout(_)
         )
     }
+
+    /// Who won the finished game, `None` for a draw or while it goes on.
+    pub fn winner(&self) -> Option<Entity> {
+        match self {
+            GameState::Win(winner) => Some(*winner),
+            GameState::Timeout(loser) => Some(!*loser),
+            _ => None,
+        }
+    }
+
+    pub fn is_playable(&self) -> bool {
+        matches!(self, GameState::Playing(_) | GameState::Ready)
+    }
 }
 
 impl Entity {
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             Self::Empty => "-",
             Self::Human => "O",
@@ -247,3 +1878,125 @@ impl std::ops::Not for Entity {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The computer to move with two wins on the spot, in (0, 2) and (2, 0), and a slower
+    /// move in (2, 2).
+    fn two_wins() -> Board {
+        let mut board = Board::default();
+        for (x, y) in [(0, 0), (0, 1), (1, 0)] {
+            board[x][y] = Entity::Computer;
+        }
+        for (x, y) in [(1, 1), (1, 2), (2, 1)] {
+            board[x][y] = Entity::Human;
+        }
+        board
+    }
+
+    #[test]
+    fn same_seed_same_moves() {
+        let mut first = Computer::default().with_seed(7);
+        let mut second = Computer::default().with_seed(7);
+        for _ in 0..10 {
+            assert_eq!(
+                first.best_action(two_wins(), Entity::Computer),
+                second.best_action(two_wins(), Entity::Computer)
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_cover_tied_moves_only() {
+        let mut picked = vec![];
+        for seed in 0..32 {
+            let mut engine = Computer::default().with_seed(seed);
+            let action = engine.best_action(two_wins(), Entity::Computer);
+            assert!(
+                matches!(action, Some(Action::Place(0, 2) | Action::Place(2, 0))),
+                "seed {seed} played {action:?}"
+            );
+            if !picked.contains(&action) {
+                picked.push(action);
+            }
+        }
+        assert_eq!(picked.len(), 2, "every win is picked by some seed");
+    }
+
+    #[test]
+    fn diff_round_trip() {
+        let start = two_wins();
+        let mut end = start;
+        end[0][2] = Entity::Computer;
+        end[1][1] = Entity::Empty;
+        let changes = start.diff(&end);
+        assert_eq!(changes.len(), 2);
+
+        let mut board = start;
+        board.apply_diff(&changes).unwrap();
+        assert_eq!(board, end);
+        // Applied twice, the changes no longer start from what the cells hold.
+        assert_eq!(board.apply_diff(&changes), Err(DiffError::Mismatch(0, 2)));
+        assert_eq!(board, end);
+
+        let reverse: Vec<_> = changes.iter().map(|change| change.reverse()).collect();
+        board.apply_diff(&reverse).unwrap();
+        assert_eq!(board, start);
+    }
+
+    #[test]
+    fn undo_redo_round_trip() {
+        let mut game = Game::default();
+        for (x, y) in [(0, 0), (1, 1), (2, 2)] {
+            game.try_play(x, y).unwrap();
+        }
+        let played = *game.board();
+        while game.undo() {}
+        assert_eq!(*game.board(), Board::default());
+        while game.redo() {}
+        assert_eq!(*game.board(), played);
+    }
+
+    #[test]
+    fn notakto_moves_keep_the_player() {
+        let mut game = Game::new(Board::default(), Rules::Notakto);
+        game.try_play(0, 0).unwrap();
+        game.try_play(1, 1).unwrap();
+        let [first, second] = game.moves() else {
+            panic!("two moves were played");
+        };
+        assert_eq!(second.player, !first.player);
+        assert_eq!(
+            (first.mark, second.mark),
+            (Entity::Computer, Entity::Computer)
+        );
+    }
+
+    fn played(cells: &[(usize, usize)]) -> Game {
+        let mut game = Game::default();
+        for &(x, y) in cells {
+            game.try_play(x, y).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn blocked_board_is_drawn() {
+        // H H C
+        // C C H
+        // H . .
+        let game = played(&[(0, 0), (1, 1), (0, 1), (0, 2), (2, 0), (1, 0), (1, 2)]);
+        assert_eq!(game.state(), GameState::Draw);
+    }
+
+    #[test]
+    fn line_one_move_away_is_not_drawn() {
+        // H H C
+        // C C .
+        // H . .
+        let game = played(&[(0, 0), (1, 1), (0, 1), (0, 2), (2, 0), (1, 0)]);
+        assert_eq!(game.state(), GameState::Playing(Entity::Human));
+    }
+}
@@ -0,0 +1,70 @@
+//! The opening book: answers to the first two plies of the classic 3×3 game, played at once
+//! rather than searched from a nearly empty board, and picked at random among moves known to
+//! hold so games vary from the first move.
+
+use super::{Board, Entity, Rules};
+
+/// How often the computer opens on each cell of a place, against the other cells. The four
+/// corners together still come up the most: they're the trap, every reply but the center
+/// losing.
+const OPENINGS: [(Place, u32); 2] = [(Place::Corner, 1), (Place::Center, 2)];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Place {
+    Center,
+    Corner,
+    Edge,
+}
+
+fn place((x, y): (usize, usize)) -> Place {
+    match (x, y) {
+        (1, 1) => Place::Center,
+        _ if x != 1 && y != 1 => Place::Corner,
+        _ => Place::Edge,
+    }
+}
+
+/// The moves of the book for the computer on `board`, each with how often to pick it, `None`
+/// past the first two plies or on boards and rules it doesn't cover.
+pub fn moves(board: &Board, rules: Rules) -> Option<Vec<((usize, usize), u32)>> {
+    if rules != Rules::Classic
+        || board.size() != 3
+        || board.has_gravity()
+        || board.pieces().is_some()
+    {
+        return None;
+    }
+    let marks: Vec<_> = board
+        .cells()
+        .filter(|(_, entity)| *entity != Entity::Empty)
+        .collect();
+    let cells = board.playable();
+    let moves: Vec<_> = match marks[..] {
+        [] => cells
+            .filter_map(|cell| {
+                OPENINGS
+                    .iter()
+                    .find(|(opening, _)| *opening == place(cell))
+                    .map(|(_, weight)| (cell, *weight))
+            })
+            .collect(),
+        [(opened, Entity::Human)] => cells
+            .filter(|&cell| match place(opened) {
+                // A corner answers the center, an edge loses.
+                Place::Center => place(cell) == Place::Corner,
+                // Only the center holds against a corner.
+                Place::Corner => place(cell) == Place::Center,
+                // The center, the corners next to the edge or the edge across all hold.
+                Place::Edge => {
+                    let across = (2 - opened.0, 2 - opened.1);
+                    let next_to =
+                        place(cell) == Place::Corner && (cell.0 == opened.0 || cell.1 == opened.1);
+                    place(cell) == Place::Center || next_to || cell == across
+                }
+            })
+            .map(|cell| (cell, 1))
+            .collect(),
+        _ => return None,
+    };
+    Some(moves)
+}
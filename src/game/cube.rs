@@ -0,0 +1,190 @@
+//! Tic-tac-toe in a 3×3×3 cube: three stacked boards where lines also run across the layers,
+//! up to the space diagonals joining opposite corners of the cube.
+
+use std::sync::OnceLock;
+
+use super::{Entity, GameState, MoveError};
+
+/// A cell of the cube as `(layer, x, y)`.
+pub type Cell = (usize, usize, usize);
+
+/// How many moves the computer looks ahead.
+const DEPTH: u32 = 4;
+
+/// Score of a won game, above any evaluation of an unfinished one.
+const WIN: i32 = 100_000;
+
+/// Every line of the cube, 49 of them.
+fn lines() -> &'static [[Cell; 3]] {
+    static LINES: OnceLock<Vec<[Cell; 3]>> = OnceLock::new();
+    LINES.get_or_init(|| {
+        let cells =
+            || (0..3).flat_map(|z| (0..3).flat_map(move |x| (0..3).map(move |y| (z, x, y))));
+        let step = |(z, x, y): Cell, (dz, dx, dy): (isize, isize, isize), i: isize| {
+            let coords = [
+                z as isize + dz * i,
+                x as isize + dx * i,
+                y as isize + dy * i,
+            ];
+            coords
+                .iter()
+                .all(|c| (0..3).contains(c))
+                .then(|| (coords[0] as usize, coords[1] as usize, coords[2] as usize))
+        };
+        // Half of the 26 directions, the other half walks the same lines backwards.
+        let directions = cells()
+            .map(|(z, x, y)| (z as isize - 1, x as isize - 1, y as isize - 1))
+            .filter(|&direction| direction > (0, 0, 0));
+        let mut lines = vec![];
+        for direction in directions {
+            for start in cells() {
+                if step(start, direction, -1).is_some() {
+                    continue;
+                }
+                if let (Some(middle), Some(end)) =
+                    (step(start, direction, 1), step(start, direction, 2))
+                {
+                    lines.push([start, middle, end]);
+                }
+            }
+        }
+        lines
+    })
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cube {
+    /// The layers from top to bottom, each indexed as `[x][y]`.
+    cells: [[[Entity; 3]; 3]; 3],
+    state: GameState,
+}
+
+impl Cube {
+    pub fn start(&mut self) {
+        self.state = GameState::Playing(Entity::Human);
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state.clone()
+    }
+
+    pub fn get(&self, (z, x, y): Cell) -> Entity {
+        self.cells[z][x][y]
+    }
+
+    /// Every empty cell.
+    pub fn actions(&self) -> Vec<Cell> {
+        (0..3)
+            .flat_map(|z| (0..3).flat_map(move |x| (0..3).map(move |y| (z, x, y))))
+            .filter(|cell| self.get(*cell) == Entity::Empty)
+            .collect()
+    }
+
+    /// Plays the mark of the player to move. A refused move leaves the game as it was.
+    pub fn update(&mut self, cell: Cell) -> Result<(), MoveError> {
+        let GameState::Playing(entity) = self.state else {
+            return Err(MoveError::GameFinished);
+        };
+        let (z, x, y) = cell;
+        if z >= 3 || x >= 3 || y >= 3 {
+            return Err(MoveError::OutOfBounds);
+        }
+        if self.get(cell) != Entity::Empty {
+            return Err(MoveError::CellOccupied);
+        }
+
+        self.play(entity, cell);
+        Ok(())
+    }
+
+    fn play(&mut self, entity: Entity, cell: Cell) {
+        let (z, x, y) = cell;
+        self.cells[z][x][y] = entity;
+        let line = lines().iter().filter(|line| line.contains(&cell));
+        if line
+            .into_iter()
+            .any(|line| line.iter().all(|cell| self.get(*cell) == entity))
+        {
+            return self.state = GameState::Win(entity);
+        }
+
+        if self.actions().is_empty() {
+            return self.state = GameState::Draw;
+        }
+
+        self.state = GameState::Playing(!entity);
+    }
+
+    /// The move of the computer, searched a few moves deep.
+    pub fn best_play(&self) -> Cell {
+        let mut best_score = i32::MIN;
+        let mut best_move = None;
+        for cell in self.actions() {
+            let mut after = self.clone();
+            after.play(Entity::Computer, cell);
+            let score = after.minimax(DEPTH - 1, best_score, i32::MAX);
+            if best_move.is_none() || score > best_score {
+                best_score = score;
+                best_move = Some(cell);
+            }
+        }
+        best_move.expect("the computer only plays unfinished games")
+    }
+
+    fn minimax(&self, depth: u32, mut alpha: i32, mut beta: i32) -> i32 {
+        let player = match self.state {
+            GameState::Win(Entity::Computer) => return WIN + depth as i32,
+            GameState::Win(_) => return -WIN - depth as i32,
+            GameState::Draw => return 0,
+            GameState::Playing(player) => player,
+            GameState::Ready => Entity::Human,
+            GameState::Timeout(_) => unreachable!("variants aren't timed"),
+        };
+        if depth == 0 {
+            return self.evaluate();
+        }
+
+        let mut best = if player == Entity::Computer {
+            i32::MIN
+        } else {
+            i32::MAX
+        };
+        for cell in self.actions() {
+            let mut after = self.clone();
+            after.play(player, cell);
+            let score = after.minimax(depth - 1, alpha, beta);
+            if player == Entity::Computer {
+                best = best.max(score);
+                alpha = alpha.max(best);
+            } else {
+                best = best.min(score);
+                beta = beta.min(best);
+            }
+            if beta <= alpha {
+                break;
+            }
+        }
+        best
+    }
+
+    /// How good an unfinished position looks for the computer, from the lines each side can
+    /// still complete: a line with two marks is worth much more than one with a single mark.
+    fn evaluate(&self) -> i32 {
+        lines()
+            .iter()
+            .map(|line| {
+                let count = |entity| {
+                    line.iter()
+                        .filter(|cell| self.get(**cell) == entity)
+                        .count()
+                };
+                let worth = |marks| [0, 1, 10][marks];
+                match (count(Entity::Computer), count(Entity::Human)) {
+                    (marks, 0) => worth(marks),
+                    (0, marks) => -worth(marks),
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,200 @@
+//! Order and Chaos: a 6×6 board where both sides place an X or an O as they please. Order wins
+//! with five of the same mark in a row, whoever placed them, Chaos wins by filling the board
+//! without any.
+//!
+//! It's kept apart from [`super::Game`] like the other variants: a turn of `Game` is a cell,
+//! which its search, book, tablebases and replays all build on, while a turn here is a cell
+//! and a mark, and who wins depends on the roles rather than on whose marks made the line.
+
+use super::{Board, Entity, GameState, MoveError};
+
+/// Side of the board.
+const SIZE: usize = 6;
+
+/// Marks in a row needed for Order to win.
+const LINE: usize = 5;
+
+/// How many turns the computer looks ahead.
+const DEPTH: u32 = 2;
+
+/// Score of a won game, above any evaluation of an unfinished one.
+const WIN: i32 = 100_000;
+
+/// The marks either side can place, O then X.
+pub const MARKS: [Entity; 2] = [Entity::Human, Entity::Computer];
+
+/// A whole turn: the cell and the mark placed on it.
+pub type Turn = ((usize, usize), Entity);
+
+#[derive(Debug, Clone)]
+pub struct OrderChaos {
+    board: Board,
+    state: GameState,
+    /// The side playing Order, which moves first. The other one plays Chaos.
+    order: Entity,
+    /// The mark the human places next, picked on the screen.
+    mark: Entity,
+}
+
+impl Default for OrderChaos {
+    fn default() -> Self {
+        OrderChaos::new(Entity::Human)
+    }
+}
+
+impl OrderChaos {
+    /// A game where `order` plays Order against the other side playing Chaos.
+    pub fn new(order: Entity) -> OrderChaos {
+        OrderChaos {
+            board: Board::new(SIZE).with_line(LINE),
+            state: GameState::default(),
+            order,
+            mark: Entity::Human,
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.state = GameState::Playing(self.order);
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state.clone()
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// The side playing Order.
+    pub fn order(&self) -> Entity {
+        self.order
+    }
+
+    /// The mark the human places next.
+    pub fn mark(&self) -> Entity {
+        self.mark
+    }
+
+    pub fn pick(&mut self, mark: Entity) {
+        if MARKS.contains(&mark) {
+            self.mark = mark;
+        }
+    }
+
+    /// Places `mark` for the side to move. A line of it wins for Order even when Chaos placed
+    /// it, filling the last empty cell without any line wins for Chaos. A refused move leaves
+    /// the game as it was.
+    pub fn place(&mut self, x: usize, y: usize, mark: Entity) -> Result<(), MoveError> {
+        debug_assert!(MARKS.contains(&mark), "only X and O are placed");
+        let GameState::Playing(entity) = self.state else {
+            return Err(MoveError::GameFinished);
+        };
+        if x >= SIZE || y >= SIZE {
+            return Err(MoveError::OutOfBounds);
+        }
+        if self.board[x][y] != Entity::Empty {
+            return Err(MoveError::CellOccupied);
+        }
+
+        self.board[x][y] = mark;
+        self.state = if self.board.has_line_through(mark, x, y) {
+            GameState::Win(self.order)
+        } else if self.board.is_full() {
+            GameState::Win(!self.order)
+        } else {
+            GameState::Playing(!entity)
+        };
+        Ok(())
+    }
+
+    /// Every turn the side to move can play, with the game it leads to.
+    fn turns(&self) -> Vec<(Turn, OrderChaos)> {
+        self.board
+            .playable()
+            .flat_map(|cell| MARKS.map(|mark| (cell, mark)))
+            .map(|(cell, mark)| {
+                let mut after = self.clone();
+                after
+                    .place(cell.0, cell.1, mark)
+                    .expect("turns are played on empty cells");
+                ((cell, mark), after)
+            })
+            .collect()
+    }
+
+    /// The turn of the computer, searched a couple of turns deep.
+    pub fn best_play(&self) -> Turn {
+        let mut best_score = i32::MIN;
+        let mut best_turn = None;
+        for (turn, after) in self.turns() {
+            let score = after.minimax(DEPTH - 1, best_score, i32::MAX);
+            if best_turn.is_none() || score > best_score {
+                best_score = score;
+                best_turn = Some(turn);
+            }
+        }
+        best_turn.expect("the computer only plays unfinished games")
+    }
+
+    fn minimax(&self, depth: u32, mut alpha: i32, mut beta: i32) -> i32 {
+        let player = match self.state {
+            GameState::Win(Entity::Computer) => return WIN + depth as i32,
+            GameState::Win(_) => return -WIN - depth as i32,
+            GameState::Draw => return 0,
+            GameState::Playing(player) => player,
+            GameState::Ready => self.order,
+            GameState::Timeout(_) => unreachable!("variants aren't timed"),
+        };
+        if depth == 0 {
+            return self.evaluate();
+        }
+
+        let mut best = if player == Entity::Computer {
+            i32::MIN
+        } else {
+            i32::MAX
+        };
+        for (_, after) in self.turns() {
+            let score = after.minimax(depth - 1, alpha, beta);
+            if player == Entity::Computer {
+                best = best.max(score);
+                alpha = alpha.max(best);
+            } else {
+                best = best.min(score);
+                beta = beta.min(best);
+            }
+            if beta <= alpha {
+                break;
+            }
+        }
+        best
+    }
+
+    /// How good an unfinished position looks for the computer. Order counts the runs of five
+    /// cells still holding a single kind of mark, each mark in a run making it worth three
+    /// times more. Chaos wants as few of them as it can get, so the same sum counts against
+    /// it.
+    fn evaluate(&self) -> i32 {
+        let open: i32 = self
+            .board
+            .windows()
+            .map(|window| {
+                let count = |mark| {
+                    window
+                        .cells()
+                        .filter(|&(x, y)| self.board[x][y] == mark)
+                        .count()
+                };
+                match (count(Entity::Human), count(Entity::Computer)) {
+                    (0, marks) | (marks, 0) => [1, 3, 9, 27, 81][marks],
+                    _ => 0,
+                }
+            })
+            .sum();
+        if self.order == Entity::Computer {
+            open
+        } else {
+            -open
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Pentago: a 6×6 board made of four 3×3 quadrants, where each turn places a mark then
+//! rotates one quadrant a quarter turn. Five marks in a row win.
+
+use std::{collections::HashSet, sync::OnceLock};
+
+use super::{has_line, Board, Entity, GameState, MoveError};
+
+/// Side of the board.
+const SIZE: usize = 6;
+
+/// Marks in a row needed to win.
+const LINE: usize = 5;
+
+/// How many turns the computer looks ahead.
+const DEPTH: u32 = 2;
+
+/// Score of a won game, above any evaluation of an unfinished one.
+const WIN: i32 = 100_000;
+
+/// One of the four 3×3 quadrants, `row` and `col` being 0 or 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quadrant {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Quadrant {
+    pub const ALL: [Quadrant; 4] = [
+        Quadrant { row: 0, col: 0 },
+        Quadrant { row: 0, col: 1 },
+        Quadrant { row: 1, col: 0 },
+        Quadrant { row: 1, col: 1 },
+    ];
+
+    /// The cell of the board at `(x, y)` within the quadrant.
+    pub fn cell(self, x: usize, y: usize) -> (usize, usize) {
+        (self.row * 3 + x, self.col * 3 + y)
+    }
+}
+
+/// A quarter turn of a quadrant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    pub quadrant: Quadrant,
+    pub clockwise: bool,
+}
+
+impl Rotation {
+    fn all() -> impl Iterator<Item = Rotation> {
+        Quadrant::ALL.into_iter().flat_map(|quadrant| {
+            [true, false].map(|clockwise| Rotation {
+                quadrant,
+                clockwise,
+            })
+        })
+    }
+
+    fn apply(self, board: &mut Board) {
+        let quadrant = self.quadrant;
+        let before = *board;
+        for x in 0..3 {
+            for y in 0..3 {
+                let (from_x, from_y) = if self.clockwise {
+                    (2 - y, x)
+                } else {
+                    (y, 2 - x)
+                };
+                let (to_x, to_y) = quadrant.cell(x, y);
+                let (from_x, from_y) = quadrant.cell(from_x, from_y);
+                board[to_x][to_y] = before[from_x][from_y];
+            }
+        }
+    }
+}
+
+/// A whole turn: the cell where the mark is placed, then the rotation.
+pub type Turn = ((usize, usize), Rotation);
+
+/// Every run of five cells of the board, where a line can be made.
+fn windows() -> &'static [[(usize, usize); LINE]] {
+    static WINDOWS: OnceLock<Vec<[(usize, usize); LINE]>> = OnceLock::new();
+    WINDOWS.get_or_init(|| {
+        const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+        let mut windows = vec![];
+        for x in 0..SIZE as isize {
+            for y in 0..SIZE as isize {
+                for (dx, dy) in DIRECTIONS {
+                    let cells = (0..LINE as isize).map(|i| (x + dx * i, y + dy * i));
+                    if cells.clone().all(|(x, y)| {
+                        (0..SIZE as isize).contains(&x) && (0..SIZE as isize).contains(&y)
+                    }) {
+                        let mut window = [(0, 0); LINE];
+                        for (slot, (x, y)) in window.iter_mut().zip(cells) {
+                            *slot = (x as usize, y as usize);
+                        }
+                        windows.push(window);
+                    }
+                }
+            }
+        }
+        windows
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pentago {
+    board: Board,
+    state: GameState,
+    /// The mark placed this turn, waiting for the rotation.
+    placed: Option<(usize, usize)>,
+}
+
+impl Default for Pentago {
+    fn default() -> Self {
+        Pentago {
+            board: Board::new(SIZE).with_line(LINE),
+            state: GameState::default(),
+            placed: None,
+        }
+    }
+}
+
+impl Pentago {
+    pub fn start(&mut self) {
+        self.state = GameState::Playing(Entity::Human);
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state.clone()
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// The mark placed this turn while the player still has to rotate a quadrant.
+    pub fn placed(&self) -> Option<(usize, usize)> {
+        self.placed
+    }
+
+    /// Places the mark of the player to move, a line made by the mark alone wins right away.
+    /// A refused move leaves the game as it was.
+    pub fn place(&mut self, x: usize, y: usize) -> Result<(), MoveError> {
+        let GameState::Playing(entity) = self.state else {
+            return Err(MoveError::GameFinished);
+        };
+        if self.placed.is_some() {
+            return Err(MoveError::MustRotate);
+        }
+        if x >= SIZE || y >= SIZE {
+            return Err(MoveError::OutOfBounds);
+        }
+        if self.board[x][y] != Entity::Empty {
+            return Err(MoveError::CellOccupied);
+        }
+
+        self.board[x][y] = entity;
+        self.placed = Some((x, y));
+        if has_line(&self.board, entity) {
+            self.state = GameState::Win(entity);
+        }
+        Ok(())
+    }
+
+    /// Ends the turn by rotating a quadrant. A rotation can complete lines of both players at
+    /// once, which is a draw.
+    pub fn rotate(&mut self, rotation: Rotation) -> Result<(), MoveError> {
+        let GameState::Playing(entity) = self.state else {
+            return Err(MoveError::GameFinished);
+        };
+        if self.placed.take().is_none() {
+            return Err(MoveError::MustPlace);
+        }
+
+        rotation.apply(&mut self.board);
+        self.state = match (
+            has_line(&self.board, entity),
+            has_line(&self.board, !entity),
+        ) {
+            (true, true) => GameState::Draw,
+            (true, false) => GameState::Win(entity),
+            (false, true) => GameState::Win(!entity),
+            _ if self.board.is_full() => GameState::Draw,
+            _ => GameState::Playing(!entity),
+        };
+        Ok(())
+    }
+
+    /// Every distinct position the player to move can reach in one turn, with the turn
+    /// reaching it. Rotating an empty quadrant either way gives the same position, so those
+    /// are only tried once.
+    fn turns(&self) -> Vec<(Turn, Pentago)> {
+        let mut seen = HashSet::new();
+        let mut turns = vec![];
+        for (cell, entity) in self.board.cells() {
+            if entity != Entity::Empty {
+                continue;
+            }
+            let mut placed = self.clone();
+            placed
+                .place(cell.0, cell.1)
+                .expect("turns are played on empty cells");
+            for rotation in Rotation::all() {
+                let mut after = placed.clone();
+                if !placed.state.is_finished() {
+                    after.rotate(rotation).expect("the mark is placed");
+                }
+                if seen.insert(after.board) {
+                    turns.push(((cell, rotation), after));
+                }
+                // The placement already won, the rotation doesn't matter.
+                if placed.state.is_finished() {
+                    break;
+                }
+            }
+        }
+        turns
+    }
+
+    /// The turn of the computer, searched a couple of turns deep.
+    pub fn best_play(&self) -> Turn {
+        let mut best_score = i32::MIN;
+        let mut best_turn = None;
+        for (turn, after) in self.turns() {
+            let score = after.minimax(DEPTH - 1, best_score, i32::MAX);
+            if best_turn.is_none() || score > best_score {
+                best_score = score;
+                best_turn = Some(turn);
+            }
+        }
+        best_turn.expect("the computer only plays unfinished games")
+    }
+
+    fn minimax(&self, depth: u32, mut alpha: i32, mut beta: i32) -> i32 {
+        let player = match self.state {
+            GameState::Win(Entity::Computer) => return WIN + depth as i32,
+            GameState::Win(_) => return -WIN - depth as i32,
+            GameState::Draw => return 0,
+            GameState::Playing(player) => player,
+            GameState::Ready => Entity::Human,
+            GameState::Timeout(_) => unreachable!("variants aren't timed"),
+        };
+        if depth == 0 {
+            return self.evaluate();
+        }
+
+        let mut best = if player == Entity::Computer {
+            i32::MIN
+        } else {
+            i32::MAX
+        };
+        for (_, after) in self.turns() {
+            let score = after.minimax(depth - 1, alpha, beta);
+            if player == Entity::Computer {
+                best = best.max(score);
+                alpha = alpha.max(best);
+            } else {
+                best = best.min(score);
+                beta = beta.min(best);
+            }
+            if beta <= alpha {
+                break;
+            }
+        }
+        best
+    }
+
+    /// How good an unfinished position looks for the computer, from the runs of five cells
+    /// each side can still fill: every mark already in a run makes it worth four times more.
+    fn evaluate(&self) -> i32 {
+        windows()
+            .iter()
+            .map(|window| {
+                let count = |entity| {
+                    window
+                        .iter()
+                        .filter(|&&(x, y)| self.board[x][y] == entity)
+                        .count()
+                };
+                let worth = |marks| [0, 1, 4, 16, 64, WIN][marks];
+                match (count(Entity::Computer), count(Entity::Human)) {
+                    (marks, 0) => worth(marks),
+                    (0, marks) => -worth(marks),
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,62 @@
+//! How each side of a game picks its moves, so the app asks whoever is to move rather than
+//! assuming the human clicks and the computer replies. A player over the network would be one
+//! more implementation.
+
+use rand::seq::SliceRandom;
+
+use super::{Board, Computer};
+
+/// A side of the game, picking the cells it plays.
+pub trait Player: Send {
+    /// The cell to play on `board`, which has one left. `None` for interactive players, whose
+    /// moves come from the window.
+    fn choose_move(&self, board: &Board) -> Option<(usize, usize)>;
+
+    /// Whether the moves come from the window, clicked or typed, rather than from
+    /// [`Player::choose_move`].
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// The human at the window.
+pub struct HumanPlayer;
+
+impl Player for HumanPlayer {
+    fn choose_move(&self, _board: &Board) -> Option<(usize, usize)> {
+        None
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+/// The engine, playing the computer's side. Clones of the engine share its table, so moves
+/// picked in the background still warm it up.
+pub struct MinimaxPlayer(pub Computer);
+
+impl Player for MinimaxPlayer {
+    fn choose_move(&self, board: &Board) -> Option<(usize, usize)> {
+        self.0.clone().best_play(*board)
+    }
+}
+
+/// Plays any cell left.
+pub struct RandomPlayer;
+
+impl RandomPlayer {
+    /// Any cell left on `board`, which has one.
+    pub fn pick(&self, board: &Board) -> (usize, usize) {
+        let free: Vec<_> = board.playable().collect();
+        *free
+            .choose(&mut rand::thread_rng())
+            .expect("players only move on unfinished boards")
+    }
+}
+
+impl Player for RandomPlayer {
+    fn choose_move(&self, board: &Board) -> Option<(usize, usize)> {
+        Some(self.pick(board))
+    }
+}
@@ -0,0 +1,307 @@
+//! Tablebases: the result with perfect play of every position reachable on a board, solved
+//! once ahead of time and looked up by the engine instead of searching.
+//!
+//! Only classic rules are covered, on boards without gravity nor sliding marks, in games the
+//! human opened: the side to move follows from the number of marks.
+//!
+//! A file starts with a small header, the magic bytes, the format version, the size of the
+//! board, the length of a line, a reserved byte and the CRC-32 of the rest, followed by 2 bits
+//! per position. Positions are indexed by reading the board as a number in base 3, the first
+//! cell being the least significant digit.
+//!
+//! Files are memory-mapped rather than read: the 4×4 one is about 10 MB and a game only ever
+//! looks a few thousand positions up, so only their pages need to be in memory.
+
+use std::{fs, io, ops::Deref, path::Path};
+
+use memmap2::Mmap;
+
+use super::{has_line, Board, Entity, Rules};
+
+const MAGIC: &[u8; 4] = b"TTTB";
+/// Version 2 added the checksum.
+const VERSION: u8 = 2;
+const HEADER: usize = 12;
+
+/// The result of a position, as stored in the file.
+const UNREACHABLE: u8 = 0;
+const DRAW: u8 = 1;
+const HUMAN: u8 = 2;
+const COMPUTER: u8 = 3;
+
+/// Boards small enough to be solved, as `(size, line)`.
+pub const SUPPORTED: [(usize, usize); 2] = [(3, 3), (4, 3)];
+
+pub struct Tablebase {
+    size: usize,
+    line: usize,
+    /// The results, four positions per byte.
+    packed: Packed,
+}
+
+/// Where the results are kept: in memory right after solving, mapped from a file once loaded.
+enum Packed {
+    Solved(Vec<u8>),
+    /// The whole file, header included.
+    Mapped(Mmap),
+}
+
+impl Deref for Packed {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Packed::Solved(packed) => packed,
+            Packed::Mapped(file) => &file[HEADER..],
+        }
+    }
+}
+
+fn positions(size: usize) -> usize {
+    3usize.pow((size * size) as u32)
+}
+
+fn digit(entity: Entity) -> usize {
+    match entity {
+        Entity::Empty => 0,
+        Entity::Human => 1,
+        Entity::Computer => 2,
+    }
+}
+
+fn index(board: &Board) -> usize {
+    board
+        .cells()
+        .fold((0, 1), |(index, power), (_, entity)| {
+            (index + power * digit(entity), power * 3)
+        })
+        .0
+}
+
+/// Whose turn it is on `board`, the human having started.
+fn to_move(board: &Board) -> Entity {
+    let count = |entity| board.cells().filter(|(_, mark)| *mark == entity).count();
+    if count(Entity::Human) == count(Entity::Computer) {
+        Entity::Human
+    } else {
+        Entity::Computer
+    }
+}
+
+fn win_of(entity: Entity) -> u8 {
+    match entity {
+        Entity::Human => HUMAN,
+        _ => COMPUTER,
+    }
+}
+
+/// The best result `player` can get out of the results of its moves.
+fn best(player: Entity, results: impl Iterator<Item = u8>) -> u8 {
+    let mut draw = false;
+    let mut any = false;
+    for result in results {
+        if result == win_of(player) {
+            return result;
+        }
+        draw |= result == DRAW;
+        any = true;
+    }
+    match (any, draw) {
+        (false, _) | (_, true) => DRAW,
+        _ => win_of(!player),
+    }
+}
+
+/// Solves every position reachable from the empty board, one byte per position.
+struct Solver {
+    results: Vec<u8>,
+    powers: Vec<usize>,
+    solved: usize,
+}
+
+impl Solver {
+    fn solve(&mut self, board: &mut Board, index: usize, player: Entity) -> u8 {
+        if self.results[index] != UNREACHABLE {
+            return self.results[index];
+        }
+        let cells: Vec<_> = board.playable().collect();
+        let mut results = Vec::with_capacity(cells.len());
+        for (x, y) in cells {
+            let child = index + self.powers[x * board.size() + y] * digit(player);
+            board[x][y] = player;
+            let result = if board.has_line_through(player, x, y) {
+                self.store(child, win_of(player))
+            } else {
+                self.solve(board, child, !player)
+            };
+            board[x][y] = Entity::Empty;
+            results.push(result);
+        }
+        self.store(index, best(player, results.into_iter()))
+    }
+
+    fn store(&mut self, index: usize, result: u8) -> u8 {
+        if self.results[index] == UNREACHABLE {
+            self.solved += 1;
+        }
+        self.results[index] = result;
+        result
+    }
+}
+
+impl Tablebase {
+    /// Memory taken by the results.
+    pub fn bytes(&self) -> usize {
+        self.packed.len()
+    }
+
+    /// Solves the board of `size` where `line` marks in a row win, calling `progress` with the
+    /// number of first moves solved out of all of them, and the positions solved so far.
+    pub fn generate(
+        size: usize,
+        line: usize,
+        mut progress: impl FnMut(usize, usize, usize),
+    ) -> Tablebase {
+        let mut board = Board::new(size).with_line(line);
+        let mut solver = Solver {
+            results: vec![UNREACHABLE; positions(size)],
+            powers: (0..size * size).map(|i| 3usize.pow(i as u32)).collect(),
+            solved: 0,
+        };
+        let first: Vec<_> = board.playable().collect();
+        let mut results = vec![];
+        for (i, &(x, y)) in first.iter().enumerate() {
+            let child = solver.powers[x * size + y] * digit(Entity::Human);
+            board[x][y] = Entity::Human;
+            results.push(solver.solve(&mut board, child, Entity::Computer));
+            board[x][y] = Entity::Empty;
+            progress(i + 1, first.len(), solver.solved);
+        }
+        solver.store(0, best(Entity::Human, results.into_iter()));
+
+        let mut packed = vec![0; solver.results.len().div_ceil(4)];
+        for (i, result) in solver.results.into_iter().enumerate() {
+            packed[i / 4] |= result << (i % 4 * 2);
+        }
+        Tablebase {
+            size,
+            line,
+            packed: Packed::Solved(packed),
+        }
+    }
+
+    fn result(&self, index: usize) -> u8 {
+        self.packed[index / 4] >> (index % 4 * 2) & 0b11
+    }
+
+    /// Who wins `board` with perfect play when `player` moves next: `Some(Entity::Empty)` for a
+    /// draw, `None` when the tablebase doesn't cover the position.
+    pub fn lookup(&self, board: &Board, player: Entity, rules: Rules) -> Option<Entity> {
+        let covered = rules == Rules::Classic
+            && board.size() == self.size
+            && board.line() == self.line
+            && !board.has_gravity()
+            && board.pieces().is_none()
+            && to_move(board) == player;
+        if !covered {
+            return None;
+        }
+        match self.result(index(board)) {
+            DRAW => Some(Entity::Empty),
+            HUMAN => Some(Entity::Human),
+            COMPUTER => Some(Entity::Computer),
+            _ => None,
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut contents = Vec::with_capacity(HEADER + self.packed.len());
+        contents.extend_from_slice(MAGIC);
+        contents.extend_from_slice(&[VERSION, self.size as u8, self.line as u8, 0]);
+        contents.extend_from_slice(&crc32fast::hash(&self.packed).to_le_bytes());
+        contents.extend_from_slice(&self.packed);
+        fs::write(path, contents)
+    }
+
+    /// Maps the file at `path`. The checksum is checked right away, which reads the file once,
+    /// but its pages are left for the system to drop and read back when looked up again.
+    pub fn load(path: &Path) -> io::Result<Tablebase> {
+        let file = fs::File::open(path)?;
+        // SAFETY: tablebases are only written whole by the `tablebase` command, nothing
+        // changes the file while it's mapped.
+        let mapped = unsafe { Mmap::map(&file)? };
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+        let header = mapped
+            .get(..HEADER)
+            .ok_or_else(|| invalid("truncated header"))?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("not a tablebase"));
+        }
+        if header[4] != VERSION {
+            return Err(invalid("unsupported tablebase version, generate it again"));
+        }
+        let (size, line) = (header[5] as usize, header[6] as usize);
+        if !SUPPORTED.contains(&(size, line)) {
+            return Err(invalid("unsupported board"));
+        }
+        if mapped.len() - HEADER != positions(size).div_ceil(4) {
+            return Err(invalid("wrong length"));
+        }
+        let checksum = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+        if crc32fast::hash(&mapped[HEADER..]) != checksum {
+            return Err(invalid("corrupted, the checksum doesn't match"));
+        }
+        Ok(Tablebase {
+            size,
+            line,
+            packed: Packed::Mapped(mapped),
+        })
+    }
+
+    /// Checks every stored result against the results of the moves from its position, which
+    /// is enough for the whole table to be right. Returns the number of positions checked, or
+    /// the first position which is wrong.
+    pub fn verify(&self) -> Result<usize, Board> {
+        let mut checked = 0;
+        for i in 0..positions(self.size) {
+            let stored = self.result(i);
+            if stored == UNREACHABLE {
+                continue;
+            }
+            let mut board = Board::new(self.size).with_line(self.line);
+            let mut rest = i;
+            for x in 0..self.size {
+                for y in 0..self.size {
+                    board[x][y] = [Entity::Empty, Entity::Human, Entity::Computer][rest % 3];
+                    rest /= 3;
+                }
+            }
+            let player = to_move(&board);
+            let expected = if has_line(&board, !player) {
+                win_of(!player)
+            } else {
+                let results: Vec<_> = board
+                    .playable()
+                    .map(|(x, y)| {
+                        let mut child = board;
+                        child[x][y] = player;
+                        match self.result(index(&child)) {
+                            // A move making a line ends the game, it's stored all the same.
+                            UNREACHABLE if has_line(&child, player) => win_of(player),
+                            result => result,
+                        }
+                    })
+                    .collect();
+                if results.contains(&UNREACHABLE) {
+                    return Err(board);
+                }
+                best(player, results.into_iter())
+            };
+            if stored != expected {
+                return Err(board);
+            }
+            checked += 1;
+        }
+        Ok(checked)
+    }
+}
@@ -0,0 +1,209 @@
+//! Ultimate tic-tac-toe: the board is a 3×3 grid of 3×3 boards. Completing a line on a small
+//! board claims its cell of the big one, and the cell played sends the opponent to the small
+//! board in the same position.
+
+use super::{has_line, Board, Entity, GameState, MoveError};
+
+/// A cell of the big board followed by a cell of the small board inside it.
+pub type Move = ((usize, usize), (usize, usize));
+
+/// How many moves the computer looks ahead, the whole tree is far out of reach.
+const DEPTH: u32 = 5;
+
+/// Score of a won game, above any evaluation of an unfinished one.
+const WIN: i32 = 100_000;
+
+/// Every line of a 3×3 board.
+const LINES: [[(usize, usize); 3]; 8] = [
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(0, 2), (1, 1), (2, 0)],
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ultimate {
+    /// The small boards, `boards[x][y]` sits in row `x` and column `y` of the big board.
+    boards: [[Board; 3]; 3],
+    /// Who claimed each small board, empty while nobody did.
+    claimed: Board,
+    /// The small board the next move must be played in, any open one when `None`.
+    target: Option<(usize, usize)>,
+    state: GameState,
+}
+
+impl Ultimate {
+    pub fn start(&mut self) {
+        self.state = GameState::Playing(Entity::Human);
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state.clone()
+    }
+
+    pub fn board(&self, (x, y): (usize, usize)) -> &Board {
+        &self.boards[x][y]
+    }
+
+    /// Who claimed the small board at `outer`.
+    pub fn owner(&self, (x, y): (usize, usize)) -> Entity {
+        self.claimed[x][y]
+    }
+
+    /// Whether no move can be played in the small board at `outer` anymore, because it was
+    /// claimed or filled up.
+    pub fn is_closed(&self, outer: (usize, usize)) -> bool {
+        self.owner(outer) != Entity::Empty || self.board(outer).is_full()
+    }
+
+    /// Whether the next move may be played in the small board at `outer`.
+    pub fn is_open(&self, outer: (usize, usize)) -> bool {
+        self.state.is_playable()
+            && !self.is_closed(outer)
+            && self.target.is_none_or(|target| target == outer)
+    }
+
+    /// Every move the player to move can play.
+    pub fn actions(&self) -> Vec<Move> {
+        let outers = (0..3).flat_map(|x| (0..3).map(move |y| (x, y)));
+        outers
+            .filter(|outer| self.is_open(*outer))
+            .flat_map(|outer| {
+                self.board(outer)
+                    .cells()
+                    .filter(|(_, entity)| *entity == Entity::Empty)
+                    .map(move |(inner, _)| (outer, inner))
+            })
+            .collect()
+    }
+
+    /// Plays the mark of the player to move. A refused move leaves the game as it was.
+    pub fn update(
+        &mut self,
+        outer: (usize, usize),
+        inner: (usize, usize),
+    ) -> Result<(), MoveError> {
+        let GameState::Playing(entity) = self.state else {
+            return Err(MoveError::GameFinished);
+        };
+        if [outer, inner].iter().any(|&(x, y)| x >= 3 || y >= 3) {
+            return Err(MoveError::OutOfBounds);
+        }
+        if !self.is_open(outer) {
+            return Err(MoveError::ClosedBoard);
+        }
+        if self.board(outer)[inner.0][inner.1] != Entity::Empty {
+            return Err(MoveError::CellOccupied);
+        }
+
+        self.play(entity, outer, inner);
+        Ok(())
+    }
+
+    fn play(&mut self, entity: Entity, outer: (usize, usize), inner: (usize, usize)) {
+        let board = &mut self.boards[outer.0][outer.1];
+        board[inner.0][inner.1] = entity;
+        if has_line(board, entity) {
+            self.claimed[outer.0][outer.1] = entity;
+            if has_line(&self.claimed, entity) {
+                return self.state = GameState::Win(entity);
+            }
+        }
+
+        let outers = (0..3).flat_map(|x| (0..3).map(move |y| (x, y)));
+        if outers.into_iter().all(|outer| self.is_closed(outer)) {
+            return self.state = GameState::Draw;
+        }
+
+        self.target = (!self.is_closed(inner)).then_some(inner);
+        self.state = GameState::Playing(!entity);
+    }
+
+    /// The move of the computer, searched a few moves deep.
+    pub fn best_play(&self) -> Move {
+        let mut best_score = i32::MIN;
+        let mut best_move = None;
+        for action in self.actions() {
+            let mut after = self.clone();
+            after.play(Entity::Computer, action.0, action.1);
+            let score = after.minimax(DEPTH - 1, best_score, i32::MAX);
+            if best_move.is_none() || score > best_score {
+                best_score = score;
+                best_move = Some(action);
+            }
+        }
+        best_move.expect("the computer only plays unfinished games")
+    }
+
+    fn minimax(&self, depth: u32, mut alpha: i32, mut beta: i32) -> i32 {
+        let player = match self.state {
+            GameState::Win(Entity::Computer) => return WIN + depth as i32,
+            GameState::Win(_) => return -WIN - depth as i32,
+            GameState::Draw => return 0,
+            GameState::Playing(player) => player,
+            GameState::Ready => Entity::Human,
+            GameState::Timeout(_) => unreachable!("variants aren't timed"),
+        };
+        if depth == 0 {
+            return self.evaluate();
+        }
+
+        let mut best = if player == Entity::Computer {
+            i32::MIN
+        } else {
+            i32::MAX
+        };
+        for (outer, inner) in self.actions() {
+            let mut after = self.clone();
+            after.play(player, outer, inner);
+            let score = after.minimax(depth - 1, alpha, beta);
+            if player == Entity::Computer {
+                best = best.max(score);
+                alpha = alpha.max(best);
+            } else {
+                best = best.min(score);
+                beta = beta.min(best);
+            }
+            if beta <= alpha {
+                break;
+            }
+        }
+        best
+    }
+
+    /// How good an unfinished position looks for the computer: claimed boards count the
+    /// most, then lines one cell away from completion on the big board and the small ones.
+    fn evaluate(&self) -> i32 {
+        let mut score = 100 * threats(&self.claimed);
+        for x in 0..3 {
+            for y in 0..3 {
+                score += match self.claimed[x][y] {
+                    Entity::Computer => 300,
+                    Entity::Human => -300,
+                    Entity::Empty if self.board((x, y)).is_full() => 0,
+                    Entity::Empty => 10 * threats(self.board((x, y))),
+                };
+            }
+        }
+        score
+    }
+}
+
+/// Lines the computer could complete with one more mark, minus those of the human.
+fn threats(board: &Board) -> i32 {
+    LINES
+        .iter()
+        .map(|line| {
+            let count = |entity| line.iter().filter(|(x, y)| board[*x][*y] == entity).count();
+            match (count(Entity::Computer), count(Entity::Human)) {
+                (2, 0) => 1,
+                (0, 2) => -1,
+                _ => 0,
+            }
+        })
+        .sum()
+}
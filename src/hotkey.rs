@@ -0,0 +1,65 @@
+//! The key bringing the window to the front from any app, registered with the OS. X11 only on
+//! Linux.
+
+use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use iced::{
+    futures::{channel::mpsc, SinkExt, StreamExt},
+    subscription, Subscription,
+};
+
+/// The registered key, replaced whenever the settings change it.
+pub struct Summon {
+    manager: GlobalHotKeyManager,
+    hotkey: Option<HotKey>,
+}
+
+impl Summon {
+    /// Has to run on the thread of the event loop, i.e. from the app rather than a task.
+    pub fn new() -> Result<Summon, String> {
+        Ok(Summon {
+            manager: GlobalHotKeyManager::new().map_err(|err| err.to_string())?,
+            hotkey: None,
+        })
+    }
+
+    /// Registers `keys`, e.g. `Ctrl+Shift+T`, in place of the previous ones, none when empty.
+    pub fn set(&mut self, keys: &str) -> Result<(), String> {
+        if let Some(hotkey) = self.hotkey.take() {
+            if let Err(err) = self.manager.unregister(hotkey) {
+                tracing::warn!("couldn't unregister the summon key: {}", err);
+            }
+        }
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let hotkey: HotKey = keys
+            .parse()
+            .map_err(|err: global_hotkey::hotkey::HotKeyParseError| err.to_string())?;
+        self.manager
+            .register(hotkey)
+            .map_err(|err| err.to_string())?;
+        self.hotkey = Some(hotkey);
+        Ok(())
+    }
+}
+
+/// Fires each time the registered key is pressed, whichever app has the focus.
+pub fn listen() -> Subscription<()> {
+    subscription::channel("summon", 10, |mut output| async move {
+        let (sender, mut receiver) = mpsc::unbounded();
+        // The events come through a blocking channel, read on a thread of its own.
+        std::thread::spawn(move || {
+            while let Ok(event) = GlobalHotKeyEvent::receiver().recv() {
+                if event.state == HotKeyState::Pressed && sender.unbounded_send(()).is_err() {
+                    break;
+                }
+            }
+        });
+        loop {
+            if receiver.next().await.is_none() {
+                iced::futures::future::pending::<()>().await;
+            }
+            let _ = output.send(()).await;
+        }
+    })
+}
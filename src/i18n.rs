@@ -0,0 +1,220 @@
+//! Translations of every string shown by the app, written as [Fluent](https://projectfluent.org)
+//! resources under `locales/` so plurals and grammatical variants live with each language.
+
+use std::borrow::Cow;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+mod format;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    /// English with accented and stretched out text, to spot hard-coded strings and layouts
+    /// which break with longer translations.
+    Pseudo,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::Spanish, Language::Pseudo];
+
+    fn id(self) -> LanguageIdentifier {
+        let id = match self {
+            Language::English | Language::Pseudo => "en-US",
+            Language::Spanish => "es",
+        };
+        id.parse().expect("language identifiers are valid")
+    }
+
+    /// The translation of the first language of the OS there's one for, English when there's
+    /// none.
+    pub fn detect() -> Language {
+        os_locales()
+            .iter()
+            .find_map(|locale| Language::matching(locale))
+            .unwrap_or_default()
+    }
+
+    /// The translation written in the language of `locale`, e.g. Spanish for `es-AR`,
+    /// whatever the region.
+    fn matching(locale: &str) -> Option<Language> {
+        let locale: LanguageIdentifier = locale.parse().ok()?;
+        [Language::English, Language::Spanish]
+            .into_iter()
+            .find(|language| language.id().language == locale.language)
+    }
+
+    fn resource(self) -> &'static str {
+        match self {
+            Language::English | Language::Pseudo => include_str!("../locales/en.ftl"),
+            Language::Spanish => include_str!("../locales/es.ftl"),
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::Pseudo => "Pséüdöö",
+        })
+    }
+}
+
+pub struct I18n {
+    language: Language,
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Default for I18n {
+    fn default() -> Self {
+        I18n::new(Language::default())
+    }
+}
+
+impl I18n {
+    pub fn new(language: Language) -> I18n {
+        let resource = FluentResource::try_new(language.resource().to_string())
+            .expect("the bundled translations are valid");
+        let mut bundle = FluentBundle::new(vec![language.id()]);
+        // iced has no bidirectional text support, the isolation marks would show up as boxes.
+        bundle.set_use_isolating(false);
+        if language == Language::Pseudo {
+            bundle.set_transform(Some(pseudolocalize));
+        }
+        bundle
+            .add_resource(resource)
+            .expect("the bundled translations have no duplicated messages");
+        I18n { language, bundle }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns the translation of the message `id`.
+    pub fn tr(&self, id: &str) -> String {
+        self.format(id, None)
+    }
+
+    /// Returns the translation of the message `id`, filling its variables with `args`.
+    pub fn tr_args<'a>(
+        &self,
+        id: &str,
+        args: impl IntoIterator<Item = (&'a str, FluentValue<'a>)>,
+    ) -> String {
+        self.format(id, Some(&FluentArgs::from_iter(args)))
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let Some(pattern) = self
+            .bundle
+            .get_message(id)
+            .and_then(|message| message.value())
+        else {
+            tracing::warn!("missing translation for {}", id);
+            return id.to_string();
+        };
+        let mut errors = vec![];
+        let text = self.bundle.format_pattern(pattern, args, &mut errors);
+        for error in errors {
+            tracing::warn!("couldn't translate {}: {}", id, error);
+        }
+        text.into_owned()
+    }
+}
+
+/// The locales of the user, the preferred one first, as language tags such as `es-AR`. The
+/// variables of POSIX systems come first, so a terminal can override the settings of the OS.
+fn os_locales() -> Vec<String> {
+    let mut locales: Vec<String> = std::env::var("LANGUAGE")
+        .unwrap_or_default()
+        .split(':')
+        .map(str::to_string)
+        .collect();
+    for variable in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        locales.extend(std::env::var(variable).ok());
+    }
+    locales.extend(system_locales());
+    locales
+        .iter()
+        // e.g. `es_AR.UTF-8@euro`.
+        .filter_map(|locale| locale.split(['.', '@']).next())
+        .filter(|locale| !locale.is_empty() && *locale != "C" && *locale != "POSIX")
+        .map(|locale| locale.replace('_', "-"))
+        .collect()
+}
+
+/// The preferred languages of the macOS settings, which apps opened from the Finder get no
+/// variable for.
+#[cfg(target_os = "macos")]
+fn system_locales() -> Vec<String> {
+    let Ok(output) = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleLanguages"])
+        .output()
+    else {
+        return vec![];
+    };
+    // A property list array, one quoted language tag per line.
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_end_matches(',')
+                .trim_matches('"')
+                .to_string()
+        })
+        .filter(|line| line != "(" && line != ")")
+        .collect()
+}
+
+/// The display language of Windows.
+#[cfg(windows)]
+fn system_locales() -> Vec<String> {
+    let Ok(output) = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", "(Get-UICulture).Name"])
+        .output()
+    else {
+        return vec![];
+    };
+    vec![String::from_utf8_lossy(&output.stdout).trim().to_string()]
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn system_locales() -> Vec<String> {
+    vec![]
+}
+
+/// Accents letters and doubles the vowels, which makes text about 30% longer. Only Latin-1
+/// letters are used since the bundled fonts don't cover anything else.
+fn pseudolocalize(text: &str) -> Cow<'_, str> {
+    let mut pseudo = String::with_capacity(text.len() * 2);
+    for c in text.chars() {
+        let accented = match c {
+            'a' => "àà",
+            'e' => "éé",
+            'i' => "î",
+            'o' => "öö",
+            'u' => "üü",
+            'A' => "Å",
+            'E' => "É",
+            'I' => "Î",
+            'O' => "Ø",
+            'U' => "Ü",
+            'c' => "ç",
+            'n' => "ñ",
+            'y' => "ý",
+            _ => {
+                pseudo.push(c);
+                continue;
+            }
+        };
+        pseudo.push_str(accented);
+    }
+    Cow::Owned(pseudo)
+}
@@ -0,0 +1,148 @@
+//! Numbers, durations and dates written the way the language of the app writes them, as ICU
+//! would for the few formats the app shows. Fluent leaves numbers as they are, so screens pass
+//! these already formatted rather than the raw values.
+
+use std::time::Duration;
+
+use super::{I18n, Language};
+
+/// How a language writes numbers.
+struct Numbers {
+    decimal: char,
+    group: char,
+    /// Fewest digits a number has before its thousands are grouped, Spanish leaving `1234`
+    /// alone.
+    grouped_from: usize,
+    /// Between a number and the percent sign.
+    percent_space: &'static str,
+    /// Times of the day go from 1 to 12 twice, AM then PM, rather than from 0 to 23.
+    twelve_hours: bool,
+}
+
+impl Language {
+    fn numbers(self) -> Numbers {
+        match self {
+            Language::English | Language::Pseudo => Numbers {
+                decimal: '.',
+                group: ',',
+                grouped_from: 4,
+                percent_space: "",
+                twelve_hours: true,
+            },
+            Language::Spanish => Numbers {
+                decimal: ',',
+                group: '.',
+                grouped_from: 5,
+                // A no-break space, so the sign never wraps to a line of its own.
+                percent_space: "\u{a0}",
+                twelve_hours: false,
+            },
+        }
+    }
+}
+
+impl I18n {
+    /// A whole number with its thousands grouped, e.g. `65,536` in English and `65.536` in
+    /// Spanish.
+    pub fn integer(&self, value: u64) -> String {
+        let numbers = self.language.numbers();
+        let digits = value.to_string();
+        if digits.len() < numbers.grouped_from {
+            return digits;
+        }
+        let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push(numbers.group);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// `value`, which isn't negative, rounded to `decimals` decimals, e.g. `1.5` in English and
+    /// `1,5` in Spanish.
+    pub fn decimal(&self, value: f64, decimals: usize) -> String {
+        let rounded = format!("{:.*}", decimals, value);
+        let (whole, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+        let whole = self.integer(whole.parse().unwrap_or_default());
+        match fraction {
+            "" => whole,
+            _ => format!("{whole}{}{fraction}", self.language.numbers().decimal),
+        }
+    }
+
+    /// `percent` out of a hundred with its sign, e.g. `87%` in English and `87 %` in Spanish.
+    pub fn percent(&self, percent: u32) -> String {
+        let numbers = self.language.numbers();
+        format!("{}{}%", self.integer(percent.into()), numbers.percent_space)
+    }
+
+    /// `duration` in its two largest units, to the second, e.g. `45 s`, `2 min 5 s` or
+    /// `1 h 20 min`.
+    pub fn duration(&self, duration: Duration) -> String {
+        let seconds = duration.as_secs();
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        let units = match (hours, minutes, seconds) {
+            (0, 0, seconds) => vec![(seconds, "s")],
+            (0, minutes, 0) => vec![(minutes, "min")],
+            (0, minutes, seconds) => vec![(minutes, "min"), (seconds, "s")],
+            (hours, 0, _) => vec![(hours, "h")],
+            (hours, minutes, _) => vec![(hours, "h"), (minutes, "min")],
+        };
+        units
+            .into_iter()
+            .map(|(count, unit)| format!("{}\u{a0}{unit}", self.integer(count)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The day `timestamp`, in seconds since 1970-01-01, falls on in the UTC calendar, e.g.
+    /// `Oct 17, 2026` in English and `17 oct 2026` in Spanish.
+    pub fn date(&self, timestamp: u64) -> String {
+        let (year, month, day) = civil_date(timestamp / 86_400);
+        self.tr_args(
+            "date",
+            [
+                ("year", year.to_string().into()),
+                ("month", month.into()),
+                ("day", day.to_string().into()),
+            ],
+        )
+    }
+
+    /// The time of the day of `timestamp` in UTC, to the minute, e.g. `3:05 PM` in English and
+    /// `15:05` in Spanish.
+    pub fn time(&self, timestamp: u64) -> String {
+        let (hour, minute) = (timestamp / 3600 % 24, timestamp / 60 % 60);
+        if !self.language.numbers().twelve_hours {
+            return format!("{hour:02}:{minute:02}");
+        }
+        let half = if hour < 12 { "AM" } else { "PM" };
+        let hour = match hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        format!("{hour}:{minute:02}\u{a0}{half}")
+    }
+}
+
+/// The year, month and day `days` after 1970-01-01, see
+/// https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_date(days: u64) -> (i64, u32, u32) {
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as u32, day as u32)
+}
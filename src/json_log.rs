@@ -0,0 +1,292 @@
+//! `--log-json`: the events of the app written as JSON lines, one object per event, to attach
+//! to bug reports. Names of people, the profile's and the chat's, are redacted before anything
+//! reaches the file, and the file is rotated once it grows past a few hundred KiB. The last
+//! lines are kept in memory too, with or without a file, for the feedback the app sends.
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Metadata, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan};
+
+/// Size past which the file is rotated, in bytes.
+const MAX_SIZE: u64 = 512 * 1024;
+
+/// Rotated files kept besides the current one, `log.1` being the newest.
+const BACKUPS: usize = 3;
+
+/// Lines kept in memory.
+const RECENT_LINES: usize = 1000;
+
+/// Fields always holding a name, written as [`REDACTED`] whatever they hold.
+const NAME_FIELDS: [&str; 3] = ["user", "name", "channel"];
+
+const REDACTED: &str = "[redacted]";
+
+/// Names to take out of every message, as the app last set them.
+static NAMES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// The last lines logged, oldest first, one per line.
+pub fn recent() -> String {
+    RECENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+/// Redacts `names` from the events logged from now on, in place of the previous ones.
+pub fn redact_names(names: impl IntoIterator<Item = String>) {
+    *NAMES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = names
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+}
+
+/// Whether an event goes to the file: warnings and errors from anywhere, and the app's own
+/// events down to debug, which carry the moves. The app's spans down to info name where each
+/// event happened, e.g. during startup, leaving out the ones of every update and frame.
+pub fn wanted(metadata: &Metadata<'_>) -> bool {
+    let ours = metadata.target().starts_with(env!("CARGO_CRATE_NAME"));
+    if metadata.is_span() {
+        return ours && *metadata.level() <= Level::INFO;
+    }
+    *metadata.level() <= Level::WARN || (ours && *metadata.level() <= Level::DEBUG)
+}
+
+/// Keeps each event it sees in memory, and writes it to the file at its path when there's one.
+#[derive(Default)]
+pub struct Layer {
+    file: Option<Mutex<Output>>,
+}
+
+struct Output {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl Output {
+    fn write(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 > MAX_SIZE {
+            self.rotate()?;
+        }
+        self.file.write_all(line)?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shifts `log` to `log.1`, `log.1` to `log.2` and so on, dropping the oldest, and starts
+    /// `log` over.
+    fn rotate(&mut self) -> io::Result<()> {
+        let backup = |index: usize| {
+            let mut name = self.path.clone().into_os_string();
+            name.push(format!(".{index}"));
+            PathBuf::from(name)
+        };
+        for index in (1..BACKUPS).rev() {
+            let from = backup(index);
+            if from.exists() {
+                fs::rename(&from, backup(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, backup(1))?;
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Layer {
+    /// Appends to the file at `path`, created when missing.
+    pub fn open(path: &Path) -> io::Result<Layer> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Layer {
+            file: Some(Mutex::new(Output {
+                path: path.to_path_buf(),
+                file,
+                size,
+            })),
+        })
+    }
+}
+
+impl<S> tracing_subscriber::Layer<S> for Layer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut fields = Fields(Map::new());
+        event.record(&mut fields);
+        let names = NAMES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        let home = dirs::home_dir().map(|home| home.display().to_string());
+        fields.redact(&names, home.as_deref());
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let mut line = Map::new();
+        line.insert("timestamp_ms".into(), timestamp.into());
+        line.insert("level".into(), metadata.level().as_str().into());
+        line.insert("target".into(), metadata.target().into());
+        if let Some(scope) = ctx.event_scope(event) {
+            let spans: Vec<Value> = scope.from_root().map(|span| span.name().into()).collect();
+            line.insert("spans".into(), spans.into());
+        }
+        line.extend(fields.0);
+
+        let text = Value::Object(line).to_string();
+        if let Some(file) = &self.file {
+            let mut output = file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            // Logging the failure would come back here.
+            if let Err(err) = output.write(format!("{text}\n").as_bytes()) {
+                eprintln!("couldn't write the JSON log: {err}");
+            }
+        }
+        let mut recent = RECENT
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if recent.len() == RECENT_LINES {
+            recent.pop_front();
+        }
+        recent.push_back(text);
+    }
+}
+
+/// `text` with each of `names` standing as a word of its own replaced by [`REDACTED`], and the
+/// home directory by `~`.
+fn redact(text: &str, names: &[String], home: Option<&str>) -> String {
+    let mut text = match home {
+        Some(home) if home.len() > 1 => text.replace(home, "~"),
+        _ => text.to_string(),
+    };
+    for name in names {
+        let mut redacted = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(at) = rest.find(name.as_str()) {
+            let (before, after) = (&rest[..at], &rest[at + name.len()..]);
+            let word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+            redacted.push_str(before);
+            if word(before.chars().next_back()) || word(after.chars().next()) {
+                redacted.push_str(name);
+            } else {
+                redacted.push_str(REDACTED);
+            }
+            rest = after;
+        }
+        redacted.push_str(rest);
+        text = redacted;
+    }
+    text
+}
+
+/// The fields of an event as JSON values, the message among them.
+struct Fields(Map<String, Value>);
+
+impl Fields {
+    /// Takes `names` and the `home` directory out of the text fields, and whatever
+    /// [`NAME_FIELDS`] hold.
+    fn redact(&mut self, names: &[String], home: Option<&str>) {
+        for (key, value) in self.0.iter_mut() {
+            if NAME_FIELDS.contains(&key.as_str()) {
+                *value = REDACTED.into();
+            } else if let Value::String(text) = value {
+                *text = redact(text, names, home);
+            }
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> Vec<String> {
+        vec!["ana".to_string()]
+    }
+
+    #[test]
+    fn redacts_whole_words() {
+        assert_eq!(
+            redact("ana won, well played ana!", &names(), None),
+            "[redacted] won, well played [redacted]!"
+        );
+    }
+
+    #[test]
+    fn keeps_names_inside_other_words() {
+        let text = "banana and anagrams";
+        assert_eq!(redact(text, &names(), None), text);
+    }
+
+    #[test]
+    fn replaces_the_home_directory() {
+        assert_eq!(
+            redact("saved to /home/ana/games", &[], Some("/home/ana")),
+            "saved to ~/games"
+        );
+        // A root home would turn every slash into one.
+        assert_eq!(redact("/tmp/log", &[], Some("/")), "/tmp/log");
+    }
+
+    #[test]
+    fn name_fields_are_always_redacted() {
+        let mut fields = Fields(Map::new());
+        fields.0.insert("user".into(), "someone".into());
+        fields.0.insert("moves".into(), 5.into());
+        fields.0.insert("message".into(), "someone joined".into());
+        fields.redact(&names(), None);
+        assert_eq!(fields.0["user"], REDACTED);
+        assert_eq!(fields.0["moves"], 5);
+        assert_eq!(fields.0["message"], "someone joined");
+    }
+}
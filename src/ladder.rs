@@ -0,0 +1,168 @@
+//! Scripted opponents weaker than the engine, each unlocked by beating the one below it.
+
+use iced::{
+    widget::{column, text},
+    Alignment, Element,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    features,
+    game::{
+        side::{Player, RandomPlayer},
+        winning_moves, Board, Computer, Entity,
+    },
+    i18n::I18n,
+    storage,
+    ui::{
+        components::{button_style, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_M, TEXT_TITLE},
+    },
+    Message,
+};
+
+/// The rungs of the ladder, from the weakest to the strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Bot {
+    /// Plays anywhere.
+    Rookie,
+    /// Mirrors the human's last move through the center.
+    Copycat,
+    /// Takes wins and blocks threats, otherwise plays anywhere.
+    Blocker,
+    /// Plays like the engine but dozes off every now and then.
+    Sleepy,
+    /// Plays by a value network learned through self-play, off the ladder. Only offered with
+    /// the `rl` feature, the engine stands in for it in other builds, e.g. for saved games.
+    Network,
+}
+
+impl Bot {
+    pub const ALL: [Bot; 4] = [Bot::Rookie, Bot::Copycat, Bot::Blocker, Bot::Sleepy];
+
+    /// Id of the translated name of the bot.
+    pub fn name(self) -> &'static str {
+        match self {
+            Bot::Rookie => "bot-rookie",
+            Bot::Copycat => "bot-copycat",
+            Bot::Blocker => "bot-blocker",
+            Bot::Sleepy => "bot-sleepy",
+            Bot::Network => "bot-network",
+        }
+    }
+
+    /// Picks the move of the bot, `last` being the move the human just played, `None` when the
+    /// bot opens the game.
+    pub fn play(self, ia: &Computer, board: Board, last: Option<(usize, usize)>) -> (usize, usize) {
+        let anywhere = || RandomPlayer.pick(&board);
+        match self {
+            Bot::Rookie => anywhere(),
+            Bot::Copycat => {
+                let edge = board.size() - 1;
+                match last.map(|(x, y)| (edge - x, edge - y)) {
+                    Some((x, y)) if board.is_playable(x, y) => (x, y),
+                    _ => anywhere(),
+                }
+            }
+            Bot::Blocker => winning_moves(&board, Entity::Computer)
+                .first()
+                .or(winning_moves(&board, Entity::Human).first())
+                .copied()
+                .unwrap_or_else(anywhere),
+            Bot::Sleepy if rand::thread_rng().gen_ratio(1, 3) => anywhere(),
+            Bot::Sleepy => ia.clone().best_play(board).unwrap_or_else(anywhere),
+            #[cfg(feature = "rl")]
+            Bot::Network => crate::engine::network::play(ia, board).unwrap_or_else(anywhere),
+            #[cfg(not(feature = "rl"))]
+            Bot::Network => ia.clone().best_play(board).unwrap_or_else(anywhere),
+        }
+    }
+}
+
+/// A bot as a side of the game, answering `last`, the move the human just played.
+pub struct BotPlayer {
+    pub bot: Bot,
+    pub ia: Computer,
+    pub last: Option<(usize, usize)>,
+}
+
+impl Player for BotPlayer {
+    fn choose_move(&self, board: &Board) -> Option<(usize, usize)> {
+        Some(self.bot.play(&self.ia, *board, self.last))
+    }
+}
+
+/// How far up the ladder the human got.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ladder {
+    /// Number of bots beaten so far.
+    beaten: usize,
+}
+
+impl Ladder {
+    pub const FILE: &'static str = "ladder";
+
+    pub fn is_unlocked(&self, bot: Bot) -> bool {
+        bot as usize <= self.beaten
+    }
+
+    /// The bot to beat to climb further, `None` once the whole ladder is beaten.
+    pub fn next(&self) -> Option<Bot> {
+        Bot::ALL.get(self.beaten).copied()
+    }
+
+    /// Records a win against `bot`, returns whether it was the next bot to beat.
+    pub fn beat(&mut self, bot: Bot) -> bool {
+        let climbed = self.next() == Some(bot);
+        if climbed {
+            self.beaten += 1;
+        }
+        climbed
+    }
+
+    /// The ladder drawn as a path climbing from the weakest bot up to the engine.
+    pub fn view(&self, opponent: Option<Bot>, i18n: &I18n) -> Element<'_, Message> {
+        let rung = |name: String, challenge: Option<Bot>, unlocked: bool| {
+            let label = if challenge == opponent {
+                i18n.tr_args("ladder-current", [("bot", name.into())])
+            } else if unlocked {
+                name
+            } else {
+                i18n.tr_args("ladder-locked", [("bot", name.into())])
+            };
+            let mut rung = primary(label).style(button_style(iced::theme::Button::Secondary));
+            if unlocked {
+                rung = rung.on_press(Message::Challenge(challenge));
+            }
+            focusable(rung)
+        };
+        let mut ladder = column![
+            text(i18n.tr("ladder")).size(TEXT_TITLE),
+            rung(i18n.tr("bot-engine"), None, true)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M);
+        for bot in Bot::ALL.into_iter().rev() {
+            ladder = ladder.push(text("|")).push(rung(
+                i18n.tr(bot.name()),
+                Some(bot),
+                self.is_unlocked(bot),
+            ));
+        }
+        if features::RL {
+            ladder = ladder.push(rung(i18n.tr(Bot::Network.name()), Some(Bot::Network), true));
+        }
+        ladder
+            .push(focusable(
+                secondary(i18n.tr("back")).on_press(Message::ToggleLadder),
+            ))
+            .into()
+    }
+}
+
+impl storage::Versioned for Ladder {
+    const VERSION: u32 = 1;
+}
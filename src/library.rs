@@ -0,0 +1,195 @@
+//! Statistics over the replays saved in the library, e.g. how often a move was chosen from a
+//! position.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    game::{Board, Entity, Game, GameState, MoveError, Rules},
+    storage,
+};
+
+/// The game embedded in a replay page, as written by [`crate::export::game_json`].
+#[derive(Deserialize)]
+struct Replay {
+    size: usize,
+    empty: String,
+    /// Marks placed before the first move, missing from pages saved before scrambled starts.
+    #[serde(default)]
+    start: Vec<ReplayMove>,
+    moves: Vec<ReplayMove>,
+}
+
+#[derive(Deserialize)]
+struct ReplayMove {
+    player: String,
+    x: usize,
+    y: usize,
+    from: Option<[usize; 2]>,
+    /// The mark put down when it isn't the player's own, under Notakto.
+    #[serde(default)]
+    mark: Option<String>,
+}
+
+impl ReplayMove {
+    /// The side whose mark was put down.
+    fn mark(&self) -> Entity {
+        Library::mark(self.mark.as_deref().unwrap_or(&self.player))
+    }
+}
+
+/// Every position of the saved games with the move played from it.
+#[derive(Default)]
+pub struct Library {
+    positions: Vec<(Board, (usize, usize))>,
+}
+
+impl Library {
+    /// Reads every replay of the library. Replays which can't be read are skipped.
+    pub fn load() -> Library {
+        let mut library = Library::default();
+        let pages = match Self::pages() {
+            Ok(pages) => pages,
+            Err(err) => {
+                tracing::warn!("couldn't list the library: {}", err);
+                return library;
+            }
+        };
+        for path in pages {
+            let replay = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|page| Self::parse(&page));
+            match replay {
+                Ok(replay) => library.add(replay),
+                Err(err) => tracing::warn!("skipped the replay {}: {}", path.display(), err),
+            }
+        }
+        library
+    }
+
+    /// The replay pages of the library, sorted by name, i.e. from the oldest.
+    pub fn pages() -> io::Result<Vec<PathBuf>> {
+        let entries = match storage::folder("library").and_then(fs::read_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        let mut pages: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+            .collect();
+        pages.sort();
+        Ok(pages)
+    }
+
+    /// When the replay page at `path` was saved, in seconds since 1970-01-01, read from the
+    /// timestamp ending its name. `None` for pages named otherwise.
+    pub fn saved_at(path: &Path) -> Option<u64> {
+        let name = path.file_stem()?.to_str()?;
+        name.rsplit('-').next()?.parse().ok()
+    }
+
+    /// Plays the game of the replay page at `path` again, under the classic rules which the
+    /// page doesn't record. Marks slid once all were placed, as many as a side has cells, and
+    /// the side of the first mark opened the game.
+    pub fn open(path: &Path) -> Result<Game, String> {
+        let page = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        let replay = Self::parse(&page)?;
+        let slides = replay.moves.iter().any(|played| played.from.is_some());
+        let board = Board::new(replay.size).with_pieces(slides.then_some(replay.size));
+        let starter = match replay.moves.first() {
+            Some(played) if played.player == Entity::Computer.as_str() => Entity::Computer,
+            _ => Entity::Human,
+        };
+        let mut start = board;
+        for placed in &replay.start {
+            if placed.x >= replay.size || placed.y >= replay.size {
+                return Err(format!(
+                    "mark out of the board at {:?}",
+                    (placed.x, placed.y)
+                ));
+            }
+            start[placed.x][placed.y] = placed.mark();
+        }
+        let mut game = Game::new(board, Rules::Classic).with_starter(starter);
+        game.start((!replay.start.is_empty()).then_some(start));
+        for played in replay.moves {
+            let result = match played.from {
+                Some([x, y]) => game.slide((x, y), (played.x, played.y)),
+                None => game.try_play(played.x, played.y),
+            };
+            match result {
+                Ok(_) => {}
+                // Pages saved before dead draws ended games early play on past them.
+                Err(MoveError::GameFinished) if game.state() == GameState::Draw => break,
+                Err(err) => return Err(format!("{:?} at {:?}", err, (played.x, played.y))),
+            }
+        }
+        Ok(game)
+    }
+
+    fn parse(page: &str) -> Result<Replay, String> {
+        let json = page
+            .lines()
+            .find_map(|line| line.strip_prefix("const game = "))
+            .and_then(|line| line.strip_suffix(';'))
+            .ok_or("no game in the page")?;
+        let replay: Replay = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        if !(1..=crate::game::MAX_SIZE).contains(&replay.size) {
+            return Err(format!("unsupported size {}", replay.size));
+        }
+        Ok(replay)
+    }
+
+    /// The side whose mark `player` is.
+    fn mark(player: &str) -> Entity {
+        match player {
+            "O" => Entity::Human,
+            "X" => Entity::Computer,
+            _ => Entity::Empty,
+        }
+    }
+
+    fn add(&mut self, replay: Replay) {
+        let size = replay.size;
+        let mut board = Board::new(size);
+        for placed in replay.start {
+            if placed.x >= size || placed.y >= size {
+                return;
+            }
+            board[placed.x][placed.y] = placed.mark();
+        }
+        for played in replay.moves {
+            let cells = [Some([played.x, played.y]), played.from];
+            if cells.iter().flatten().any(|&[x, y]| x >= size || y >= size)
+                || played.player == replay.empty
+            {
+                return;
+            }
+            self.positions.push((board, (played.x, played.y)));
+            if let Some([x, y]) = played.from {
+                board[x][y] = Entity::Empty;
+            }
+            board[played.x][played.y] = played.mark();
+        }
+    }
+
+    /// How many saved games reached the marks of `board`, and how many times each cell was
+    /// played from there.
+    pub fn continuations(&self, board: &Board) -> (u32, HashMap<(usize, usize), u32>) {
+        let mut reached = 0;
+        let mut played = HashMap::new();
+        for (position, cell) in &self.positions {
+            if position.size() == board.size() && position.cells().eq(board.cells()) {
+                reached += 1;
+                *played.entry(*cell).or_insert(0) += 1;
+            }
+        }
+        (reached, played)
+    }
+}
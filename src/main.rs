@@ -1,16 +1,298 @@
 use iced::{
-    widget::{button, column, container, row, text},
+    widget::{button, column, container, row, scrollable, text, tooltip},
     Application, Element, Length, Renderer, Settings,
 };
 
+mod activity;
+mod analysis;
+mod autosave;
+mod calibration;
+mod chat;
+mod command;
+mod cube;
+mod engine;
+mod events;
+mod export;
+mod features;
+mod feedback;
 mod game;
-use game::*;
+#[cfg(feature = "hotkey")]
+mod hotkey;
+mod i18n;
+mod json_log;
+mod ladder;
+mod library;
+mod menu;
+mod order_chaos;
+mod overlay;
+mod palette;
+mod pentago;
+mod perf;
+mod player;
+mod recorder;
+mod regression;
+#[cfg(feature = "render")]
+mod render;
+mod session;
+mod settings;
+#[cfg(feature = "sound")]
+mod sound;
+mod spectate;
+mod storage;
+mod tabs;
+mod trainer;
+mod tune;
+mod tutorial;
+mod ui;
+mod ultimate;
+mod voice;
+use events::Subscriber;
+use game::{
+    side::{HumanPlayer, MinimaxPlayer, Player},
+    *,
+};
+use ui::{
+    components::{button_style, cell, modal, panel, panel_style, primary, secondary},
+    dwell::dwell,
+    focus::focusable,
+    tokens,
+};
 
 #[derive(Debug, Clone)]
 enum Message {
+    /// The human pressed a cell of the board.
+    CellPressed(usize, usize),
+    /// Drops a mark in the column, with gravity.
+    ColumnPressed(usize),
+    /// Picks up a mark of the human to slide it, or puts it back.
+    PickUp(usize, usize),
+    /// Slides the picked up mark of the human from the first cell to the second.
+    Slide((usize, usize), (usize, usize)),
     UserClicked(usize, usize),
     ComputerClicked(usize, usize),
+    /// The move the computer searched on `Board`, played unless the game moved on meanwhile.
+    ComputerMoveReady(Board, Option<game::Action>),
+    /// How each move of the human ends on `Board` under `Rules`, `None` when the search
+    /// panicked.
+    OutcomesReady(Board, Rules, Option<game::Outcomes>),
+    /// The principal line from the second cell, explaining in tutor mode the move of the
+    /// human on the first one, played on `Board`.
+    TutorReady(Board, (usize, usize), (usize, usize), Option<Vec<Action>>),
     Reset,
+    /// Takes back moves until it's the human's turn again.
+    Undo,
+    /// Plays again the moves taken back, up to the human's next turn.
+    Redo,
+    ExportReplay,
+    Screenshot,
+    /// Saves an image summing up the finished game.
+    ShareCard,
+    Chat(chat::Event),
+    Tick(activity::Timer),
+    Overlay(bool),
+    ToggleSettings,
+    TogglePrivacy,
+    Checkpoints(bool),
+    SaveProgress(bool),
+    /// Asks for confirmation before deleting every saved file.
+    WipeData,
+    ConfirmWipe,
+    CancelWipe,
+    EngineMemory(u32),
+    Ponder(bool),
+    PerfHud(bool),
+    /// The window drew a frame, while the performance HUD counts them.
+    Frame(std::time::Instant),
+    BlunderAlarm(bool),
+    Scramble(bool),
+    LowTime(u32),
+    TickingTime(u32),
+    Mute(bool),
+    ReduceMotion(bool),
+    LowSpec(bool),
+    DwellTime(u32),
+    /// Plays the move the blunder alarm went off for.
+    ConfirmBlunder,
+    CancelBlunder,
+    Practice(bool),
+    /// Rewinds to before the losing move the computer punished, in practice mode.
+    TakeBack,
+    /// Keeps the losing move the computer punished on the board.
+    PlayOn,
+    Tutor(bool),
+    MistakeRate(u32),
+    /// The settings file was edited outside the app.
+    SettingsReloaded(Result<settings::Settings, String>),
+    /// The engine finished pondering on the given position.
+    Pondered(Board, Option<game::Prediction>),
+    CloseRequested,
+    /// Exits the app, checkpointing the game in progress when `save` is set.
+    Quit {
+        save: bool,
+    },
+    CancelQuit,
+    Language(i18n::Language),
+    Autosave(autosave::Autosave),
+    BoardSize(settings::BoardSize),
+    LineLength(Option<usize>),
+    Gravity(bool),
+    Morris(bool),
+    Rules(game::Rules),
+    Clock(Option<u64>),
+    Starter(settings::Starter),
+    PlayerName(String),
+    PlayerGlyph(char),
+    PlayerColor(player::Color),
+    ToggleLadder,
+    ToggleLessons,
+    StartLesson(usize),
+    LessonClicked(usize, usize),
+    LessonNext,
+    LeaveLesson,
+    ToggleTrainer,
+    TrainerClicked(usize, usize),
+    TrainerNext,
+    ToggleSpectate,
+    /// Picks who plays the marks of the given side in the spectate mode, the engine for `None`.
+    SpectateSide(Entity, Option<ladder::Bot>),
+    SpectateDelay(u32),
+    /// Starts a game of the computer against itself, with the sides picked.
+    WatchSpectate,
+    /// Starts a new game against the given bot, or the engine for `None`.
+    Challenge(Option<ladder::Bot>),
+    ToggleUltimate,
+    UltimateClicked((usize, usize), (usize, usize)),
+    /// The move the computer searched on the game of Ultimate, played unless the game moved
+    /// on meanwhile.
+    UltimateReplied(Box<game::ultimate::Ultimate>, Option<game::ultimate::Move>),
+    UltimateReset,
+    NewTab,
+    SelectTab(usize),
+    CloseTab(usize),
+    ToggleCube,
+    CubeClicked(game::cube::Cell),
+    /// The move the computer searched on the 3D game, played unless the game moved on
+    /// meanwhile.
+    CubeReplied(Box<game::cube::Cube>, Option<game::cube::Cell>),
+    CubeReset,
+    TogglePentago,
+    PentagoPlaced((usize, usize)),
+    PentagoRotated(game::pentago::Rotation),
+    /// The turn the computer searched on the game of Pentago, played unless the game moved on
+    /// meanwhile.
+    PentagoReplied(Box<game::pentago::Pentago>, Option<game::pentago::Turn>),
+    PentagoReset,
+    ToggleOrderChaos,
+    OrderChaosPlaced((usize, usize)),
+    /// The turn the computer searched on the game of Order and Chaos, played unless the game
+    /// moved on meanwhile.
+    OrderChaosReplied(
+        Box<game::order_chaos::OrderChaos>,
+        Option<game::order_chaos::Turn>,
+    ),
+    /// Picks the mark the human places next in Order and Chaos.
+    OrderChaosMark(Entity),
+    OrderChaosReset,
+    /// Starts a new game of Order and Chaos with the sides playing the other role.
+    OrderChaosSwap,
+    ToggleAnalysis,
+    AnalysisClicked(usize, usize),
+    /// Picks the mark placed by the next click on the analysis board.
+    AnalysisSide(Entity),
+    AnalysisSelect(usize),
+    AnalysisClear,
+    /// Shows or hides the variations branching off a line of the analysis.
+    AnalysisFold(usize),
+    AnalysisSave,
+    /// The engine's assessment of a position of the analysis board, `None` when its search
+    /// panicked.
+    AnalysisAssessed(Board, Entity, Option<game::Assessment>),
+    /// Opens the game which just finished on the analysis board.
+    AnalyseGame,
+    /// Unfolds a menu of the menu bar, or folds them all for `None`.
+    Menu(Option<menu::Menu>),
+    /// Runs a command picked in a menu, in the palette or through its shortcut.
+    Command(command::Command),
+    /// A key pressed along with Ctrl (Cmd on macOS), which may be bound to a command.
+    Key(iced::keyboard::KeyCode, iced::keyboard::Modifiers),
+    OpenPalette,
+    ToggleTheme,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+    ToggleMiniMode,
+    /// Edits the keys bringing the window to the front, registered once applied.
+    SummonKey(String),
+    ApplySummonKey,
+    VoiceCommand(String),
+    /// Starts the speech recognizer typed in the settings, in place of the previous one.
+    ApplyVoiceCommand,
+    Voice(voice::Event),
+    /// The keys bringing the window to the front were pressed.
+    #[cfg(feature = "hotkey")]
+    Summon,
+    #[cfg(feature = "hotkey")]
+    WindowFocused(bool),
+    /// The window was resized to this many points.
+    WindowResized(u32, u32),
+    PaletteQuery(String),
+    /// Moves the highlight of the palette by that many commands.
+    PaletteSelect(isize),
+    /// Runs the command highlighted in the palette.
+    PaletteRun,
+    /// Closes whatever is on top: the palette, a menu, the overlay or the mini mode.
+    Escape,
+    /// Moves the keyboard focus to the next widget, or the previous one when `backwards`.
+    MoveFocus {
+        backwards: bool,
+    },
+    /// Shows the move the engine recommends to the human.
+    Hint,
+    /// The engine found the move it recommends to the human on the given board.
+    HintReady(Board, Option<game::Action>),
+    /// The move recommended on the given board stops being highlighted.
+    HintExpired(Board),
+    Resign,
+    /// Lists the replay pages of the library to open one.
+    BrowseReplays,
+    OpenReplay(std::path::PathBuf),
+    CloseReplays,
+    ToggleFeedback,
+    FeedbackDescription(String),
+    /// Allows the given item in the feedback, or leaves it out.
+    FeedbackConsent(feedback::Item, bool),
+    SaveFeedback,
+    /// Saves the feedback and opens an email to attach it to.
+    EmailFeedback,
+    /// Plays the difficulty tiers against the bots, on the calibration screen.
+    RunCalibration,
+    Calibrated(Option<Vec<calibration::Matchup>>),
+    CloseCalibration,
+}
+
+#[derive(Default)]
+struct Flags {
+    /// Lets the chat of this Twitch channel play the human side.
+    twitch_channel: Option<String>,
+    /// Records every input of the session into this file.
+    record: Option<std::path::PathBuf>,
+    /// Replays the inputs recorded in this file.
+    replay: Option<std::path::PathBuf>,
+    replay_speed: Option<f32>,
+    /// Renders the final screen of the replay into this PNG without opening a window.
+    #[cfg(feature = "render")]
+    render: Option<std::path::PathBuf>,
+    /// Pixels per point of the screenshots and result cards, e.g. 2 for HiDPI screens.
+    #[cfg(feature = "render")]
+    scale: Option<f32>,
+    /// Opens the analysis saved in this file.
+    analysis: Option<std::path::PathBuf>,
+    /// Opens the calibration screen, which has no button.
+    calibration: bool,
+    /// Plays the computer's side with this executable rather than the engine.
+    engine: Option<std::path::PathBuf>,
+    /// Time the executable is given for each move.
+    engine_timeout: Option<std::time::Duration>,
 }
 
 #[derive(Default)]
@@ -18,18 +300,1003 @@ struct App {
     game: game::Game,
     ia: game::Computer,
     text: String,
+    twitch_channel: Option<String>,
+    tally: chat::Tally,
+    session: session::Session,
+    /// Shows only the board and the score over a chroma key background.
+    overlay: bool,
+    /// Shows only the board in a small window kept above the others.
+    mini: bool,
+    /// Size of the window outside of the mini mode as last resized, in points, to go back to.
+    window_size: Option<(u32, u32)>,
+    /// The keys bringing the window to the front, once registered.
+    #[cfg(feature = "hotkey")]
+    summon: Option<hotkey::Summon>,
+    #[cfg(feature = "hotkey")]
+    focused: bool,
+    /// Opened with the first sound played, holding `None` when the system has no output.
+    #[cfg(feature = "sound")]
+    speaker: std::cell::OnceCell<Option<sound::Speaker>>,
+    /// Whole seconds left to the human at the last tick of the clock, so each is cued once.
+    #[cfg(feature = "sound")]
+    clock_seconds: Option<u64>,
+    /// The speech recognizer listened to, none when empty.
+    recognizer: String,
+    recorder: Option<recorder::Recorder>,
+    player: Option<recorder::Player>,
+    /// Searches the moves of the computer within `update` rather than in the background, so
+    /// the clicks of a replay never come before the answer to the previous one.
+    synchronous: bool,
+    settings: settings::Settings,
+    show_settings: bool,
+    show_privacy: bool,
+    /// The deletion of every saved file waits for confirmation.
+    wiping: bool,
+    /// Stops the search running on the human's time, if any.
+    pondering: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    prediction: Option<game::Prediction>,
+    /// How each move of the human ends on the positions of the game searched so far, `None`
+    /// while one is being searched. Each position is searched once, whatever judges the move
+    /// played on it.
+    outcomes: std::collections::HashMap<Board, Option<game::Outcomes>>,
+    /// A move of the human held back until the outcomes of the board it was played on are
+    /// known, sent again then.
+    waiting: Option<(Board, Message)>,
+    /// The user wants to leave with a game in progress.
+    closing: bool,
+    /// What the human left half done on the board, put aside with the game of its tab.
+    draft: tabs::Draft,
+    /// The unfolded menu of the menu bar.
+    menu: Option<menu::Menu>,
+    palette: Option<palette::Palette>,
+    /// The keys bound to commands, as the settings left them.
+    keymap: command::Keymap,
+    /// The replay pages listed to open one, while the list is shown.
+    replays: Option<Vec<std::path::PathBuf>>,
+    /// The feedback being written, while its dialog is shown.
+    feedback: Option<feedback::Feedback>,
+    /// The calibration screen, while it's shown.
+    calibration: Option<calibration::Calibration>,
+    i18n: i18n::I18n,
+    ladder: ladder::Ladder,
+    show_ladder: bool,
+    /// The bot played against, `None` for the engine.
+    opponent: Option<ladder::Bot>,
+    /// The executable playing in place of the engine, if any.
+    external: Option<engine::external::External>,
+    lessons: Vec<tutorial::Lesson>,
+    show_lessons: bool,
+    tutorial: Option<tutorial::Tutorial>,
+    progress: tutorial::Progress,
+    /// The opening trainer, shown while there's one.
+    trainer: Option<trainer::Trainer>,
+    /// When the opening trainer asks each position again.
+    schedule: trainer::Schedule,
+    /// Frames drawn during the last second, counted while the performance HUD is shown.
+    frames: perf::Frames,
+    /// The spectate mode, shown while there's one.
+    spectate: Option<spectate::Spectate>,
+    /// A game of the Ultimate variant, shown while there's one.
+    ultimate: Option<game::ultimate::Ultimate>,
+    /// Every open tab, the one at `tab` is only a placeholder while its game is played in
+    /// the fields above.
+    tabs: Vec<tabs::Tab>,
+    tab: usize,
+    /// A game of the 3D variant, shown while there's one.
+    cube: Option<game::cube::Cube>,
+    /// A game of the Pentago variant, shown while there's one.
+    pentago: Option<game::pentago::Pentago>,
+    /// A game of the Order and Chaos variant, shown while there's one.
+    order_chaos: Option<game::order_chaos::OrderChaos>,
+    /// Why the last move played on a variant's screen was refused, shown under its board.
+    refused: Option<game::MoveError>,
+    /// The free analysis board, shown while there's one.
+    analysis: Option<analysis::Analysis>,
+    /// Pixels per point of the images drawn by the headless renderer, 1 when `None`.
+    #[cfg(feature = "render")]
+    scale: Option<f32>,
 }
 
 impl App {
-    fn update_text(&mut self) {
+    fn players(&self) -> player::Players {
+        match &self.spectate {
+            Some(spectate) if spectate.watching => {
+                player::Players::spectated(spectate.x, spectate.o, &self.i18n)
+            }
+            _ => player::Players::new(&self.settings.player, self.opponent, &self.i18n),
+        }
+    }
+
+    /// The frame rate and the timings of the performance HUD, in a line.
+    fn perf_hud(&self) -> Element<'_, Message, Renderer> {
+        let timings = perf::timings();
+        let millis =
+            |duration: std::time::Duration| self.i18n.decimal(duration.as_secs_f64() * 1000.0, 1);
+        let search = match timings.search {
+            Some(search) => self
+                .i18n
+                .tr_args("perf-search", [("ms", millis(search).into())]),
+            None => self.i18n.tr("perf-search-none"),
+        };
+        let mut line = vec![
+            self.i18n.tr_args(
+                "perf-fps",
+                [(
+                    "fps",
+                    self.i18n.integer(self.frames.per_second() as u64).into(),
+                )],
+            ),
+            self.i18n.tr_args(
+                "perf-update",
+                [
+                    ("ms", millis(timings.update.average()).into()),
+                    ("max", millis(timings.update.max()).into()),
+                ],
+            ),
+            self.i18n.tr_args(
+                "perf-view",
+                [
+                    ("ms", millis(timings.view.average()).into()),
+                    ("max", millis(timings.view.max()).into()),
+                ],
+            ),
+            search,
+        ];
+        line.extend(self.cache_usage());
+        container(text(line.join(" · ")).size(tokens::TEXT_SMALL))
+            .padding([tokens::SPACE_S, tokens::SPACE_M])
+            .into()
+    }
+
+    /// The memory of the engine's caches for the performance HUD, the tablebases only when
+    /// some are loaded.
+    fn cache_usage(&self) -> Vec<String> {
+        let usage = self.ia.cache_usage();
+        let kib = |bytes: usize| self.i18n.integer(bytes.div_ceil(1024) as u64);
+        let mut caches = vec![self.i18n.tr_args(
+            "perf-table",
+            [
+                ("kib", kib(usage.table).into()),
+                ("max", kib(usage.table_limit).into()),
+            ],
+        )];
+        if usage.tablebases > 0 {
+            caches.push(
+                self.i18n
+                    .tr_args("perf-tablebases", [("kib", kib(usage.tablebases).into())]),
+            );
+        }
+        caches
+    }
+
+    /// Whether the game on the board is the computer playing itself.
+    fn watching(&self) -> bool {
+        self.spectate
+            .as_ref()
+            .is_some_and(|spectate| spectate.watching)
+    }
+
+    fn result_text(&self) -> Option<String> {
+        let players = self.players();
         match self.game.state() {
-            GameState::Draw => {
-                self.text = "It's a draw!".to_string();
+            GameState::Draw => Some(self.i18n.tr("result-draw")),
+            GameState::Win(winner) => Some(
+                self.i18n
+                    .tr_args("result-win", [("winner", players.addressed(winner).into())]),
+            ),
+            GameState::Timeout(loser) => Some(self.i18n.tr_args(
+                "result-timeout",
+                [
+                    ("winner", players.addressed(!loser).into()),
+                    ("loser", players.addressed(loser).into()),
+                ],
+            )),
+            _ => None,
+        }
+    }
+
+    /// Summary of the game which just finished, with what to do next.
+    fn result_card(&self, result: String) -> Element<'_, Message, Renderer> {
+        let mut card = column![text(result).size(tokens::TEXT_HEADING)]
+            .align_items(iced::Alignment::Center)
+            .spacing(tokens::SPACE_M);
+        if let Some(record) = self.session.history.last() {
+            if let Some(percent) = record.accuracy.percent() {
+                card = card.push(text(self.i18n.tr_args(
+                    "result-accuracy",
+                    [("percent", self.i18n.percent(percent).into())],
+                )));
             }
-            GameState::Win(winner) => {
-                self.text = format!("{:?} Won!", winner);
+            card = card.push(text(self.i18n.tr_args(
+                "result-length",
+                [
+                    ("moves", record.moves.into()),
+                    ("duration", self.i18n.duration(record.duration).into()),
+                ],
+            )));
+            let games = self.session.history.len();
+            let streak = match self.session.streak(games) {
+                0 => match self.session.streak(games - 1) {
+                    0 => String::new(),
+                    lost => self.i18n.tr_args("streak-lost", [("count", lost.into())]),
+                },
+                wins => self.i18n.tr_args("streak-wins", [("count", wins.into())]),
+            };
+            card = card.push(text(streak));
+        }
+        let mut buttons = row![
+            focusable(primary(self.i18n.tr("rematch")).on_press(Message::Challenge(self.opponent))),
+            focusable(
+                primary(self.i18n.tr("analyze"))
+                    .on_press(Message::Command(command::Command::AnalyseGame))
+            ),
+            focusable(
+                primary(self.i18n.tr("export-replay"))
+                    .on_press(Message::Command(command::Command::Export))
+            )
+        ]
+        .spacing(tokens::SPACE_M);
+        if features::RENDER {
+            buttons = buttons.push(focusable(
+                primary(self.i18n.tr("share")).on_press(Message::ShareCard),
+            ));
+        }
+        card.push(text(self.text.clone())).push(buttons).into()
+    }
+
+    /// The time left to both sides of a timed game, e.g. `O 2:59   X 3:00`. The clock of the
+    /// human turns red once it runs low, pulsing unless motion is reduced.
+    fn clock(&self) -> Element<'_, Message, Renderer> {
+        let players = self.players();
+        let clock = |entity: Entity| {
+            let left = self.game.remaining(entity)?;
+            let seconds = seconds_left(left);
+            let label = format!(
+                "{} {}:{:02}",
+                players.mark(entity),
+                seconds / 60,
+                seconds % 60
+            );
+            Some((left, text(label).size(tokens::TEXT_HEADING)))
+        };
+        let (Some((left, mut human)), Some((_, computer))) =
+            (clock(Entity::Human), clock(Entity::Computer))
+        else {
+            return column![].into();
+        };
+        if seconds_left(left) <= self.settings.low_time.into() {
+            let colors = self.settings.theme.colors();
+            human = human.style(if self.settings.reduce_motion || self.settings.low_spec {
+                colors.danger
+            } else {
+                pulse(colors.text, colors.danger, left)
+            });
+        }
+        row![human, computer].spacing(tokens::SPACE_L).into()
+    }
+
+    /// Beeps as the clock of the human gets low, then ticks through its last seconds.
+    #[cfg(feature = "sound")]
+    fn clock_cues(&mut self) {
+        // Only the clock of the side to move runs.
+        if self.game.state() != GameState::Playing(Entity::Human) {
+            return;
+        }
+        let Some(seconds) = self.game.remaining(Entity::Human).map(seconds_left) else {
+            return;
+        };
+        let Some(previous) = self.clock_seconds.replace(seconds) else {
+            return;
+        };
+        if seconds == previous || self.settings.mute {
+            return;
+        }
+        let low_time = self.settings.low_time.into();
+        let cue = if previous > low_time && seconds <= low_time {
+            sound::Cue::Warning
+        } else if seconds <= self.settings.ticking_time.into() {
+            sound::Cue::Tick
+        } else {
+            return;
+        };
+        let speaker = self.speaker.get_or_init(|| {
+            sound::Speaker::new()
+                .map_err(|err| tracing::warn!("no sound output: {}", err))
+                .ok()
+        });
+        if let Some(speaker) = speaker {
+            speaker.play(cue);
+        }
+    }
+
+    fn score_text(&self) -> String {
+        self.i18n.tr_args(
+            "score",
+            [
+                ("you", self.players().human.name.into()),
+                ("human", self.session.score.human.into()),
+                ("computer", self.session.score.computer.into()),
+                ("draws", self.session.score.draws.into()),
+            ],
+        )
+    }
+
+    /// Registers the keys of the settings bringing the window to the front, in place of the
+    /// previous ones. The OS is only asked for keys once some are set.
+    #[cfg(feature = "hotkey")]
+    fn register_summon_key(&mut self) {
+        if self.summon.is_none() && self.settings.summon_key.is_empty() {
+            return;
+        }
+        let registered = match &mut self.summon {
+            Some(summon) => summon.set(&self.settings.summon_key),
+            None => hotkey::Summon::new().and_then(|mut summon| {
+                summon.set(&self.settings.summon_key)?;
+                self.summon = Some(summon);
+                Ok(())
+            }),
+        };
+        if let Err(err) = registered {
+            self.text = self.i18n.tr_args("summon-failed", [("error", err.into())]);
+        }
+    }
+
+    /// Size of the window in the mini mode, in points: the board with a line of text and a
+    /// button below it.
+    fn mini_size(&self) -> (u32, u32) {
+        // A cell is its glyph and the padding around it.
+        let cell = u32::from(tokens::TEXT_BODY) * 3 / 2 + 2 * u32::from(tokens::CELL_PADDING);
+        let cells = self.game.board().size() as u32;
+        let board = cells * cell + (cells - 1) * u32::from(tokens::SPACE_M);
+        let margin = 2 * u32::from(tokens::SPACE_M);
+        (board.max(MINI_WIDTH) + margin, board + MINI_FOOTER + margin)
+    }
+
+    /// One chip per finished game of the session, clicking one starts a rematch.
+    fn history(&self) -> Element<'_, Message, Renderer> {
+        row(self
+            .session
+            .history
+            .iter()
+            .map(|record| {
+                let (label, style) = match record.result.winner() {
+                    Some(Entity::Human) => {
+                        ("chip-win", button_style(iced::theme::Button::Positive))
+                    }
+                    Some(_) => ("chip-loss", button_style(iced::theme::Button::Destructive)),
+                    None => ("chip-draw", button_style(iced::theme::Button::Secondary)),
+                };
+                let opponent = match record.opponent {
+                    Some(bot) => self.i18n.tr(bot.name()),
+                    None => self.i18n.tr("bot-engine"),
+                };
+                let details = self.i18n.tr_args(
+                    "chip-details",
+                    [
+                        ("result", label.into()),
+                        ("opponent", opponent.into()),
+                        ("moves", record.moves.into()),
+                        (
+                            "accuracy",
+                            self.i18n
+                                .percent(record.accuracy.percent().unwrap_or(100))
+                                .into(),
+                        ),
+                        ("duration", self.i18n.duration(record.duration).into()),
+                    ],
+                );
+                tooltip(
+                    focusable(
+                        button(text(self.i18n.tr(label)).size(tokens::TEXT_SMALL))
+                            .style(style)
+                            .padding([2, 8])
+                            .on_press(Message::Challenge(record.opponent)),
+                    ),
+                    details,
+                    tooltip::Position::Top,
+                )
+                .style(panel_style())
+                .into()
+            })
+            .collect())
+        .spacing(tokens::SPACE_S)
+        .into()
+    }
+
+    fn record(&mut self, input: recorder::Input) {
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(err) = recorder.record(input) {
+                self.text = self
+                    .i18n
+                    .tr_args("record-input-failed", [("error", err.to_string().into())]);
+                self.recorder = None;
             }
-            _ => {}
+        }
+    }
+
+    /// Draws the current screen with the headless renderer.
+    #[cfg(feature = "render")]
+    fn snapshot(&self) -> tiny_skia::Pixmap {
+        if self.overlay {
+            return render::screen(
+                self.game.board(),
+                &[self.score_text()],
+                render::Palette::chroma_key(),
+                self.scale.unwrap_or(1.0),
+            );
+        }
+        let lines: Vec<String> = [
+            self.result_text().unwrap_or_default(),
+            self.text.clone(),
+            self.score_text(),
+        ]
+        .into_iter()
+        .filter(|line| !line.is_empty())
+        .collect();
+        render::screen(
+            self.game.board(),
+            &lines,
+            render::Palette::new(self.settings.theme.colors()),
+            self.scale.unwrap_or(1.0),
+        )
+    }
+
+    /// Draws the result card of the finished game with the headless renderer.
+    #[cfg(feature = "render")]
+    fn share_card(&self) -> Option<tiny_skia::Pixmap> {
+        let opponent = match self.opponent {
+            Some(bot) => self.i18n.tr(bot.name()),
+            None => self.i18n.tr("bot-engine"),
+        };
+        let lines = [
+            self.i18n.tr_args(
+                "card-players",
+                [
+                    ("you", self.players().human.name.into()),
+                    ("opponent", opponent.into()),
+                ],
+            ),
+            self.i18n.date(timestamp()),
+        ];
+        Some(render::card(
+            &self.result_text()?,
+            self.game.board(),
+            &lines,
+            render::Palette::new(self.settings.theme.colors()),
+            self.scale.unwrap_or(1.0),
+        ))
+    }
+
+    /// The replay pages of the library, clicking one opens it on the analysis board.
+    fn replays_view<'a>(
+        &self,
+        replays: &'a [std::path::PathBuf],
+    ) -> Element<'a, Message, Renderer> {
+        let list: Element<'a, Message, Renderer> = if replays.is_empty() {
+            text(self.i18n.tr("replays-empty")).into()
+        } else {
+            scrollable(
+                column(
+                    replays
+                        .iter()
+                        .map(|path| {
+                            let name = match library::Library::saved_at(path) {
+                                Some(saved) => self.i18n.tr_args(
+                                    "replay-saved",
+                                    [
+                                        ("date", self.i18n.date(saved).into()),
+                                        ("time", self.i18n.time(saved).into()),
+                                    ],
+                                ),
+                                None => path
+                                    .file_stem()
+                                    .unwrap_or_default()
+                                    .to_string_lossy()
+                                    .into_owned(),
+                            };
+                            focusable(
+                                button(text(name))
+                                    .style(button_style(iced::theme::Button::Text))
+                                    .on_press(Message::OpenReplay(path.clone())),
+                            )
+                        })
+                        .collect(),
+                )
+                .spacing(tokens::SPACE_S),
+            )
+            .height(400)
+            .into()
+        };
+        column![
+            text(self.i18n.tr("replays")).size(tokens::TEXT_TITLE),
+            list,
+            text(self.text.clone()),
+            focusable(secondary(self.i18n.tr("back")).on_press(Message::CloseReplays))
+        ]
+        .align_items(iced::Alignment::Center)
+        .spacing(tokens::SPACE_M)
+        .into()
+    }
+
+    /// Whether `command` can run right now.
+    fn is_enabled(&self, command: command::Command) -> bool {
+        let human_turn = self.game.state() == GameState::Playing(Entity::Human)
+            && self.twitch_channel.is_none()
+            && !self.watching();
+        match command {
+            // The opening move of the computer isn't the human's to take back.
+            command::Command::Undo => {
+                let opening = usize::from(self.game.starter() == Entity::Computer);
+                self.can_step() && self.game.moves().len() > opening
+            }
+            command::Command::Redo => self.can_step() && self.game.can_redo(),
+            command::Command::Hint | command::Command::Resign => human_turn,
+            command::Command::AnalyseGame => !self.game.moves().is_empty(),
+            command::Command::Screenshot => features::RENDER,
+            command::Command::ZoomIn => self.settings.zoom < *settings::ZOOM_RANGE.end(),
+            command::Command::ZoomOut => self.settings.zoom > *settings::ZOOM_RANGE.start(),
+            _ => true,
+        }
+    }
+
+    /// The question the game waits on below the board, about a losing move of the human.
+    fn prompt(&self) -> Option<Element<'_, Message, Renderer>> {
+        if let Some((x, y)) = self.draft.blunder {
+            return Some(modal(
+                self.i18n
+                    .tr_args("blunder-warning", [("cell", cell_name(x, y).into())]),
+                [
+                    primary(self.i18n.tr("blunder-play")).on_press(Message::ConfirmBlunder),
+                    secondary(self.i18n.tr("blunder-cancel")).on_press(Message::CancelBlunder),
+                ],
+            ));
+        }
+        // Explained once the computer answered it.
+        let mistake = self
+            .draft
+            .mistake
+            .filter(|_| self.game.state() != GameState::Playing(Entity::Computer))?;
+        Some(modal(
+            self.mistake_text(mistake),
+            [
+                primary(self.i18n.tr("practice-take-back")).on_press(Message::TakeBack),
+                secondary(self.i18n.tr("practice-play-on")).on_press(Message::PlayOn),
+            ],
+        ))
+    }
+
+    /// Why the move of the human at `(x, y)` loses, from the threats the computer's answer
+    /// left on the board.
+    fn mistake_text(&self, (x, y): (usize, usize)) -> String {
+        let played = cell_name(x, y);
+        // Under misère and Notakto the move itself may have finished the game.
+        let Some(reply) = self
+            .game
+            .moves()
+            .last()
+            .filter(|reply| reply.player == Entity::Computer)
+        else {
+            return self
+                .i18n
+                .tr_args("practice-lost", [("move", played.into())]);
+        };
+        let args = |extra: Vec<(&'static str, String)>| {
+            let mut args = vec![
+                ("move", played.clone()),
+                ("reply", cell_name(reply.x, reply.y)),
+            ];
+            args.extend(extra);
+            args.into_iter().map(|(name, value)| (name, value.into()))
+        };
+        // Threats only tell the story when lines win and marks stay where they're placed.
+        let board = self.game.board();
+        if self.game.rules() != Rules::Classic || board.pieces().is_some() {
+            return self.i18n.tr_args("practice-forced", args(vec![]));
+        }
+        if self.game.state() == GameState::Win(Entity::Computer) {
+            return self.i18n.tr_args("practice-completed", args(vec![]));
+        }
+        match winning_moves(board, Entity::Computer)[..] {
+            [(x, y)] => self
+                .i18n
+                .tr_args("practice-threat", args(vec![("cell", cell_name(x, y))])),
+            [first, second, ..] => self.i18n.tr_args(
+                "practice-fork",
+                args(vec![
+                    ("first", cell_name(first.0, first.1)),
+                    ("second", cell_name(second.0, second.1)),
+                ]),
+            ),
+            [] => self.i18n.tr_args("practice-forced", args(vec![])),
+        }
+    }
+
+    /// Searches in the background the line explaining the move of the human at `played` on
+    /// `before` in tutor mode: the best move's own line when the move let a win slip, since it
+    /// doesn't lose, or the engine's refutation when it does.
+    fn explain(
+        &mut self,
+        before: Board,
+        played: (usize, usize),
+        outcomes: &game::Outcomes,
+    ) -> iced::Command<Message> {
+        let scores = outcomes.scores();
+        let outcome = scores
+            .iter()
+            .find(|score| score.cell == played)
+            .map_or(0, MoveScore::outcome);
+        let searched = if outcome <= 0 {
+            let Some(best) = scores.iter().min_by_key(|score| score.score) else {
+                return iced::Command::none();
+            };
+            best.cell
+        } else {
+            played
+        };
+        self.draft.tutoring = Some((before, played));
+        let mut ia = self.ia.clone();
+        let mut search = move || ia.principal_line(before, searched.0, searched.1);
+        if self.synchronous {
+            return self.update(Message::TutorReady(
+                before,
+                played,
+                searched,
+                Some(search()),
+            ));
+        }
+        iced::Command::perform(background(search), move |line| {
+            Message::TutorReady(before, played, searched, line)
+        })
+    }
+
+    /// What the move of the human at `(x, y)` on `before` gives away, in tutor mode, from the
+    /// `principal` line searched from `searched`: the win the best move kept, or the threats
+    /// the engine's refutation makes.
+    fn tutor_text(
+        &self,
+        before: Board,
+        (x, y): (usize, usize),
+        searched: (usize, usize),
+        principal: Vec<Action>,
+    ) -> String {
+        let played = cell_name(x, y);
+        let line = |actions: &[Action]| {
+            actions
+                .iter()
+                .map(|action| action.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if searched != (x, y) {
+            let (bx, by) = searched;
+            let mut actions = vec![Action::Place(bx, by)];
+            actions.extend(principal);
+            return self.i18n.tr_args(
+                "tutor-missed-win",
+                [
+                    ("move", played.into()),
+                    ("best", cell_name(bx, by).into()),
+                    ("line", line(&actions).into()),
+                ],
+            );
+        }
+
+        let refutation = principal;
+        let args = |extra: Vec<(&'static str, String)>| {
+            let mut args = vec![
+                ("move", played.clone()),
+                ("line", line(&refutation)),
+                (
+                    "reply",
+                    refutation
+                        .first()
+                        .map(|reply| reply.name())
+                        .unwrap_or_default(),
+                ),
+            ];
+            args.extend(extra);
+            args.into_iter().map(|(name, value)| (name, value.into()))
+        };
+        // Threats only tell the story when lines win and marks stay where they're placed.
+        let Some(&Action::Place(rx, ry)) = refutation
+            .first()
+            .filter(|_| self.game.rules() == Rules::Classic && before.pieces().is_none())
+        else {
+            return self.i18n.tr_args("tutor-line", args(vec![]));
+        };
+        let mut after = before;
+        after[x][y] = Entity::Human;
+        after[rx][ry] = Entity::Computer;
+        if has_line(&after, Entity::Computer) {
+            return self.i18n.tr_args("tutor-completed", args(vec![]));
+        }
+        match winning_moves(&after, Entity::Computer)[..] {
+            [(cx, cy)] => self
+                .i18n
+                .tr_args("tutor-threat", args(vec![("cell", cell_name(cx, cy))])),
+            [first, second, ..] => self.i18n.tr_args(
+                "tutor-fork",
+                args(vec![
+                    ("first", cell_name(first.0, first.1)),
+                    ("second", cell_name(second.0, second.1)),
+                ]),
+            ),
+            [] => self.i18n.tr_args("tutor-line", args(vec![])),
+        }
+    }
+
+    /// Moves can't be taken back while the chat plays, nor against the clock, nor while the
+    /// computer plays itself.
+    fn can_step(&self) -> bool {
+        self.twitch_channel.is_none() && !self.game.is_timed() && !self.watching()
+    }
+
+    /// Starts the game, playing the first move of the computer right away when it opens.
+    fn open_game(&mut self) -> iced::Command<Message> {
+        let initial = self
+            .settings
+            .scramble
+            .then(|| self.game.scrambled_start(&mut rand::thread_rng()));
+        self.game.start(initial);
+        tracing::debug!(
+            rules = ?self.game.rules(),
+            size = self.game.board().size(),
+            starter = ?self.game.starter(),
+            "game started"
+        );
+        self.think(None)
+    }
+
+    /// Logs the move just played, for `--log-json`.
+    fn log_move(&self) {
+        if let Some(played) = self.game.moves().last() {
+            tracing::debug!(
+                side = ?played.player,
+                mark = ?played.mark,
+                from = ?played.from,
+                x = played.x,
+                y = played.y,
+                "move"
+            );
+        }
+    }
+
+    /// Keeps the names of the human and of the chat out of the JSON log.
+    fn redact_names(&self) {
+        json_log::redact_names(
+            std::iter::once(self.settings.player.name.clone()).chain(self.twitch_channel.clone()),
+        );
+    }
+
+    /// Who plays `side`, `last` being the move the other side just played.
+    fn player(&self, side: Entity, last: Option<(usize, usize)>) -> Box<dyn Player> {
+        if let Some(spectate) = self.spectate.as_ref().filter(|spectate| spectate.watching) {
+            return self.contender(spectate.contender(side), last);
+        }
+        match side {
+            Entity::Human => Box::new(HumanPlayer),
+            _ => self.contender(self.opponent, last),
+        }
+    }
+
+    /// `bot` playing the computer's side, or the engine for `None`, the executable of
+    /// `--engine` when there's one.
+    fn contender(&self, bot: Option<ladder::Bot>, last: Option<(usize, usize)>) -> Box<dyn Player> {
+        match bot {
+            Some(bot) => Box::new(ladder::BotPlayer {
+                bot,
+                ia: self.ia.clone(),
+                last,
+            }),
+            None => match &self.external {
+                Some(external) => Box::new(engine::external::ExternalPlayer {
+                    engine: external.clone(),
+                    rules: self.game.rules(),
+                    ia: self.ia.clone(),
+                }),
+                None => Box::new(MinimaxPlayer(self.ia.clone())),
+            },
+        }
+    }
+
+    /// Asks the side to move for its move on a thread, the window staying responsive
+    /// meanwhile, and plays it once found. Interactive sides are waited for instead. `last` is
+    /// the move it answers.
+    fn think(&mut self, last: Option<(usize, usize)>) -> iced::Command<Message> {
+        let GameState::Playing(side) = self.game.state() else {
+            return iced::Command::none();
+        };
+        let player = self.player(side, last);
+        if player.is_interactive() {
+            // Whatever is played next gets judged.
+            return self.search_outcomes();
+        }
+        let board = *self.game.board();
+        // Players pick moves for the computer's side, the human's is played on the board seen
+        // the other way around while the computer plays itself.
+        let seen = if side == Entity::Human && self.game.rules() != Rules::Notakto {
+            board.swapped()
+        } else {
+            board
+        };
+        let delay = self
+            .watching()
+            .then(|| std::time::Duration::from_millis(self.settings.spectate_delay.into()));
+        let mut ia = self.ia.clone();
+        let mut search = move || {
+            if let Some(delay) = delay {
+                std::thread::sleep(delay);
+            }
+            let _span = tracing::debug_span!("engine.search").entered();
+            // Players only pick cells, the engine slides for them.
+            if board.must_slide(side) {
+                return ia.best_action(board, side);
+            }
+            let (x, y) = player.choose_move(&seen)?;
+            Some(game::Action::Place(x, y))
+        };
+        if self.synchronous {
+            return self.update(Message::ComputerMoveReady(board, search()));
+        }
+        iced::Command::perform(background(search), move |action| {
+            Message::ComputerMoveReady(board, action.flatten())
+        })
+    }
+
+    /// Plays the cell the human queued during the computer's turn, `None` when there's none
+    /// or it can't be played anymore, e.g. the computer took it.
+    fn premove(&mut self) -> Option<iced::Command<Message>> {
+        let (x, y) = self.draft.premove.take()?;
+        let board = self.game.board();
+        let playable = self.game.state() == GameState::Playing(Entity::Human)
+            && self.draft.mistake.is_none()
+            && !board.must_slide(Entity::Human)
+            && board.is_playable(x, y);
+        playable.then(|| self.update(Message::CellPressed(x, y)))
+    }
+
+    /// Searches in the background how each move of the human ends on the board, unless it's
+    /// known or being searched already.
+    fn search_outcomes(&mut self) -> iced::Command<Message> {
+        let board = *self.game.board();
+        if !self.game.state().is_playable() || self.outcomes.contains_key(&board) {
+            return iced::Command::none();
+        }
+        self.outcomes.insert(board, None);
+        let rules = self.game.rules();
+        let mut ia = self.ia.clone();
+        let mut search = move || {
+            let _span = tracing::debug_span!("engine.outcomes").entered();
+            ia.outcomes(board)
+        };
+        if self.synchronous {
+            return self.update(Message::OutcomesReady(board, rules, Some(search())));
+        }
+        iced::Command::perform(background(search), move |outcomes| {
+            Message::OutcomesReady(board, rules, outcomes)
+        })
+    }
+
+    /// Shows `analysis`, its first position being assessed in the background.
+    fn open_analysis(&mut self, analysis: analysis::Analysis) -> iced::Command<Message> {
+        self.analysis = Some(analysis);
+        self.assess_analysis()
+    }
+
+    /// Searches the position shown on the analysis board in the background.
+    fn assess_analysis(&mut self) -> iced::Command<Message> {
+        let Some(analysis) = &mut self.analysis else {
+            return iced::Command::none();
+        };
+        let (board, side, search) = analysis.search();
+        if self.synchronous {
+            return self.update(Message::AnalysisAssessed(board, side, Some(search())));
+        }
+        iced::Command::perform(background(search), move |assessment| {
+            Message::AnalysisAssessed(board, side, assessment)
+        })
+    }
+
+    fn stop_pondering(&mut self) {
+        if let Some(cancel) = self.pondering.take() {
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    /// Persists everything worth keeping and closes the window.
+    fn shutdown(&mut self, checkpoint: bool) -> iced::Command<Message> {
+        self.stop_pondering();
+        if let Err(err) = storage::save(settings::Settings::FILE, &self.settings) {
+            tracing::error!(
+                code = "save-settings",
+                "couldn't save the settings: {}",
+                err
+            );
+        }
+        let saved = if checkpoint {
+            storage::save(
+                session::Checkpoint::FILE,
+                &session::Checkpoint::new(&self.game),
+            )
+        } else {
+            storage::remove(session::Checkpoint::FILE)
+        };
+        if let Err(err) = saved {
+            tracing::error!(
+                code = "save-checkpoint",
+                "couldn't checkpoint the game: {}",
+                err
+            );
+        }
+        iced::window::close()
+    }
+
+    /// Puts the game being played aside and brings the one of tab `index` to the board.
+    fn select_tab(&mut self, index: usize) -> iced::Command<Message> {
+        self.stop_pondering();
+        self.prediction = None;
+        // The game the computer played against itself isn't kept in the tab.
+        if self
+            .spectate
+            .take()
+            .is_some_and(|spectate| spectate.watching)
+        {
+            self.game = self.settings.game(self.game.starter());
+            self.text.clear();
+        }
+        self.tabs[self.tab] = tabs::Tab {
+            game: std::mem::take(&mut self.game),
+            opponent: self.opponent.take(),
+            text: std::mem::take(&mut self.text),
+            ongoing: std::mem::take(&mut self.session.current),
+            draft: std::mem::take(&mut self.draft),
+        };
+        let tab = std::mem::take(&mut self.tabs[index]);
+        self.game = tab.game;
+        self.opponent = tab.opponent;
+        self.text = tab.text;
+        self.draft = tab.draft;
+        self.session.current = tab.ongoing;
+        self.tab = index;
+        self.ia.new_game(self.game.rules());
+        self.outcomes.clear();
+        // A move searched while the game was put aside got dropped.
+        self.think(None)
+    }
+
+    fn finish_move(&mut self) {
+        if self.game.state().is_finished() {
+            tracing::debug!(state = ?self.game.state(), "game finished");
+        }
+        // Games the computer plays against itself are only watched.
+        if self.watching() {
+            return;
+        }
+        if self.game.state().is_finished() {
+            self.text.clear();
+            let event = events::Event::Finished {
+                game: &self.game,
+                opponent: self.opponent,
+            };
+            self.session.notify(&event);
+            self.settings.autosave.notify(&event);
+        }
+        let Some(bot) = self.opponent else {
+            return;
+        };
+        if self.game.state().winner() == Some(Entity::Human) && self.ladder.beat(bot) {
+            if self.settings.privacy.progress {
+                if let Err(err) = storage::save(ladder::Ladder::FILE, &self.ladder) {
+                    tracing::error!(code = "save-ladder", "couldn't save the ladder: {}", err);
+                }
+            }
+            self.text = match self.ladder.next() {
+                Some(next) => self.i18n.tr_args(
+                    "ladder-unlocked",
+                    [("bot", self.i18n.tr(next.name()).into())],
+                ),
+                None => self.i18n.tr("ladder-completed"),
+            };
         }
     }
 }
@@ -38,102 +1305,2027 @@ impl Application for App {
     type Executor = iced::executor::Default;
     type Message = Message;
     type Theme = iced::Theme;
-    type Flags = ();
+    type Flags = Flags;
 
-    fn new(_: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        (
-            Self {
-                ..Default::default()
-            },
-            iced::Command::none(),
-        )
+    fn new(flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        let mut tally = chat::Tally::default();
+        tally.open();
+        let mut app = Self {
+            twitch_channel: flags.twitch_channel,
+            #[cfg(feature = "render")]
+            scale: flags.scale,
+            tally,
+            tabs: vec![tabs::Tab::default()],
+            ..Default::default()
+        };
+        if flags.record.is_some() || flags.replay.is_some() {
+            app.ia = game::Computer::default().with_seed(recorder::SEED);
+        }
+        {
+            let _span = tracing::info_span!("startup.settings").entered();
+            match storage::load::<settings::Settings>(settings::Settings::FILE) {
+                Ok(Some(settings)) => app.settings = settings.validate(),
+                // On the first run the language follows the OS, and is kept from then on.
+                Ok(None) => {
+                    app.settings.language = i18n::Language::detect();
+                    if let Err(err) = storage::save(settings::Settings::FILE, &app.settings) {
+                        tracing::error!(
+                            code = "save-settings",
+                            "couldn't save the settings: {}",
+                            err
+                        );
+                    }
+                }
+                Err(err) => {
+                    app.text = app
+                        .i18n
+                        .tr_args("settings-load-failed", [("error", err.to_string().into())])
+                }
+            }
+            app.i18n = i18n::I18n::new(app.settings.language);
+            app.ia.set_memory_limit(app.settings.engine_memory_bytes());
+            app.ia.set_mistake_rate(app.settings.mistake_rate);
+            match storage::load::<game::Weights>(tune::FILE) {
+                Ok(Some(weights)) => app.ia.set_weights(weights),
+                Ok(None) => {}
+                Err(err) => tracing::warn!("couldn't load the tuned weights: {}", err),
+            }
+            app.keymap = command::Keymap::new(&app.settings.bindings);
+            tokens::set_low_spec(app.settings.low_spec);
+            app.redact_names();
+            app.game = app.settings.game(Entity::Human);
+            app.ia.new_game(app.game.rules());
+        }
+        #[cfg(feature = "tablebases")]
+        {
+            let _span = tracing::info_span!("startup.tablebases").entered();
+            app.ia.set_tablebases(load_tablebases());
+        }
+        #[cfg(feature = "hotkey")]
+        app.register_summon_key();
+        app.recognizer = app.settings.voice_command.clone();
+        {
+            let _span = tracing::info_span!("startup.lessons").entered();
+            match storage::load::<tutorial::Progress>(tutorial::Progress::FILE) {
+                Ok(progress) => app.progress = progress.unwrap_or_default(),
+                Err(err) => tracing::error!(
+                    code = "load-lessons",
+                    "couldn't load the lessons progress: {}",
+                    err
+                ),
+            }
+        }
+        {
+            let _span = tracing::info_span!("startup.openings").entered();
+            match storage::load::<trainer::Schedule>(trainer::Schedule::FILE) {
+                Ok(schedule) => app.schedule = schedule.unwrap_or_default(),
+                Err(err) => tracing::error!(
+                    code = "load-openings",
+                    "couldn't load the opening trainer: {}",
+                    err
+                ),
+            }
+        }
+        {
+            let _span = tracing::info_span!("startup.ladder").entered();
+            match storage::load::<ladder::Ladder>(ladder::Ladder::FILE) {
+                Ok(ladder) => app.ladder = ladder.unwrap_or_default(),
+                Err(err) => {
+                    app.text = app
+                        .i18n
+                        .tr_args("ladder-load-failed", [("error", err.to_string().into())])
+                }
+            }
+        }
+        if let Some(path) = flags.record {
+            let _span = tracing::info_span!("startup.recorder").entered();
+            match recorder::Recorder::create(&path) {
+                Ok(recorder) => app.recorder = Some(recorder),
+                Err(err) => {
+                    app.text = app.i18n.tr_args(
+                        "record-failed",
+                        [
+                            ("path", path.display().to_string().into()),
+                            ("error", err.to_string().into()),
+                        ],
+                    )
+                }
+            }
+        }
+        app.synchronous = flags.replay.is_some();
+        if let Some(path) = flags.replay {
+            let _span = tracing::info_span!("startup.replay").entered();
+            match recorder::Player::load(&path, flags.replay_speed.unwrap_or(1.0)) {
+                Ok(player) => app.player = Some(player),
+                Err(err) => {
+                    app.text = app.i18n.tr_args(
+                        "replay-failed",
+                        [
+                            ("path", path.display().to_string().into()),
+                            ("error", err.to_string().into()),
+                        ],
+                    )
+                }
+            }
+        }
+        if flags.calibration {
+            app.calibration = Some(calibration::Calibration::default());
+        }
+        if let Some(program) = flags.engine {
+            let mut external = engine::external::External::new(program);
+            if let Some(timeout) = flags.engine_timeout {
+                external.timeout = timeout;
+            }
+            app.external = Some(external);
+        }
+        let mut startup = iced::Command::none();
+        if let Some(path) = flags.analysis {
+            match analysis::Analysis::load(&path) {
+                Ok(analysis) => startup = app.open_analysis(analysis),
+                Err(err) => {
+                    app.text = app.i18n.tr_args(
+                        "analysis-load-failed",
+                        [
+                            ("path", path.display().to_string().into()),
+                            ("error", err.to_string().into()),
+                        ],
+                    )
+                }
+            }
+        }
+        if app.player.is_none() {
+            let _span = tracing::info_span!("startup.checkpoint").entered();
+            match storage::load::<session::Checkpoint>(session::Checkpoint::FILE) {
+                Ok(Some(checkpoint)) => {
+                    app.game = checkpoint.restore();
+                    app.ia.new_game(app.game.rules());
+                    app.text = app.i18n.tr("checkpoint-restored");
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    app.text = app.i18n.tr_args(
+                        "checkpoint-restore-failed",
+                        [("error", err.to_string().into())],
+                    )
+                }
+            }
+        }
+        (app, startup)
     }
 
     fn title(&self) -> String {
-        "Tic Tac Toe".to_string()
+        self.i18n.tr("title")
     }
 
     fn update(&mut self, msg: Self::Message) -> iced::Command<Self::Message> {
+        // Counting frames isn't part of the work the performance HUD measures.
+        let _span =
+            (!matches!(msg, Message::Frame(_))).then(|| tracing::debug_span!("update").entered());
         if self.game.state() == game::GameState::Ready {
-            self.game.start();
+            self.game.start(None);
         };
         match msg {
+            Message::CellPressed(x, y) => {
+                if let GameState::Playing(side) = self.game.state() {
+                    if side != Entity::Human
+                        && !self.player(side, None).is_interactive()
+                        && !self.watching()
+                    {
+                        self.draft.premove = Some((x, y));
+                        return iced::Command::none();
+                    }
+                }
+                let human_turn = matches!(self.game.state(), GameState::Playing(Entity::Human));
+                let board = *self.game.board();
+                if self.settings.blunder_alarm && human_turn && board[x][y] == Entity::Empty {
+                    let Some(Some(outcomes)) = self.outcomes.get(&board) else {
+                        self.waiting = Some((board, Message::CellPressed(x, y)));
+                        return self.search_outcomes();
+                    };
+                    if outcomes.is_blunder((x, y)) {
+                        self.draft.blunder = Some((x, y));
+                        return iced::Command::none();
+                    }
+                }
+                return self.update(Message::UserClicked(x, y));
+            }
+            Message::ColumnPressed(y) => {
+                if let Some(x) = self.game.board().drop_row(y) {
+                    return self.update(Message::CellPressed(x, y));
+                }
+            }
+            Message::PickUp(x, y) => {
+                self.draft.picked = (self.draft.picked != Some((x, y))).then_some((x, y));
+            }
+            Message::Slide(from, to) => {
+                self.draft.picked = None;
+                if let Err(err) = self.game.slide(from, to) {
+                    self.text = self.i18n.tr(err.label());
+                    return iced::Command::none();
+                }
+                self.text.clear();
+                self.draft.hint = None;
+                self.stop_pondering();
+                self.prediction = None;
+                self.log_move();
+                self.finish_move();
+                return self.think(None);
+            }
+            Message::ConfirmBlunder => {
+                if let Some((x, y)) = self.draft.blunder.take() {
+                    return self.update(Message::UserClicked(x, y));
+                }
+            }
+            Message::CancelBlunder => self.draft.blunder = None,
+            Message::TakeBack => {
+                if let Some((x, y)) = self.draft.mistake {
+                    let command = self.update(Message::Undo);
+                    self.draft.retrying = true;
+                    self.text = self
+                        .i18n
+                        .tr_args("practice-retry", [("move", cell_name(x, y).into())]);
+                    return command;
+                }
+            }
+            Message::PlayOn => self.draft.mistake = None,
             Message::UserClicked(x, y) => {
-                self.game.update(x, y);
-                self.update_text();
+                // The side to move doesn't play from the window, e.g. the computer still
+                // thinking about its move.
+                if let GameState::Playing(side) = self.game.state() {
+                    if !self.player(side, None).is_interactive() {
+                        return iced::Command::none();
+                    }
+                }
+                let before = *self.game.board();
+                // The move is judged on the outcomes of the board, it waits for them.
+                let outcomes = match self.outcomes.get(&before) {
+                    Some(Some(outcomes)) => Some(outcomes.clone()),
+                    _ if self.game.state().is_playable() => {
+                        self.waiting = Some((before, Message::UserClicked(x, y)));
+                        return self.search_outcomes();
+                    }
+                    _ => None,
+                };
+                self.record(recorder::Input::Click(x, y));
+                let mistake = self.settings.practice
+                    && self.can_step()
+                    && outcomes
+                        .as_ref()
+                        .is_some_and(|outcomes| outcomes.is_blunder((x, y)));
+                if let Err(err) = self.game.try_play(x, y) {
+                    self.text = self.i18n.tr(err.label());
+                    return iced::Command::none();
+                }
+                self.text.clear();
+                self.draft.hint = None;
+                let best = outcomes
+                    .as_ref()
+                    .is_some_and(|outcomes| outcomes.is_best((x, y)));
+                self.session
+                    .current
+                    .judge(self.game.moves().len() - 1, best);
+                self.stop_pondering();
+                self.log_move();
+                self.finish_move();
+                if std::mem::take(&mut self.draft.retrying) && !mistake {
+                    self.text = self
+                        .i18n
+                        .tr_args("practice-found", [("move", cell_name(x, y).into())]);
+                }
+                let tutor = match &outcomes {
+                    Some(outcomes)
+                        if self.settings.tutor && !best && !self.game.state().is_finished() =>
+                    {
+                        self.explain(before, (x, y), outcomes)
+                    }
+                    _ => iced::Command::none(),
+                };
+                if mistake {
+                    // The game pauses on the computer's answer until the human decides, with
+                    // nothing to ponder meanwhile.
+                    self.draft.mistake = Some((x, y));
+                }
                 if let GameState::Playing(_) = self.game.state() {
-                    let (x, y) = self.ia.best_play(*self.game.board());
-                    return self.update(Message::ComputerClicked(x, y));
+                    let answer = match self.prediction.take() {
+                        // The human played the predicted move, the answer is ready.
+                        Some(prediction)
+                            if prediction.board == before && prediction.human == (x, y) =>
+                        {
+                            let (x, y) = prediction.reply;
+                            self.update(Message::ComputerClicked(x, y))
+                        }
+                        _ => self.think(Some((x, y))),
+                    };
+                    return iced::Command::batch([tutor, answer]);
+                }
+            }
+            Message::TutorReady(before, played, searched, principal) => {
+                // Another move was played or taken back meanwhile, or the answer ended the game.
+                if self.draft.tutoring != Some((before, played)) || self.game.state().is_finished()
+                {
+                    return iced::Command::none();
+                }
+                self.draft.tutoring = None;
+                if let Some(principal) = principal {
+                    self.text = self.tutor_text(before, played, searched, principal);
+                }
+            }
+            Message::OutcomesReady(board, rules, outcomes) => {
+                // Searched for another game.
+                if rules != self.game.rules() {
+                    return iced::Command::none();
+                }
+                match outcomes {
+                    Some(outcomes) => self.outcomes.insert(board, Some(outcomes)),
+                    None => self.outcomes.remove(&board),
+                };
+                let waiting = self.waiting.take_if(|(waited, _)| *waited == board);
+                // The move waited on the board the human still sees.
+                if let Some((_, message)) = waiting.filter(|_| board == *self.game.board()) {
+                    if self.outcomes.contains_key(&board) {
+                        return self.update(message);
+                    }
+                }
+            }
+            Message::ComputerMoveReady(board, action) => {
+                // The game moved on meanwhile, e.g. a move was taken back or a new game started.
+                let GameState::Playing(side) = self.game.state() else {
+                    return iced::Command::none();
+                };
+                if board != *self.game.board() || self.player(side, None).is_interactive() {
+                    return iced::Command::none();
+                }
+                match action {
+                    Some(game::Action::Place(x, y)) => {
+                        return self.update(Message::ComputerClicked(x, y));
+                    }
+                    Some(game::Action::Slide(from, to)) => {
+                        if let Err(err) = self.game.slide(from, to) {
+                            tracing::error!(
+                                code = "illegal-slide",
+                                "the engine slid illegally: {:?}",
+                                err
+                            );
+                            // Nothing was played, so there's no move to log or answer.
+                            return iced::Command::none();
+                        }
+                        self.log_move();
+                        self.finish_move();
+                        if let Some(command) = self.premove() {
+                            return command;
+                        }
+                        if self.watching() {
+                            return self.think(None);
+                        }
+                    }
+                    None => {}
                 }
             }
             Message::ComputerClicked(x, y) => {
-                self.game.update(x, y);
-                self.update_text();
+                if let Err(err) = self.game.try_play(x, y) {
+                    tracing::error!(
+                        code = "illegal-move",
+                        "the computer played {:?} illegally: {:?}",
+                        (x, y),
+                        err
+                    );
+                }
+                self.log_move();
+                self.finish_move();
+                if self.watching() {
+                    return self.think(Some((x, y)));
+                }
+                if let Some(command) = self.premove() {
+                    return command;
+                }
+                if self.settings.ponder
+                    && self.opponent.is_none()
+                    && self.draft.mistake.is_none()
+                    && self.game.state() == GameState::Playing(Entity::Human)
+                {
+                    let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    let mut ia = self.ia.with_cancel(cancel.clone());
+                    self.pondering = Some(cancel);
+                    let board = *self.game.board();
+                    return iced::Command::batch([
+                        iced::Command::perform(
+                            background(move || ia.ponder(board)),
+                            move |prediction| Message::Pondered(board, prediction.flatten()),
+                        ),
+                        self.search_outcomes(),
+                    ]);
+                }
+                return self.search_outcomes();
             }
             Message::Reset => {
-                self.game = self.game.reset();
-                self.text.clear()
+                let starter = self.settings.starter.next(&self.game);
+                self.game = self.settings.game(starter);
+                self.session.new_game();
+                self.draft = tabs::Draft::default();
+                self.stop_pondering();
+                self.prediction = None;
+                self.ia.new_game(self.game.rules());
+                self.outcomes.clear();
+                self.tally.open();
+                self.text.clear();
+                return self.open_game();
+            }
+            Message::Undo | Message::Redo => {
+                self.stop_pondering();
+                self.prediction = None;
+                self.draft = tabs::Draft::default();
+                let step = match msg {
+                    Message::Undo => Game::undo,
+                    _ => Game::redo,
+                };
+                while step(&mut self.game) {
+                    let state = self.game.state();
+                    if state == GameState::Playing(Entity::Human) || state.is_finished() {
+                        break;
+                    }
+                }
+                // Redone moves aren't judged again, practice retries only count once.
+                self.session.current.take_back(self.game.moves().len());
+                return self.search_outcomes();
+            }
+            Message::ExportReplay => {
+                let path = format!("tic-tac-toe-replay-{}.html", timestamp());
+                self.text = match export::web::export(&self.game, &path) {
+                    Ok(()) => self
+                        .i18n
+                        .tr_args("replay-exported", [("path", path.into())]),
+                    Err(err) => self
+                        .i18n
+                        .tr_args("replay-export-failed", [("error", err.to_string().into())]),
+                };
+            }
+            #[cfg(feature = "render")]
+            Message::Screenshot => {
+                let path = format!("tic-tac-toe-{}.png", timestamp());
+                self.text = match self.snapshot().save_png(&path) {
+                    Ok(()) => self
+                        .i18n
+                        .tr_args("screenshot-saved", [("path", path.into())]),
+                    Err(err) => self
+                        .i18n
+                        .tr_args("screenshot-failed", [("error", err.to_string().into())]),
+                };
+            }
+            #[cfg(feature = "render")]
+            Message::ShareCard => {
+                let Some(card) = self.share_card() else {
+                    return iced::Command::none();
+                };
+                let path = format!("tic-tac-toe-result-{}.png", timestamp());
+                self.text = match card.save_png(&path) {
+                    Ok(()) => self.i18n.tr_args("card-saved", [("path", path.into())]),
+                    Err(err) => self
+                        .i18n
+                        .tr_args("screenshot-failed", [("error", err.to_string().into())]),
+                };
+            }
+            // Their buttons are hidden when the build has no renderer.
+            #[cfg(not(feature = "render"))]
+            Message::Screenshot | Message::ShareCard => {}
+            Message::Chat(chat::Event::Vote { user, cell }) => {
+                tracing::debug!(user = %user, ?cell, "chat vote");
+                self.tally.vote(user, cell);
+            }
+            Message::Chat(chat::Event::Connected) => self.text.clear(),
+            Message::Chat(chat::Event::Disconnected) => {
+                self.text = self.i18n.tr("chat-disconnected");
+            }
+            Message::Tick(activity::Timer::Clock) => {
+                #[cfg(feature = "sound")]
+                self.clock_cues();
+                if self.game.tick() {
+                    self.stop_pondering();
+                    self.prediction = None;
+                    self.finish_move();
+                }
+            }
+            Message::Tick(activity::Timer::Voting) => {
+                if self.game.state() != GameState::Playing(Entity::Human) {
+                    return iced::Command::none();
+                }
+                if self.tally.remaining > 1 {
+                    self.tally.remaining -= 1;
+                    return iced::Command::none();
+                }
+                let winner = self.tally.winner(self.game.board());
+                self.tally.open();
+                if let Some((x, y)) = winner {
+                    return self.update(Message::UserClicked(x, y));
+                }
+            }
+            Message::ToggleSettings => self.show_settings = !self.show_settings,
+            Message::TogglePrivacy => {
+                self.show_privacy = !self.show_privacy;
+                self.wiping = false;
+            }
+            Message::Checkpoints(enabled) => self.settings.privacy.checkpoints = enabled,
+            Message::SaveProgress(enabled) => self.settings.privacy.progress = enabled,
+            Message::WipeData => self.wiping = true,
+            Message::CancelWipe => self.wiping = false,
+            Message::ConfirmWipe => {
+                self.wiping = false;
+                self.text = match storage::wipe() {
+                    Ok(()) => {
+                        self.ladder = Default::default();
+                        self.progress = Default::default();
+                        self.schedule = Default::default();
+                        self.session = Default::default();
+                        self.settings = Default::default();
+                        self.i18n = i18n::I18n::new(self.settings.language);
+                        self.ia
+                            .set_memory_limit(self.settings.engine_memory_bytes());
+                        self.ia.set_mistake_rate(self.settings.mistake_rate);
+                        self.keymap = command::Keymap::default();
+                        self.show_privacy = false;
+                        self.show_settings = false;
+                        self.i18n.tr("privacy-wiped")
+                    }
+                    Err(err) => self
+                        .i18n
+                        .tr_args("privacy-wipe-failed", [("error", err.to_string().into())]),
+                };
+            }
+            Message::ToggleLadder => self.show_ladder = !self.show_ladder,
+            Message::ToggleLessons => {
+                self.show_lessons = !self.show_lessons;
+                if self.show_lessons {
+                    // Scripts may have been added since the last time.
+                    self.lessons = tutorial::Lesson::all();
+                }
+            }
+            Message::StartLesson(index) => {
+                if let Some(lesson) = self.lessons.get(index) {
+                    self.tutorial = Some(tutorial::Tutorial::new(lesson.clone()));
+                    self.show_lessons = false;
+                }
+            }
+            Message::LessonClicked(x, y) => {
+                if let Some(tutorial) = &mut self.tutorial {
+                    tutorial.click(x, y);
+                }
+            }
+            Message::LessonNext => {
+                if let Some(tutorial) = &mut self.tutorial {
+                    if !tutorial.next() {
+                        self.progress.complete(tutorial);
+                        if self.settings.privacy.progress {
+                            if let Err(err) =
+                                storage::save(tutorial::Progress::FILE, &self.progress)
+                            {
+                                tracing::error!(
+                                    code = "save-lessons",
+                                    "couldn't save the lessons progress: {}",
+                                    err
+                                );
+                            }
+                        }
+                        self.tutorial = None;
+                        self.show_lessons = true;
+                    }
+                }
+            }
+            Message::ToggleUltimate => {
+                self.ultimate = match self.ultimate {
+                    Some(_) => None,
+                    None => Some(game::ultimate::Ultimate::default()),
+                };
+                return self.update(Message::UltimateReset);
+            }
+            Message::UltimateReset => {
+                self.refused = None;
+                if let Some(ultimate) = &mut self.ultimate {
+                    *ultimate = game::ultimate::Ultimate::default();
+                    ultimate.start();
+                }
+            }
+            Message::UltimateClicked(outer, inner) => {
+                if let Some(ultimate) = &mut self.ultimate {
+                    if ultimate.state() != GameState::Playing(Entity::Human) {
+                        return iced::Command::none();
+                    }
+                    self.refused = ultimate.update(outer, inner).err();
+                    if ultimate.state() == GameState::Playing(Entity::Computer) {
+                        return reply(
+                            ultimate,
+                            game::ultimate::Ultimate::best_play,
+                            Message::UltimateReplied,
+                        );
+                    }
+                }
+            }
+            Message::UltimateReplied(searched, Some((outer, inner))) => {
+                if let Some(ultimate) = self.ultimate.as_mut().filter(|game| **game == *searched) {
+                    ultimate
+                        .update(outer, inner)
+                        .expect("the computer plays one of its own moves");
+                }
+            }
+            Message::NewTab => {
+                self.tabs.push(tabs::Tab {
+                    game: self.settings.game(Entity::Human),
+                    ..Default::default()
+                });
+                return self.select_tab(self.tabs.len() - 1);
+            }
+            Message::SelectTab(index) => return self.select_tab(index),
+            Message::CloseTab(index) => {
+                if self.tabs.len() > 1 {
+                    let mut command = iced::Command::none();
+                    if index == self.tab {
+                        command = self.select_tab(if index == 0 { 1 } else { index - 1 });
+                    }
+                    self.tabs.remove(index);
+                    if index < self.tab {
+                        self.tab -= 1;
+                    }
+                    return command;
+                }
+            }
+            Message::ToggleCube => {
+                self.cube = match self.cube {
+                    Some(_) => None,
+                    None => Some(game::cube::Cube::default()),
+                };
+                return self.update(Message::CubeReset);
+            }
+            Message::CubeReset => {
+                self.refused = None;
+                if let Some(cube) = &mut self.cube {
+                    *cube = game::cube::Cube::default();
+                    cube.start();
+                }
+            }
+            Message::CubeClicked(cell) => {
+                if let Some(cube) = &mut self.cube {
+                    if cube.state() != GameState::Playing(Entity::Human) {
+                        return iced::Command::none();
+                    }
+                    self.refused = cube.update(cell).err();
+                    if cube.state() == GameState::Playing(Entity::Computer) {
+                        return reply(cube, game::cube::Cube::best_play, Message::CubeReplied);
+                    }
+                }
+            }
+            Message::CubeReplied(searched, Some(cell)) => {
+                if let Some(cube) = self.cube.as_mut().filter(|game| **game == *searched) {
+                    cube.update(cell)
+                        .expect("the computer plays one of its own moves");
+                }
+            }
+            Message::TogglePentago => {
+                self.pentago = match self.pentago {
+                    Some(_) => None,
+                    None => Some(game::pentago::Pentago::default()),
+                };
+                return self.update(Message::PentagoReset);
+            }
+            Message::PentagoReset => {
+                self.refused = None;
+                if let Some(pentago) = &mut self.pentago {
+                    *pentago = game::pentago::Pentago::default();
+                    pentago.start();
+                }
+            }
+            Message::PentagoPlaced((x, y)) => {
+                if let Some(pentago) = &mut self.pentago {
+                    self.refused = pentago.place(x, y).err();
+                }
+            }
+            Message::PentagoRotated(rotation) => {
+                if let Some(pentago) = &mut self.pentago {
+                    if pentago.state() != GameState::Playing(Entity::Human) {
+                        return iced::Command::none();
+                    }
+                    self.refused = pentago.rotate(rotation).err();
+                    if pentago.state() == GameState::Playing(Entity::Computer) {
+                        return reply(
+                            pentago,
+                            game::pentago::Pentago::best_play,
+                            Message::PentagoReplied,
+                        );
+                    }
+                }
+            }
+            Message::PentagoReplied(searched, Some(((x, y), rotation))) => {
+                if let Some(pentago) = self.pentago.as_mut().filter(|game| **game == *searched) {
+                    pentago
+                        .place(x, y)
+                        .expect("the computer plays one of its own turns");
+                    if !pentago.state().is_finished() {
+                        pentago.rotate(rotation).expect("the mark is placed");
+                    }
+                }
+            }
+            Message::ToggleOrderChaos => {
+                self.order_chaos = match self.order_chaos {
+                    Some(_) => None,
+                    None => Some(game::order_chaos::OrderChaos::default()),
+                };
+                return self.update(Message::OrderChaosReset);
+            }
+            Message::OrderChaosReset => {
+                self.refused = None;
+                if let Some(order_chaos) = &mut self.order_chaos {
+                    *order_chaos = game::order_chaos::OrderChaos::new(order_chaos.order());
+                    order_chaos.start();
+                    if order_chaos.state() == GameState::Playing(Entity::Computer) {
+                        return reply(
+                            order_chaos,
+                            game::order_chaos::OrderChaos::best_play,
+                            Message::OrderChaosReplied,
+                        );
+                    }
+                }
+            }
+            Message::OrderChaosSwap => {
+                self.refused = None;
+                if let Some(order_chaos) = &mut self.order_chaos {
+                    *order_chaos = game::order_chaos::OrderChaos::new(!order_chaos.order());
+                    order_chaos.start();
+                    if order_chaos.state() == GameState::Playing(Entity::Computer) {
+                        return reply(
+                            order_chaos,
+                            game::order_chaos::OrderChaos::best_play,
+                            Message::OrderChaosReplied,
+                        );
+                    }
+                }
+            }
+            Message::OrderChaosMark(mark) => {
+                if let Some(order_chaos) = &mut self.order_chaos {
+                    order_chaos.pick(mark);
+                }
+            }
+            Message::OrderChaosPlaced((x, y)) => {
+                if let Some(order_chaos) = &mut self.order_chaos {
+                    if order_chaos.state() != GameState::Playing(Entity::Human) {
+                        return iced::Command::none();
+                    }
+                    self.refused = order_chaos.place(x, y, order_chaos.mark()).err();
+                    if order_chaos.state() == GameState::Playing(Entity::Computer) {
+                        return reply(
+                            order_chaos,
+                            game::order_chaos::OrderChaos::best_play,
+                            Message::OrderChaosReplied,
+                        );
+                    }
+                }
+            }
+            Message::OrderChaosReplied(searched, Some(((x, y), mark))) => {
+                // The mark picked for the human may have changed meanwhile, only the position
+                // matters.
+                let unchanged = |game: &&mut game::order_chaos::OrderChaos| {
+                    game.board() == searched.board() && game.state() == searched.state()
+                };
+                if let Some(order_chaos) = self.order_chaos.as_mut().filter(unchanged) {
+                    order_chaos
+                        .place(x, y, mark)
+                        .expect("the computer plays one of its own turns");
+                }
+            }
+            // The search panicked, there's nothing to play.
+            Message::UltimateReplied(_, None)
+            | Message::CubeReplied(_, None)
+            | Message::PentagoReplied(_, None)
+            | Message::OrderChaosReplied(_, None) => {}
+            Message::ToggleAnalysis => {
+                if self.analysis.take().is_none() {
+                    return self.open_analysis(analysis::Analysis::new(
+                        self.settings.board().with_pieces(None),
+                        self.settings.rules,
+                    ));
+                }
+            }
+            Message::AnalysisClicked(x, y) => {
+                if let Some(analysis) = &mut self.analysis {
+                    analysis.place(x, y);
+                    return self.assess_analysis();
+                }
+            }
+            Message::AnalysisSide(side) => {
+                if let Some(analysis) = &mut self.analysis {
+                    analysis.set_side(side);
+                    return self.assess_analysis();
+                }
+            }
+            Message::AnalysisSelect(node) => {
+                if let Some(analysis) = &mut self.analysis {
+                    analysis.select(node);
+                    return self.assess_analysis();
+                }
+            }
+            Message::AnalysisClear => {
+                if let Some(analysis) = &mut self.analysis {
+                    analysis.clear();
+                    return self.assess_analysis();
+                }
+            }
+            Message::AnalysisAssessed(board, side, assessment) => {
+                if let (Some(analysis), Some(assessment)) = (&mut self.analysis, assessment) {
+                    analysis.assessed(board, side, assessment);
+                }
+            }
+            Message::AnalysisFold(node) => {
+                if let Some(analysis) = &mut self.analysis {
+                    analysis.fold(node);
+                }
+            }
+            Message::AnalysisSave => {
+                if let Some(analysis) = &mut self.analysis {
+                    analysis.save(&self.i18n);
+                }
+            }
+            Message::AnalyseGame => {
+                return self.open_analysis(analysis::Analysis::from_game(&self.game));
+            }
+            Message::Menu(menu) => self.menu = menu,
+            Message::Command(command) => {
+                self.menu = None;
+                self.palette = None;
+                if !self.is_enabled(command) {
+                    return iced::Command::none();
+                }
+                self.record(recorder::Input::Command(command));
+                if let Some(tutorial) = &mut self.tutorial {
+                    tutorial.ran(command);
+                }
+                return self.update(command.message());
+            }
+            Message::Key(key, modifiers) => {
+                if let Some(command) = self.keymap.command(key, modifiers) {
+                    return self.update(Message::Command(command));
+                }
+            }
+            Message::OpenPalette => {
+                self.palette = Some(palette::Palette::default());
+                return iced::widget::text_input::focus(palette::input());
+            }
+            Message::ToggleTheme => {
+                self.settings.theme = match self.settings.theme {
+                    settings::Theme::Dark => settings::Theme::Light,
+                    settings::Theme::Light => settings::Theme::Dark,
+                };
+            }
+            Message::ZoomIn => self.settings.zoom += settings::ZOOM_STEP,
+            Message::ZoomOut => self.settings.zoom -= settings::ZOOM_STEP,
+            Message::ZoomReset => self.settings.zoom = 100,
+            Message::ToggleMiniMode => {
+                self.mini = !self.mini;
+                self.menu = None;
+                self.palette = None;
+                let zoom = f64::from(self.settings.zoom) / 100.0;
+                let (width, height) = if self.mini {
+                    self.mini_size()
+                } else {
+                    self.window_size.unwrap_or(DEFAULT_WINDOW_SIZE)
+                };
+                let scaled = |points: u32| (f64::from(points) * zoom).round() as u32;
+                return iced::Command::batch([
+                    iced::window::change_always_on_top(self.mini),
+                    iced::window::resize(scaled(width), scaled(height)),
+                ]);
+            }
+            Message::VoiceCommand(command) => self.settings.voice_command = command,
+            Message::ApplyVoiceCommand => {
+                self.settings.voice_command = self.settings.voice_command.trim().to_string();
+                self.recognizer = self.settings.voice_command.clone();
+            }
+            Message::Voice(voice::Event::Heard(phrase)) => {
+                let board = self.game.board();
+                match voice::understand(&phrase, board.size(), &self.i18n) {
+                    Some(voice::Intent::Command(command)) => {
+                        return self.update(Message::Command(command));
+                    }
+                    // Under gravity the mark falls down the column named.
+                    Some(voice::Intent::Cell(_, y)) if board.has_gravity() => {
+                        return self.update(Message::ColumnPressed(y));
+                    }
+                    Some(voice::Intent::Cell(x, y)) => {
+                        return self.update(Message::CellPressed(x, y));
+                    }
+                    None => {
+                        self.text = self
+                            .i18n
+                            .tr_args("voice-unknown", [("phrase", phrase.into())]);
+                    }
+                }
+            }
+            Message::Voice(voice::Event::Stopped(reason)) => {
+                self.text = self
+                    .i18n
+                    .tr_args("voice-stopped", [("reason", reason.into())]);
+            }
+            Message::SummonKey(keys) => self.settings.summon_key = keys,
+            Message::ApplySummonKey => {
+                self.settings.summon_key = self.settings.summon_key.trim().to_string();
+                #[cfg(feature = "hotkey")]
+                self.register_summon_key();
+            }
+            #[cfg(feature = "hotkey")]
+            Message::Summon => {
+                if self.focused {
+                    return self.update(Message::ToggleMiniMode);
+                }
+                return iced::Command::batch([
+                    iced::window::minimize(false),
+                    iced::window::gain_focus(),
+                ]);
+            }
+            #[cfg(feature = "hotkey")]
+            Message::WindowFocused(focused) => self.focused = focused,
+            Message::WindowResized(width, height) => {
+                if !self.mini {
+                    self.window_size = Some((width, height));
+                }
+            }
+            Message::Hint => {
+                if !self.is_enabled(command::Command::Hint) {
+                    return iced::Command::none();
+                }
+                let board = *self.game.board();
+                let mut ia = self.ia.clone();
+                let mut search = move || {
+                    let _span = tracing::debug_span!("engine.search").entered();
+                    ia.best_action(board, Entity::Human)
+                };
+                if self.synchronous {
+                    return self.update(Message::HintReady(board, search()));
+                }
+                return iced::Command::perform(background(search), move |action| {
+                    Message::HintReady(board, action.flatten())
+                });
+            }
+            Message::HintReady(board, action) => {
+                // The human moved on meanwhile.
+                if board != *self.game.board() || !self.is_enabled(command::Command::Hint) {
+                    return iced::Command::none();
+                }
+                self.draft.hint = action;
+                return iced::Command::perform(
+                    background(|| std::thread::sleep(HINT_TIME)),
+                    move |_| Message::HintExpired(board),
+                );
+            }
+            Message::HintExpired(board) => {
+                if board == *self.game.board() {
+                    self.draft.hint = None;
+                }
+            }
+            Message::Resign => {
+                self.stop_pondering();
+                self.prediction = None;
+                if self.game.resign().is_ok() {
+                    self.finish_move();
+                }
+            }
+            Message::RunCalibration => {
+                if let Some(calibration) = &mut self.calibration {
+                    calibration.running = true;
+                    return iced::Command::perform(
+                        background(calibration::run),
+                        Message::Calibrated,
+                    );
+                }
+            }
+            Message::Calibrated(matchups) => {
+                if let Some(calibration) = &mut self.calibration {
+                    calibration.running = false;
+                    calibration.matchups = matchups.unwrap_or_default();
+                }
+            }
+            Message::CloseCalibration => self.calibration = None,
+            Message::ToggleFeedback => {
+                self.feedback = match self.feedback {
+                    Some(_) => None,
+                    None => Some(feedback::Feedback::default()),
+                };
+            }
+            Message::FeedbackDescription(description) => {
+                if let Some(feedback) = &mut self.feedback {
+                    feedback.description = description;
+                }
+            }
+            Message::FeedbackConsent(item, included) => {
+                if let Some(feedback) = &mut self.feedback {
+                    feedback.set(item, included);
+                }
+            }
+            Message::SaveFeedback | Message::EmailFeedback => {
+                let Some(feedback) = &self.feedback else {
+                    return iced::Command::none();
+                };
+                let path = format!("tic-tac-toe-feedback-{}.zip", timestamp());
+                if let Err(err) = feedback.save(path.as_ref(), &self.settings, &self.game) {
+                    self.text = self
+                        .i18n
+                        .tr_args("feedback-failed", [("error", err.to_string().into())]);
+                    return iced::Command::none();
+                }
+                self.text = self
+                    .i18n
+                    .tr_args("feedback-saved", [("path", path.clone().into())]);
+                if matches!(msg, Message::EmailFeedback) {
+                    let body = format!(
+                        "{}\n\n{}",
+                        feedback.description.trim(),
+                        self.i18n
+                            .tr_args("feedback-attach", [("path", path.into())])
+                    );
+                    if let Err(err) = feedback::email(&self.i18n.tr("feedback-subject"), &body) {
+                        self.text = self
+                            .i18n
+                            .tr_args("feedback-email-failed", [("error", err.to_string().into())]);
+                    }
+                }
+                self.feedback = None;
+            }
+            Message::BrowseReplays => match library::Library::pages() {
+                Ok(pages) => self.replays = Some(pages),
+                Err(err) => {
+                    self.text = self
+                        .i18n
+                        .tr_args("replay-open-failed", [("error", err.to_string().into())])
+                }
+            },
+            Message::OpenReplay(path) => match library::Library::open(&path) {
+                Ok(game) => {
+                    self.replays = None;
+                    return self.open_analysis(analysis::Analysis::from_game(&game));
+                }
+                Err(err) => {
+                    self.text = self
+                        .i18n
+                        .tr_args("replay-open-failed", [("error", err.into())])
+                }
+            },
+            Message::CloseReplays => self.replays = None,
+            Message::PaletteQuery(query) => {
+                if let Some(palette) = &mut self.palette {
+                    palette.search(query);
+                }
+            }
+            Message::PaletteSelect(by) => {
+                if let Some(palette) = &mut self.palette {
+                    palette.select(by, &self.i18n);
+                }
+            }
+            Message::PaletteRun => {
+                let selected = self
+                    .palette
+                    .as_ref()
+                    .and_then(|palette| palette.selected(&self.i18n));
+                if let Some(command) = selected {
+                    return self.update(Message::Command(command));
+                }
+            }
+            Message::MoveFocus { backwards } => return ui::focus::step(backwards),
+            Message::Escape => {
+                if self.palette.take().is_none() && self.menu.take().is_none() {
+                    if self.mini {
+                        return self.update(Message::ToggleMiniMode);
+                    }
+                    return self.update(Message::Overlay(false));
+                }
+            }
+            Message::LeaveLesson => {
+                self.tutorial = None;
+                self.show_lessons = true;
+            }
+            Message::ToggleTrainer => {
+                self.trainer = match self.trainer {
+                    Some(_) => None,
+                    None => {
+                        let cards = trainer::cards(&mut game::Computer::default());
+                        let today = timestamp() / 86_400;
+                        Some(trainer::Trainer::new(cards, &self.schedule, today))
+                    }
+                };
+            }
+            Message::TrainerClicked(x, y) => {
+                if let Some(trainer) = &mut self.trainer {
+                    trainer.click(x, y, &mut self.schedule);
+                    if self.settings.privacy.progress {
+                        if let Err(err) = storage::save(trainer::Schedule::FILE, &self.schedule) {
+                            tracing::error!(
+                                code = "save-openings",
+                                "couldn't save the opening trainer: {}",
+                                err
+                            );
+                        }
+                    }
+                }
+            }
+            Message::ToggleSpectate => {
+                let watching = self.watching();
+                self.spectate = match self.spectate {
+                    Some(_) => None,
+                    None => Some(spectate::Spectate::default()),
+                };
+                // The game the computer played against itself makes way for one of the human.
+                if watching {
+                    return self.update(Message::Reset);
+                }
+            }
+            Message::SpectateSide(side, contender) => {
+                if let Some(spectate) = &mut self.spectate {
+                    match side {
+                        Entity::Human => spectate.o = contender,
+                        _ => spectate.x = contender,
+                    }
+                }
+            }
+            Message::SpectateDelay(millis) => self.settings.spectate_delay = millis,
+            Message::WatchSpectate => {
+                let Some(spectate) = &mut self.spectate else {
+                    return iced::Command::none();
+                };
+                spectate.watching = true;
+                self.stop_pondering();
+                self.prediction = None;
+                self.draft = tabs::Draft::default();
+                self.text.clear();
+                self.game = self.settings.game(Entity::Computer);
+                self.ia.new_game(self.game.rules());
+                self.outcomes.clear();
+                return self.open_game();
+            }
+            Message::TrainerNext => {
+                if let Some(trainer) = &mut self.trainer {
+                    trainer.next(&self.schedule);
+                }
+            }
+            Message::Challenge(opponent) => {
+                self.opponent = opponent;
+                self.show_ladder = false;
+                return self.update(Message::Reset);
+            }
+            Message::EngineMemory(kib) => {
+                self.settings.engine_memory = kib;
+                self.ia
+                    .set_memory_limit(self.settings.engine_memory_bytes());
+            }
+            Message::Ponder(enabled) => self.settings.ponder = enabled,
+            Message::BlunderAlarm(enabled) => self.settings.blunder_alarm = enabled,
+            Message::Practice(enabled) => self.settings.practice = enabled,
+            Message::Tutor(enabled) => self.settings.tutor = enabled,
+            Message::MistakeRate(percent) => {
+                self.settings.mistake_rate = percent;
+                self.ia.set_mistake_rate(percent);
+            }
+            Message::Scramble(enabled) => self.settings.scramble = enabled,
+            Message::LowTime(seconds) => self.settings.low_time = seconds,
+            Message::TickingTime(seconds) => self.settings.ticking_time = seconds,
+            Message::Mute(enabled) => self.settings.mute = enabled,
+            Message::ReduceMotion(enabled) => self.settings.reduce_motion = enabled,
+            Message::LowSpec(enabled) => {
+                self.settings.low_spec = enabled;
+                tokens::set_low_spec(enabled);
+            }
+            Message::PerfHud(enabled) => {
+                self.settings.perf_hud = enabled;
+                self.frames = perf::Frames::default();
+            }
+            Message::Frame(now) => self.frames.tick(now),
+            Message::DwellTime(millis) => self.settings.dwell_time = millis,
+            Message::SettingsReloaded(Ok(settings)) => {
+                // Saving them on close triggers a reload too, which changes nothing.
+                if settings != self.settings {
+                    if settings.language != self.settings.language {
+                        self.i18n = i18n::I18n::new(settings.language);
+                    }
+                    self.ia.set_memory_limit(settings.engine_memory_bytes());
+                    self.ia.set_mistake_rate(settings.mistake_rate);
+                    self.keymap = command::Keymap::new(&settings.bindings);
+                    #[cfg(feature = "hotkey")]
+                    let summon_changed = settings.summon_key != self.settings.summon_key;
+                    self.settings = settings;
+                    self.recognizer = self.settings.voice_command.clone();
+                    tokens::set_low_spec(self.settings.low_spec);
+                    self.redact_names();
+                    #[cfg(feature = "hotkey")]
+                    if summon_changed {
+                        self.register_summon_key();
+                    }
+                    self.text = self.i18n.tr("settings-reloaded");
+                }
+            }
+            Message::SettingsReloaded(Err(err)) => {
+                self.text = self
+                    .i18n
+                    .tr_args("settings-reload-failed", [("error", err.into())]);
+            }
+            Message::Autosave(autosave) => self.settings.autosave = autosave,
+            Message::BoardSize(size) => self.settings.board_size = size,
+            Message::LineLength(length) => self.settings.line_length = length,
+            Message::Gravity(enabled) => self.settings.gravity = enabled,
+            Message::Morris(enabled) => self.settings.morris = enabled,
+            Message::Rules(rules) => self.settings.rules = rules,
+            Message::Clock(minutes) => self.settings.clock = minutes,
+            Message::Starter(starter) => self.settings.starter = starter,
+            Message::PlayerName(name) => {
+                self.settings.player.name = name.chars().take(player::NAME_LENGTH).collect();
+                self.redact_names();
+            }
+            Message::PlayerGlyph(glyph) => self.settings.player.glyph = glyph,
+            Message::PlayerColor(color) => self.settings.player.color = color,
+            Message::Pondered(board, prediction) => {
+                // Results of searches interrupted by a move belong to an older position.
+                if board == *self.game.board() {
+                    self.pondering = None;
+                    self.prediction = prediction;
+                }
+            }
+            Message::CloseRequested => {
+                let in_progress = !self.game.moves().is_empty() && !self.game.state().is_finished();
+                if !in_progress || !self.settings.privacy.checkpoints {
+                    return self.shutdown(false);
+                }
+                self.closing = true;
+            }
+            Message::Quit { save } => return self.shutdown(save),
+            Message::CancelQuit => self.closing = false,
+            Message::Language(language) => {
+                self.settings.language = language;
+                self.i18n = i18n::I18n::new(language);
+            }
+            Message::Overlay(enabled) => {
+                if self.overlay != enabled {
+                    self.overlay = enabled;
+                    return iced::window::toggle_decorations();
+                }
+            }
+            Message::Tick(activity::Timer::Replay) => {
+                let Some(player) = &mut self.player else {
+                    return iced::Command::none();
+                };
+                let due = player.due();
+                if player.is_finished() {
+                    self.player = None;
+                }
+                return iced::Command::batch(due.into_iter().map(|input| {
+                    self.update(match input {
+                        recorder::Input::Click(x, y) => Message::UserClicked(x, y),
+                        recorder::Input::Command(command) => Message::Command(command),
+                    })
+                }));
             }
         };
         iced::Command::none()
     }
 
+    fn subscription(&self) -> iced::Subscription<Self::Message> {
+        let keys = iced::subscription::events_with(|event, _| match event {
+            iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                key_code: iced::keyboard::KeyCode::Escape,
+                ..
+            }) => Some(Message::Escape),
+            iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                key_code: iced::keyboard::KeyCode::Up,
+                ..
+            }) => Some(Message::PaletteSelect(-1)),
+            iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                key_code: iced::keyboard::KeyCode::Down,
+                ..
+            }) => Some(Message::PaletteSelect(1)),
+            iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                key_code: iced::keyboard::KeyCode::Tab,
+                modifiers,
+            }) => Some(Message::MoveFocus {
+                backwards: modifiers.shift(),
+            }),
+            iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) if modifiers.command() => Some(Message::Key(key_code, modifiers)),
+            iced::Event::Window(iced::window::Event::CloseRequested) => {
+                Some(Message::CloseRequested)
+            }
+            iced::Event::Window(iced::window::Event::Resized { width, height }) => {
+                Some(Message::WindowResized(width, height))
+            }
+            #[cfg(feature = "hotkey")]
+            iced::Event::Window(iced::window::Event::Focused) => Some(Message::WindowFocused(true)),
+            #[cfg(feature = "hotkey")]
+            iced::Event::Window(iced::window::Event::Unfocused) => {
+                Some(Message::WindowFocused(false))
+            }
+            _ => None,
+        });
+        let voting = self.twitch_channel.is_some()
+            && matches!(
+                self.game.state(),
+                GameState::Ready | GameState::Playing(Entity::Human)
+            );
+        let timers = activity::Tracker::default()
+            .with(activity::Timer::Voting, voting)
+            .with(activity::Timer::Replay, self.player.is_some())
+            .with(
+                activity::Timer::Clock,
+                self.game.is_timed() && matches!(self.game.state(), GameState::Playing(_)),
+            );
+
+        let mut subscriptions = vec![
+            keys,
+            timers.subscription().map(Message::Tick),
+            settings::watch().map(Message::SettingsReloaded),
+        ];
+        if let Some(channel) = &self.twitch_channel {
+            subscriptions.push(chat::listen(channel.clone()).map(Message::Chat));
+        }
+        if !self.recognizer.is_empty() {
+            subscriptions.push(voice::listen(self.recognizer.clone()).map(Message::Voice));
+        }
+        #[cfg(feature = "hotkey")]
+        if self.summon.is_some() {
+            subscriptions.push(hotkey::listen().map(|()| Message::Summon));
+        }
+        // Each frame counted asks for the next one, so the HUD shows how fast the window can
+        // redraw, at the cost of redrawing all the time.
+        if self.settings.perf_hud {
+            subscriptions.push(iced::window::frames().map(Message::Frame));
+        }
+        iced::Subscription::batch(subscriptions)
+    }
+
     fn view(&self) -> iced::Element<'_, Self::Message, iced::Renderer<Self::Theme>> {
-        let activate = self.game.state().is_playable();
-        container(
-            column!(
-                row![
-                    text_button(self.game.board()[0][0].as_str(), 0, 0, activate),
-                    text_button(self.game.board()[0][1].as_str(), 0, 1, activate),
-                    text_button(self.game.board()[0][2].as_str(), 0, 2, activate)
-                ]
-                .align_items(iced::Alignment::Center)
-                .spacing(10),
-                row![
-                    text_button(self.game.board()[1][0].as_str(), 1, 0, activate),
-                    text_button(self.game.board()[1][1].as_str(), 1, 1, activate),
-                    text_button(self.game.board()[1][2].as_str(), 1, 2, activate)
+        let _span = tracing::debug_span!("view").entered();
+        static FIRST_FRAME: std::sync::Once = std::sync::Once::new();
+        FIRST_FRAME.call_once(|| {
+            if let Some(started) = STARTED.get() {
+                tracing::info!(elapsed = ?started.elapsed(), "first frame");
+            }
+        });
+        let activate = self.game.state().is_playable()
+            && self.twitch_channel.is_none()
+            && self.draft.blunder.is_none()
+            && self.draft.mistake.is_none()
+            && !self.watching();
+        let mut undo = primary(self.i18n.tr("undo"));
+        if self.is_enabled(command::Command::Undo) {
+            undo = undo.on_press(Message::Command(command::Command::Undo));
+        }
+        let mut hint = primary(self.i18n.tr("hint"));
+        if self.is_enabled(command::Command::Hint) {
+            hint = hint.on_press(Message::Command(command::Command::Hint));
+        }
+        let mut redo = primary(self.i18n.tr("redo"));
+        if self.can_step() && self.game.can_redo() {
+            redo = redo.on_press(Message::Command(command::Command::Redo));
+        }
+        let mut game_actions = row![
+            focusable(
+                primary(self.i18n.tr("reset"))
+                    .on_press(Message::Command(command::Command::NewGame))
+            ),
+            focusable(undo),
+            focusable(redo),
+            focusable(hint),
+            focusable(primary(self.i18n.tr("overlay")).on_press(Message::Overlay(true)))
+        ]
+        .spacing(tokens::SPACE_M);
+        if features::RENDER {
+            game_actions = game_actions.push(focusable(
+                primary(self.i18n.tr("screenshot"))
+                    .on_press(Message::Command(command::Command::Screenshot)),
+            ));
+        }
+        let actions = row![
+            game_actions,
+            focusable(
+                primary(self.i18n.tr("settings"))
+                    .on_press(Message::Command(command::Command::Settings))
+            ),
+            focusable(
+                primary(self.i18n.tr("ladder"))
+                    .on_press(Message::Command(command::Command::Ladder))
+            ),
+            focusable(
+                primary(self.i18n.tr("lessons"))
+                    .on_press(Message::Command(command::Command::Lessons))
+            ),
+            focusable(
+                primary(self.i18n.tr("trainer"))
+                    .on_press(Message::Command(command::Command::Trainer))
+            ),
+            focusable(
+                primary(self.i18n.tr("spectate"))
+                    .on_press(Message::Command(command::Command::Spectate))
+            ),
+            focusable(
+                primary(self.i18n.tr("ultimate"))
+                    .on_press(Message::Command(command::Command::Ultimate))
+            ),
+            focusable(
+                primary(self.i18n.tr("cube")).on_press(Message::Command(command::Command::Cube))
+            ),
+            focusable(
+                primary(self.i18n.tr("pentago"))
+                    .on_press(Message::Command(command::Command::Pentago))
+            ),
+            focusable(
+                primary(self.i18n.tr("order-chaos"))
+                    .on_press(Message::Command(command::Command::OrderChaos))
+            ),
+            focusable(
+                primary(self.i18n.tr("analysis"))
+                    .on_press(Message::Command(command::Command::Analysis))
+            )
+        ]
+        .spacing(tokens::SPACE_M);
+        let chat_tally = match &self.twitch_channel {
+            Some(channel) if self.game.state().is_playable() => column![
+                text(self.i18n.tr_args(
+                    "chat-voting",
+                    [
+                        ("channel", channel.as_str().into()),
+                        ("seconds", self.tally.remaining.into()),
+                    ]
+                )),
+                text(self.tally.summary())
+            ]
+            .align_items(iced::Alignment::Center),
+            _ => column![],
+        };
+        let size = self.game.board().size();
+        let winning_line = self.game.winning_line();
+        let players = self.players();
+        let board = column(
+            (0..size)
+                .map(|x| {
+                    row((0..size)
+                        .map(|y| {
+                            let cells = self.game.board();
+                            // Once its marks are placed, the human picks one up then clicks
+                            // where it slides.
+                            let (press, enabled) = if cells.must_slide(Entity::Human) {
+                                match self.draft.picked {
+                                    Some(from)
+                                        if cells
+                                            .slides(Entity::Human)
+                                            .contains(&(from, (x, y))) =>
+                                    {
+                                        (Message::Slide(from, (x, y)), true)
+                                    }
+                                    _ => (Message::PickUp(x, y), cells[x][y] == Entity::Human),
+                                }
+                            } else if cells.has_gravity() {
+                                // Any cell of a column drops a mark in it.
+                                (Message::ColumnPressed(y), cells.drop_row(y).is_some())
+                            } else {
+                                (Message::CellPressed(x, y), true)
+                            };
+                            let mark = cells[x][y];
+                            let glyph = text(players.mark(mark)).size(tokens::TEXT_BODY);
+                            let glyph = match players.get(mark).color {
+                                Some(color) if mark != Entity::Empty => glyph.style(color),
+                                _ => glyph,
+                            };
+                            let enabled = activate && enabled;
+                            let dwell_press =
+                                (self.settings.dwell_time > 0 && enabled).then(|| press.clone());
+                            let cell = cell(glyph, press, enabled);
+                            let queued = [self.draft.picked, self.draft.premove];
+                            let cell = focusable(if queued.contains(&Some((x, y))) {
+                                cell.style(button_style(iced::theme::Button::Primary))
+                            } else if winning_line.is_some_and(|line| line.contains((x, y))) {
+                                cell.style(button_style(iced::theme::Button::Positive))
+                            } else if self.draft.hint.is_some_and(|hint| hint.touches((x, y))) {
+                                cell.style(button_style(iced::theme::Button::Secondary))
+                            } else {
+                                cell
+                            });
+                            // Hovering a cell long enough clicks it too.
+                            match dwell_press {
+                                Some(press) => dwell(
+                                    cell,
+                                    press,
+                                    std::time::Duration::from_millis(
+                                        self.settings.dwell_time.into(),
+                                    ),
+                                ),
+                                None => cell,
+                            }
+                        })
+                        .collect())
+                    .align_items(iced::Alignment::Center)
+                    .spacing(tokens::SPACE_M)
+                    .into()
+                })
+                .collect(),
+        )
+        .align_items(iced::Alignment::Center)
+        .spacing(tokens::SPACE_M);
+
+        if self.overlay && !self.closing {
+            return container(
+                column![board, text(self.score_text()).size(tokens::TEXT_HEADING)]
+                    .align_items(iced::Alignment::Center)
+                    .spacing(tokens::SPACE_M),
+            )
+            .style(iced::theme::Container::Custom(Box::new(overlay::ChromaKey)))
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .center_x()
+            .center_y()
+            .into();
+        }
+
+        if self.mini && !self.closing {
+            let footer: Element<Message, Renderer> = match self.prompt() {
+                Some(prompt) => prompt,
+                None => column![
+                    text(self.result_text().unwrap_or_else(|| self.text.clone()))
+                        .size(tokens::TEXT_SMALL),
+                    focusable(
+                        secondary(self.i18n.tr("mini-leave")).on_press(Message::ToggleMiniMode)
+                    )
                 ]
                 .align_items(iced::Alignment::Center)
-                .spacing(10),
-                row![
-                    text_button(self.game.board()[2][0].as_str(), 2, 0, activate),
-                    text_button(self.game.board()[2][1].as_str(), 2, 1, activate),
-                    text_button(self.game.board()[2][2].as_str(), 2, 2, activate)
-                ]
+                .spacing(tokens::SPACE_S)
+                .into(),
+            };
+            return container(
+                column![board, footer]
+                    .align_items(iced::Alignment::Center)
+                    .spacing(tokens::SPACE_M),
+            )
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .center_x()
+            .center_y()
+            .into();
+        }
+
+        let content = if self.closing {
+            modal(
+                self.i18n.tr("close-prompt"),
+                [
+                    primary(self.i18n.tr("close-save")).on_press(Message::Quit { save: true }),
+                    primary(self.i18n.tr("close-discard")).on_press(Message::Quit { save: false }),
+                    secondary(self.i18n.tr("close-cancel")).on_press(Message::CancelQuit),
+                ],
+            )
+        } else if let Some(palette) = &self.palette {
+            palette.view(|command| self.is_enabled(command), &self.keymap, &self.i18n)
+        } else if let Some(replays) = &self.replays {
+            self.replays_view(replays)
+        } else if let Some(feedback) = &self.feedback {
+            feedback.view(&self.i18n)
+        } else if let Some(calibration) = &self.calibration {
+            calibration.view(&self.i18n)
+        } else if self.show_privacy {
+            self.settings
+                .privacy
+                .view(self.settings.autosave, self.wiping, &self.i18n)
+        } else if self.show_settings {
+            self.settings.view(&self.i18n)
+        } else if let Some(prompt) = self.prompt() {
+            column![board, prompt]
                 .align_items(iced::Alignment::Center)
-                .spacing(10),
-                text(self.text.clone()),
-                button("reset").on_press(Message::Reset).padding([10, 20])
+                .spacing(tokens::SPACE_M)
+                .into()
+        } else if let Some(spectate) = &self.spectate {
+            let status = match self.result_text() {
+                Some(result) => result,
+                None if self.game.state().is_playable() => self.i18n.tr("computer-thinking"),
+                None => String::new(),
+            };
+            spectate.view(
+                board.into(),
+                status,
+                self.settings.spectate_delay,
+                &self.i18n,
+            )
+        } else if let Some(analysis) = &self.analysis {
+            analysis.view(&self.i18n)
+        } else if let Some(game) = &self.order_chaos {
+            order_chaos::view(game, self.refused, &self.players(), &self.i18n)
+        } else if let Some(game) = &self.pentago {
+            pentago::view(game, self.refused, &self.players(), &self.i18n)
+        } else if let Some(game) = &self.cube {
+            cube::view(game, self.refused, &self.players(), &self.i18n)
+        } else if let Some(game) = &self.ultimate {
+            ultimate::view(game, self.refused, &self.players(), &self.i18n)
+        } else if let Some(tutorial) = &self.tutorial {
+            tutorial.view(&self.i18n)
+        } else if let Some(trainer) = &self.trainer {
+            trainer.view(&self.schedule, &self.i18n)
+        } else if self.show_lessons {
+            tutorial::menu(&self.lessons, &self.progress, &self.i18n)
+        } else if self.show_ladder {
+            self.ladder.view(self.opponent, &self.i18n)
+        } else {
+            let opponent = match self.opponent {
+                Some(bot) => self.i18n.tr_args(
+                    "ladder-opponent",
+                    [("bot", self.i18n.tr(bot.name()).into())],
+                ),
+                None => String::new(),
+            };
+            let status: Element<Message, Renderer> = match self.result_text() {
+                Some(result) => panel(self.result_card(result)).into(),
+                None if self.text.is_empty()
+                    && self.game.state() == GameState::Playing(Entity::Computer) =>
+                {
+                    text(self.i18n.tr("computer-thinking")).into()
+                }
+                None => text(self.text.clone()).into(),
+            };
+            let opener = match self.game.starter() {
+                Entity::Computer => "opener-computer",
+                _ => "opener-human",
+            };
+            column!(
+                tabs::bar(self.tabs.len(), self.tab, &self.i18n),
+                text(opponent),
+                text(self.i18n.tr(opener)).size(tokens::TEXT_SMALL),
+                self.clock(),
+                board,
+                status,
+                chat_tally,
+                text(self.score_text()),
+                text(match self.session.total_accuracy().percent() {
+                    Some(percent) => self.i18n.tr_args(
+                        "session-accuracy",
+                        [("percent", self.i18n.percent(percent).into())]
+                    ),
+                    None => String::new(),
+                }),
+                self.history(),
+                text(if self.pondering.is_some() {
+                    self.i18n.tr("pondering")
+                } else {
+                    String::new()
+                })
+                .size(tokens::TEXT_SMALL),
+                actions
             )
             .align_items(iced::Alignment::Center)
-            .spacing(10),
-        )
-        .height(Length::Fill)
-        .width(Length::Fill)
-        .center_x()
-        .center_y()
+            .spacing(tokens::SPACE_M)
+            .into()
+        };
+
+        let bar = menu::bar(
+            self.menu,
+            |command| self.is_enabled(command),
+            &self.keymap,
+            &self.i18n,
+        );
+        let hud: Element<Message, Renderer> = if self.settings.perf_hud {
+            self.perf_hud()
+        } else {
+            column![].into()
+        };
+        column![
+            bar,
+            hud,
+            container(content)
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .center_x()
+                .center_y()
+        ]
         .into()
     }
 
     fn theme(&self) -> Self::Theme {
-        iced::Theme::Dark
+        self.settings.theme.iced()
+    }
+
+    fn scale_factor(&self) -> f64 {
+        f64::from(self.settings.zoom) / 100.0
+    }
+}
+
+/// Size iced opens the window with, in points.
+const DEFAULT_WINDOW_SIZE: (u32, u32) = (1024, 768);
+
+/// How long the move recommended by a hint stays highlighted.
+const HINT_TIME: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Narrowest the window gets in the mini mode, so the text below the board fits.
+const MINI_WIDTH: u32 = 200;
+
+/// Height of what the mini mode shows below the board.
+const MINI_FOOTER: u32 = 90;
+
+/// When the process started, used to measure how long the window takes to show up.
+static STARTED: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+
+/// Runs `work` on its own thread so it doesn't stall the executor, resolving to its output.
+async fn background<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Option<T> {
+    let (sender, receiver) = iced::futures::channel::oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(work());
+    });
+    receiver.await.ok()
+}
+
+/// Searches the computer's reply to `game`, one of a variant, in the background. The message
+/// made by `ready` carries the game searched, so the reply is dropped if the game moved on
+/// meanwhile.
+fn reply<G: Clone + Send + 'static, M: Send + 'static>(
+    game: &G,
+    search: fn(&G) -> M,
+    ready: fn(Box<G>, Option<M>) -> Message,
+) -> iced::Command<Message> {
+    let searched = Box::new(game.clone());
+    let game = game.clone();
+    iced::Command::perform(background(move || search(&game)), move |reply| {
+        ready(searched, reply)
+    })
+}
+
+/// Whole seconds shown on a clock with `left` on it, rounded up so it reads 0:00 only once out.
+fn seconds_left(left: std::time::Duration) -> u64 {
+    left.as_secs_f32().ceil() as u64
+}
+
+/// The color between `from` and `to` of a clock pulsing once a second, `to` as each second
+/// starts.
+fn pulse(from: iced::Color, to: iced::Color, left: std::time::Duration) -> iced::Color {
+    // What's left of the current second shrinks as it goes by, fading back to `from`.
+    let strength = left.subsec_millis() as f32 / 1000.0;
+    let mix = |from: f32, to: f32| from + (to - from) * strength;
+    iced::Color::from_rgb(mix(from.r, to.r), mix(from.g, to.g), mix(from.b, to.b))
+}
+
+fn timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Plays the whole replay without a window and writes the resulting screen into `output`.
+#[cfg(feature = "render")]
+fn render_headless(flags: Flags, output: std::path::PathBuf) {
+    let (mut app, _) = App::new(flags);
+    if let Some(mut player) = app.player.take() {
+        for input in player.drain() {
+            let _ = app.update(match input {
+                recorder::Input::Click(x, y) => Message::UserClicked(x, y),
+                recorder::Input::Command(command) => Message::Command(command),
+            });
+        }
+    }
+    if let Err(err) = app.snapshot().save_png(&output) {
+        eprintln!("Couldn't render to {}: {}", output.display(), err);
+        std::process::exit(1);
     }
 }
 
-fn text_button<'a>(
-    content: impl Into<Element<'a, Message, Renderer>>,
-    x: usize,
-    y: usize,
-    op: bool,
-) -> button::Button<'a, Message, Renderer> {
-    let mut btn = button(content).style(iced::theme::Button::Text).padding(10);
-    if op {
-        btn = btn.on_press(Message::UserClicked(x, y));
+/// File where the tablebase of the board of `size` is written by default.
+#[cfg(feature = "tablebases")]
+fn tablebase_file(size: usize, line: usize) -> std::io::Result<std::path::PathBuf> {
+    let folder = storage::folder("tablebases")?;
+    std::fs::create_dir_all(&folder)?;
+    Ok(folder.join(format!("{size}x{size}-{line}.ttb")))
+}
+
+/// Reads every tablebase written by the `tablebase` command. Those which can't be read are
+/// skipped.
+#[cfg(feature = "tablebases")]
+fn load_tablebases() -> Vec<game::tablebase::Tablebase> {
+    let entries = match storage::folder("tablebases").and_then(std::fs::read_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return vec![],
+        Err(err) => {
+            tracing::warn!("couldn't list the tablebases: {}", err);
+            return vec![];
+        }
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ttb"))
+        .filter_map(|path| match game::tablebase::Tablebase::load(&path) {
+            Ok(tablebase) => Some(tablebase),
+            Err(err) => {
+                tracing::warn!("skipped the tablebase {}: {}", path.display(), err);
+                None
+            }
+        })
+        .collect()
+}
+
+/// The `tablebase [--size 3|4] [--output <file>]` command: solves a whole board, writes it
+/// and checks the written file.
+#[cfg(feature = "tablebases")]
+fn generate_tablebase(mut args: impl Iterator<Item = String>) {
+    let mut size = 3;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = args.next().and_then(|size| size.parse().ok()).unwrap_or(0),
+            "--output" => output = args.next().map(std::path::PathBuf::from),
+            _ => {}
+        }
+    }
+    let Some(&(size, line)) = game::tablebase::SUPPORTED
+        .iter()
+        .find(|(supported, _)| *supported == size)
+    else {
+        eprintln!("Only 3×3 and 4×4 boards can be solved");
+        std::process::exit(1);
+    };
+    let fail = |err: std::io::Error| -> ! {
+        eprintln!("Couldn't write the tablebase: {}", err);
+        std::process::exit(1);
+    };
+    let output = match output.map_or_else(|| tablebase_file(size, line), Ok) {
+        Ok(output) => output,
+        Err(err) => fail(err),
+    };
+
+    eprintln!("Solving {size}×{size} with {line} in a row");
+    let tablebase = game::tablebase::Tablebase::generate(size, line, |done, total, solved| {
+        eprintln!("[{done}/{total}] {solved} positions");
+    });
+    if let Err(err) = tablebase.save(&output) {
+        fail(err);
+    }
+    eprintln!("Verifying {}", output.display());
+    let verified = game::tablebase::Tablebase::load(&output)
+        .map_err(|err| err.to_string())
+        .and_then(|tablebase| {
+            tablebase.verify().map_err(|board| {
+                let rows: Vec<String> = board
+                    .rows()
+                    .map(|row| row.iter().map(|entity| entity.as_str()).collect())
+                    .collect();
+                format!("wrong result for {:?}", rows)
+            })
+        });
+    match verified {
+        Ok(checked) => eprintln!("{checked} positions written to {}", output.display()),
+        Err(err) => {
+            eprintln!("The tablebase is wrong: {}", err);
+            std::process::exit(1);
+        }
     }
-    btn
+}
+
+/// The `train [--games <count>] [--output <file>]` command: learns the network of the
+/// network bot from scratch, writes it and plays it against the engine.
+#[cfg(feature = "rl")]
+fn train_network(mut args: impl Iterator<Item = String>) {
+    use engine::network;
+
+    let mut games = 200_000;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => {
+                games = args
+                    .next()
+                    .and_then(|games| games.parse().ok())
+                    .unwrap_or(0)
+            }
+            "--output" => output = args.next().map(std::path::PathBuf::from),
+            _ => {}
+        }
+    }
+    if games == 0 {
+        eprintln!("--games needs a number of games");
+        std::process::exit(1);
+    }
+    let fail = |err: std::io::Error| -> ! {
+        eprintln!("Couldn't write the network: {}", err);
+        std::process::exit(1);
+    };
+    let output = match output.map_or_else(network::file, Ok) {
+        Ok(output) => output,
+        Err(err) => fail(err),
+    };
+
+    eprintln!("Training over {games} games");
+    let mut rng = rand::thread_rng();
+    let trained = network::train(games, &mut rng, |played| {
+        eprintln!("[{played}/{games}]");
+    });
+    if let Some(folder) = output.parent() {
+        if let Err(err) = std::fs::create_dir_all(folder) {
+            fail(err);
+        }
+    }
+    if let Err(err) = trained.save(&output) {
+        fail(err);
+    }
+    eprintln!("Written to {}", output.display());
+
+    let (wins, draws, losses) = network::against_engine(&trained, &mut game::Computer::default());
+    eprintln!("Against the engine: {wins} wins, {draws} draws, {losses} losses");
 }
 
 fn main() -> iced::Result {
-    App::run(Settings::default())
+    STARTED.get_or_init(std::time::Instant::now);
+    // Read ahead of the other options, so the log has the startup in it.
+    let json_log = match std::env::args()
+        .skip_while(|arg| arg != "--log-json")
+        .nth(1)
+    {
+        Some(path) => json_log::Layer::open(path.as_ref()).unwrap_or_else(|err| {
+            eprintln!("Couldn't open the JSON log {path}: {err}");
+            json_log::Layer::default()
+        }),
+        None => json_log::Layer::default(),
+    };
+    {
+        use tracing_subscriber::{filter, layer::SubscriberExt, util::SubscriberInitExt, Layer};
+        tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(std::io::stderr)
+                    .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+                    .with_filter(filter::LevelFilter::INFO),
+            )
+            .with(perf::Layer.with_filter(filter::filter_fn(|metadata| {
+                metadata.is_span() && perf::SPANS.contains(&metadata.name())
+            })))
+            .with(json_log.with_filter(filter::filter_fn(json_log::wanted)))
+            .init();
+    }
+
+    let missing = std::env::args()
+        .skip(1)
+        .find_map(|arg| Some((features::missing_for(&arg)?, arg)));
+    if let Some((feature, arg)) = missing {
+        eprintln!("{arg} needs the `{feature}` feature, which this build left out");
+        std::process::exit(1);
+    }
+
+    let mut args = std::env::args().skip(1).peekable();
+    if args.next_if_eq("regression").is_some() {
+        std::process::exit(if regression::run() { 0 } else { 1 });
+    }
+    #[cfg(feature = "tablebases")]
+    if args.next_if_eq("tablebase").is_some() {
+        generate_tablebase(args);
+        return Ok(());
+    }
+    if args.next_if_eq("tune").is_some() {
+        tune::run(args);
+        return Ok(());
+    }
+    #[cfg(feature = "rl")]
+    if args.next_if_eq("train").is_some() {
+        train_network(args);
+        return Ok(());
+    }
+
+    let span = tracing::info_span!("startup.flags").entered();
+    let mut flags = Flags::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--twitch" => flags.twitch_channel = args.next(),
+            "--record" => flags.record = args.next().map(Into::into),
+            "--replay" => flags.replay = args.next().map(Into::into),
+            "--speed" => {
+                flags.replay_speed = args
+                    .next()
+                    .and_then(|speed| speed.parse().ok())
+                    .filter(|speed| recorder::SPEEDS.contains(speed))
+            }
+            #[cfg(feature = "render")]
+            "--render" => flags.render = args.next().map(Into::into),
+            #[cfg(feature = "render")]
+            "--scale" => {
+                flags.scale = args
+                    .next()
+                    .and_then(|scale| scale.parse().ok())
+                    .filter(|scale| render::SCALES.contains(scale))
+            }
+            "--analysis" => flags.analysis = args.next().map(Into::into),
+            "--calibration" => flags.calibration = true,
+            "--engine" => flags.engine = args.next().map(Into::into),
+            "--engine-timeout" => {
+                flags.engine_timeout = args
+                    .next()
+                    .and_then(|seconds| seconds.parse().ok())
+                    .and_then(|seconds| std::time::Duration::try_from_secs_f32(seconds).ok())
+            }
+            // Opened before the logs started.
+            "--log-json" => {
+                args.next();
+            }
+            _ => {}
+        }
+    }
+    span.exit();
+
+    #[cfg(feature = "render")]
+    if let Some(output) = flags.render.take() {
+        render_headless(flags, output);
+        return Ok(());
+    }
+    App::run(Settings {
+        // Closing goes through `Message::CloseRequested` to save the game first.
+        exit_on_close_request: false,
+        ..Settings::with_flags(flags)
+    })
 }
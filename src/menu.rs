@@ -0,0 +1,107 @@
+//! The menu bar, gathering the [`Command`]s of the app by topic.
+
+use iced::{
+    widget::{button, column, horizontal_space, row, text},
+    Element, Length,
+};
+
+use crate::{
+    command::{Command, Keymap},
+    i18n::I18n,
+    ui::{
+        components::{button_style, panel},
+        focus::focusable,
+        tokens::{SPACE_S, TEXT_SMALL},
+    },
+    Message,
+};
+
+/// One of the menus of the bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Menu {
+    File,
+    Game,
+    View,
+}
+
+impl Menu {
+    pub const ALL: [Menu; 3] = [Menu::File, Menu::Game, Menu::View];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Menu::File => "menu-file",
+            Menu::Game => "menu-game",
+            Menu::View => "menu-view",
+        }
+    }
+
+    pub fn commands(self) -> &'static [Command] {
+        match self {
+            Menu::File => &[
+                Command::NewGame,
+                Command::OpenReplay,
+                Command::Export,
+                Command::Feedback,
+            ],
+            Menu::Game => &[Command::Undo, Command::Redo, Command::Hint, Command::Resign],
+            Menu::View => &[
+                Command::Theme,
+                Command::ZoomIn,
+                Command::ZoomOut,
+                Command::ZoomReset,
+                Command::MiniMode,
+            ],
+        }
+    }
+}
+
+/// The menu bar with the `open` menu unfolded below it. Commands which can't run right now,
+/// as told by `enabled`, are greyed out.
+pub fn bar<'a>(
+    open: Option<Menu>,
+    enabled: impl Fn(Command) -> bool,
+    keymap: &Keymap,
+    i18n: &I18n,
+) -> Element<'a, Message> {
+    let titles = row(Menu::ALL
+        .into_iter()
+        .map(|menu| {
+            let style = if open == Some(menu) {
+                button_style(iced::theme::Button::Secondary)
+            } else {
+                button_style(iced::theme::Button::Text)
+            };
+            // Clicking the open menu again folds it.
+            let toggle = (open != Some(menu)).then_some(menu);
+            focusable(
+                button(text(i18n.tr(menu.label())))
+                    .style(style)
+                    .on_press(Message::Menu(toggle))
+                    .padding([4, 12]),
+            )
+        })
+        .collect());
+    let Some(menu) = open else {
+        return titles.into();
+    };
+
+    let items = column(
+        menu.commands()
+            .iter()
+            .map(|&command| {
+                let mut item = button(row![
+                    text(i18n.tr(command.label())),
+                    horizontal_space(Length::Fill),
+                    text(keymap.shortcut(command).unwrap_or_default()).size(TEXT_SMALL)
+                ])
+                .style(button_style(iced::theme::Button::Text))
+                .width(240);
+                if enabled(command) {
+                    item = item.on_press(Message::Command(command));
+                }
+                focusable(item)
+            })
+            .collect(),
+    );
+    column![titles, panel(items).padding(SPACE_S)].into()
+}
@@ -0,0 +1,107 @@
+//! The screen of the Order and Chaos variant, with the mark to place picked above the board.
+
+use iced::{
+    widget::{button, column, radio, row, text},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    game::{
+        order_chaos::{OrderChaos, MARKS},
+        Entity, GameState, MoveError,
+    },
+    i18n::I18n,
+    player::Players,
+    ui::{
+        components::{button_style, panel, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_L, SPACE_M, SPACE_S, TEXT_TITLE},
+    },
+    Message,
+};
+
+const CELL: u16 = 40;
+
+pub fn view<'a>(
+    game: &'a OrderChaos,
+    refused: Option<MoveError>,
+    players: &Players,
+    i18n: &I18n,
+) -> Element<'a, Message> {
+    let turn = game.state() == GameState::Playing(Entity::Human);
+    let board = column(
+        game.board()
+            .rows()
+            .enumerate()
+            .map(|(x, cells)| {
+                row(cells
+                    .iter()
+                    .enumerate()
+                    .map(|(y, &entity)| {
+                        let mut cell = button(
+                            text(entity.as_str())
+                                .width(Length::Fill)
+                                .horizontal_alignment(iced::alignment::Horizontal::Center),
+                        )
+                        .style(button_style(iced::theme::Button::Text))
+                        .width(CELL)
+                        .height(CELL);
+                        if turn && entity == Entity::Empty {
+                            cell = cell.on_press(Message::OrderChaosPlaced((x, y)));
+                        }
+                        focusable(cell)
+                    })
+                    .collect())
+                .spacing(SPACE_S)
+                .into()
+            })
+            .collect(),
+    )
+    .spacing(SPACE_S);
+
+    let mark = MARKS
+        .into_iter()
+        .fold(row![text(i18n.tr("order-chaos-mark"))], |picker, mark| {
+            picker.push(focusable(radio(
+                mark.as_str(),
+                mark,
+                Some(game.mark()),
+                Message::OrderChaosMark,
+            )))
+        });
+
+    let role = if game.order() == Entity::Human {
+        "order-chaos-order"
+    } else {
+        "order-chaos-chaos"
+    };
+    let status = match game.state() {
+        GameState::Win(Entity::Human) => i18n.tr_args(
+            "result-win",
+            [("winner", players.addressed(Entity::Human).into())],
+        ),
+        GameState::Win(_) => i18n.tr_args(
+            "result-win",
+            [("winner", i18n.tr("player-computer").into())],
+        ),
+        _ => match refused {
+            Some(err) => i18n.tr(err.label()),
+            None => i18n.tr(role),
+        },
+    };
+    column![
+        text(i18n.tr("order-chaos")).size(TEXT_TITLE),
+        mark.align_items(Alignment::Center).spacing(SPACE_L),
+        panel(board).padding(SPACE_S),
+        text(status),
+        row![
+            focusable(primary(i18n.tr("reset")).on_press(Message::OrderChaosReset)),
+            focusable(secondary(i18n.tr("order-chaos-swap")).on_press(Message::OrderChaosSwap)),
+            focusable(secondary(i18n.tr("back")).on_press(Message::ToggleOrderChaos))
+        ]
+        .spacing(SPACE_M)
+    ]
+    .align_items(Alignment::Center)
+    .spacing(SPACE_M)
+    .into()
+}
@@ -0,0 +1,19 @@
+use iced::{widget::container, Color, Theme};
+
+/// Pure green background which streaming software can key out.
+pub const CHROMA_KEY: Color = Color::from_rgb(0.0, 1.0, 0.0);
+
+/// Container style used by the overlay mode, meant to be captured by OBS and friends.
+pub struct ChromaKey;
+
+impl container::StyleSheet for ChromaKey {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            text_color: Some(Color::WHITE),
+            background: Some(CHROMA_KEY.into()),
+            ..Default::default()
+        }
+    }
+}
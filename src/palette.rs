@@ -0,0 +1,136 @@
+//! The command palette: every [`Command`] of the app, filtered by typing part of its name.
+
+use std::cmp::Reverse;
+
+use iced::{
+    widget::{button, column, horizontal_space, row, text, text_input},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    command::{Command, Keymap},
+    i18n::I18n,
+    ui::{
+        components::{button_style, panel},
+        focus::focusable,
+        tokens::{SPACE_M, TEXT_SMALL},
+    },
+    Message,
+};
+
+/// The text input of the palette, focused when it opens.
+pub fn input() -> text_input::Id {
+    text_input::Id::new("palette")
+}
+
+/// How well `query` matches `label`: its characters have to appear in order, each one scoring
+/// more when it follows the previous one or starts a word. `None` when they don't all appear.
+fn score(query: &str, label: &str) -> Option<u32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = next + label[next..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if found > 0 && found == next {
+            score += 2;
+        }
+        if found == 0 || !label[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        next = found + 1;
+    }
+    Some(score)
+}
+
+#[derive(Debug, Default)]
+pub struct Palette {
+    pub query: String,
+    /// Index of the highlighted command among the matches, run by pressing Enter.
+    selected: usize,
+}
+
+impl Palette {
+    /// The commands whose translated name matches the query, the best matches first.
+    pub fn matches(&self, i18n: &I18n) -> Vec<Command> {
+        let mut matches: Vec<_> = Command::all()
+            .filter(|&command| command != Command::Palette)
+            .filter_map(|command| Some((command, score(&self.query, &i18n.tr(command.label()))?)))
+            .collect();
+        // Sorting is stable, so equal matches keep the order of the registry.
+        matches.sort_by_key(|&(_, score)| Reverse(score));
+        matches.into_iter().map(|(command, _)| command).collect()
+    }
+
+    pub fn search(&mut self, query: String) {
+        self.query = query;
+        self.selected = 0;
+    }
+
+    /// Moves the highlight `by` matches down, up when negative, wrapping around.
+    pub fn select(&mut self, by: isize, i18n: &I18n) {
+        let count = self.matches(i18n).len() as isize;
+        if count > 0 {
+            self.selected = (self.selected as isize + by).rem_euclid(count) as usize;
+        }
+    }
+
+    /// The highlighted command.
+    pub fn selected(&self, i18n: &I18n) -> Option<Command> {
+        self.matches(i18n).get(self.selected).copied()
+    }
+
+    /// Commands which can't run right now, as told by `enabled`, are greyed out.
+    pub fn view<'a>(
+        &self,
+        enabled: impl Fn(Command) -> bool,
+        keymap: &Keymap,
+        i18n: &I18n,
+    ) -> Element<'a, Message> {
+        let matches = self.matches(i18n);
+        let list: Element<'a, Message> = if matches.is_empty() {
+            text(i18n.tr("palette-empty")).into()
+        } else {
+            column(
+                matches
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, command)| {
+                        let style = if i == self.selected {
+                            button_style(iced::theme::Button::Primary)
+                        } else {
+                            button_style(iced::theme::Button::Text)
+                        };
+                        let mut item = button(row![
+                            text(i18n.tr(command.label())),
+                            horizontal_space(Length::Fill),
+                            text(keymap.shortcut(command).unwrap_or_default()).size(TEXT_SMALL)
+                        ])
+                        .style(style)
+                        .width(Length::Fill);
+                        if enabled(command) {
+                            item = item.on_press(Message::Command(command));
+                        }
+                        focusable(item)
+                    })
+                    .collect(),
+            )
+            .into()
+        };
+        panel(
+            column![
+                text_input(&i18n.tr("palette-placeholder"), &self.query)
+                    .id(input())
+                    .on_input(Message::PaletteQuery)
+                    .on_submit(Message::PaletteRun)
+                    .padding(SPACE_M),
+                list
+            ]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M),
+        )
+        .padding(SPACE_M)
+        .width(400)
+        .into()
+    }
+}
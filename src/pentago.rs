@@ -0,0 +1,119 @@
+//! The screen of the Pentago variant, the four quadrants drawn apart with the buttons turning
+//! each of them below it.
+
+use iced::{
+    widget::{button, column, row, text},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    game::{
+        pentago::{Pentago, Quadrant, Rotation},
+        Entity, GameState, MoveError,
+    },
+    i18n::I18n,
+    player::Players,
+    ui::{
+        components::{button_style, panel, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_L, SPACE_M, SPACE_S, TEXT_SMALL, TEXT_TITLE},
+    },
+    Message,
+};
+
+const CELL: u16 = 40;
+
+pub fn view<'a>(
+    game: &'a Pentago,
+    refused: Option<MoveError>,
+    players: &Players,
+    i18n: &I18n,
+) -> Element<'a, Message> {
+    let turn = game.state() == GameState::Playing(Entity::Human);
+    let placing = turn && game.placed().is_none();
+    let quadrant = |quadrant: Quadrant| -> Element<'a, Message> {
+        let cells = column(
+            (0..3)
+                .map(|x| {
+                    row((0..3)
+                        .map(|y| {
+                            let (x, y) = quadrant.cell(x, y);
+                            let entity = game.board()[x][y];
+                            let style = if game.placed() == Some((x, y)) {
+                                button_style(iced::theme::Button::Secondary)
+                            } else {
+                                button_style(iced::theme::Button::Text)
+                            };
+                            let mut cell = button(
+                                text(entity.as_str())
+                                    .width(Length::Fill)
+                                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                            )
+                            .style(style)
+                            .width(CELL)
+                            .height(CELL);
+                            if placing && entity == Entity::Empty {
+                                cell = cell.on_press(Message::PentagoPlaced((x, y)));
+                            }
+                            focusable(cell)
+                        })
+                        .collect())
+                    .spacing(SPACE_S)
+                    .into()
+                })
+                .collect(),
+        )
+        .spacing(SPACE_S);
+        let rotate = |clockwise: bool, label: &str| {
+            let mut rotate = button(text(i18n.tr(label)).size(TEXT_SMALL));
+            if turn && game.placed().is_some() {
+                rotate = rotate.on_press(Message::PentagoRotated(Rotation {
+                    quadrant,
+                    clockwise,
+                }));
+            }
+            focusable(rotate)
+        };
+        column![
+            panel(cells).padding(SPACE_S),
+            row![
+                rotate(false, "pentago-turn-left"),
+                rotate(true, "pentago-turn-right")
+            ]
+            .spacing(SPACE_S)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_S)
+        .into()
+    };
+
+    let status = match game.state() {
+        GameState::Draw => i18n.tr("result-draw"),
+        GameState::Win(winner) => {
+            i18n.tr_args("result-win", [("winner", players.addressed(winner).into())])
+        }
+        _ => match refused {
+            Some(err) => i18n.tr(err.label()),
+            None if game.placed().is_some() => i18n.tr("pentago-rotate"),
+            None => i18n.tr("pentago-rules"),
+        },
+    };
+    let [top_left, top_right, bottom_left, bottom_right] = Quadrant::ALL;
+    column![
+        text(i18n.tr("pentago")).size(TEXT_TITLE),
+        column![
+            row![quadrant(top_left), quadrant(top_right)].spacing(SPACE_L),
+            row![quadrant(bottom_left), quadrant(bottom_right)].spacing(SPACE_L)
+        ]
+        .spacing(SPACE_L),
+        text(status),
+        row![
+            focusable(primary(i18n.tr("reset")).on_press(Message::PentagoReset)),
+            focusable(secondary(i18n.tr("back")).on_press(Message::TogglePentago))
+        ]
+        .spacing(SPACE_M)
+    ]
+    .align_items(Alignment::Center)
+    .spacing(SPACE_M)
+    .into()
+}
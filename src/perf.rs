@@ -0,0 +1,128 @@
+//! The performance HUD: frames per second, and how long updates, views and engine searches
+//! take, measured from the spans the app traces rather than by timing the code itself.
+
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tracing::{span, Subscriber};
+use tracing_subscriber::{layer::Context, registry::LookupSpan};
+
+/// How many of the last samples of each span the averages cover.
+const SAMPLES: usize = 120;
+
+/// Spans the HUD times.
+pub const SPANS: [&str; 3] = ["update", "view", "engine.search"];
+
+static TIMINGS: Mutex<Timings> = Mutex::new(Timings {
+    update: Samples::new(),
+    view: Samples::new(),
+    search: None,
+});
+
+/// Durations of the last spans of one name.
+#[derive(Debug, Clone)]
+pub struct Samples(VecDeque<Duration>);
+
+impl Samples {
+    const fn new() -> Samples {
+        Samples(VecDeque::new())
+    }
+
+    fn push(&mut self, duration: Duration) {
+        if self.0.len() == SAMPLES {
+            self.0.pop_front();
+        }
+        self.0.push_back(duration);
+    }
+
+    pub fn average(&self) -> Duration {
+        match self.0.len() {
+            0 => Duration::ZERO,
+            len => self.0.iter().sum::<Duration>() / len as u32,
+        }
+    }
+
+    pub fn max(&self) -> Duration {
+        self.0.iter().max().copied().unwrap_or_default()
+    }
+}
+
+/// What the HUD shows besides the frame rate.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    pub update: Samples,
+    pub view: Samples,
+    /// How long the engine took to pick its last move, `None` before its first one.
+    pub search: Option<Duration>,
+}
+
+/// The timings measured so far.
+pub fn timings() -> Timings {
+    TIMINGS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// When a span opened, kept in its extensions until it closes.
+struct Opened(Instant);
+
+/// Records the duration of the spans in [`SPANS`] as they close, from whichever thread.
+pub struct Layer;
+
+impl<S> tracing_subscriber::Layer<S> for Layer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Opened(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(elapsed) = span
+            .extensions()
+            .get::<Opened>()
+            .map(|Opened(at)| at.elapsed())
+        else {
+            return;
+        };
+        let mut timings = TIMINGS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match span.name() {
+            "update" => timings.update.push(elapsed),
+            "view" => timings.view.push(elapsed),
+            "engine.search" => timings.search = Some(elapsed),
+            _ => {}
+        }
+    }
+}
+
+/// Counts the frames drawn during the last second.
+#[derive(Debug, Default)]
+pub struct Frames(VecDeque<Instant>);
+
+impl Frames {
+    pub fn tick(&mut self, now: Instant) {
+        while self
+            .0
+            .front()
+            .is_some_and(|&at| now.saturating_duration_since(at) > Duration::from_secs(1))
+        {
+            self.0.pop_front();
+        }
+        self.0.push_back(now);
+    }
+
+    pub fn per_second(&self) -> usize {
+        self.0.len()
+    }
+}
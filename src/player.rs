@@ -0,0 +1,183 @@
+//! Who plays: the name, mark and color each side of the game shows in the app.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{game::Entity, i18n::I18n, ladder::Bot};
+
+/// Marks the human can play with, the computer taking X, or O when the human took X.
+pub const GLYPHS: [char; 8] = ['O', 'X', '@', '#', '*', '+', '&', '%'];
+
+/// Longest name the settings keep, in characters.
+pub const NAME_LENGTH: usize = 20;
+
+/// Colors of the marks of the human.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Color {
+    /// The color of the text of the theme.
+    #[default]
+    Plain,
+    Red,
+    Orange,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl Color {
+    pub const ALL: [Color; 6] = [
+        Color::Plain,
+        Color::Red,
+        Color::Orange,
+        Color::Green,
+        Color::Blue,
+        Color::Purple,
+    ];
+
+    /// Id of the translated name of the color.
+    pub fn label(self) -> &'static str {
+        match self {
+            Color::Plain => "color-plain",
+            Color::Red => "color-red",
+            Color::Orange => "color-orange",
+            Color::Green => "color-green",
+            Color::Blue => "color-blue",
+            Color::Purple => "color-purple",
+        }
+    }
+
+    pub fn iced(self) -> Option<iced::Color> {
+        let (r, g, b) = match self {
+            Color::Plain => return None,
+            Color::Red => (0xe5, 0x48, 0x4d),
+            Color::Orange => (0xf0, 0x8c, 0x2e),
+            Color::Green => (0x46, 0xa7, 0x58),
+            Color::Blue => (0x3e, 0x8e, 0xd0),
+            Color::Purple => (0x9b, 0x6b, 0xd6),
+        };
+        Some(iced::Color::from_rgb8(r, g, b))
+    }
+}
+
+/// How the human shows up, as picked in the settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// Addressed as "you" when empty.
+    pub name: String,
+    pub glyph: char,
+    pub color: Color,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile {
+            name: String::new(),
+            glyph: 'O',
+            color: Color::default(),
+        }
+    }
+}
+
+impl Profile {
+    /// Brings values edited by hand back within what the settings screen allows.
+    pub fn validate(mut self) -> Profile {
+        self.name = self.name.trim().chars().take(NAME_LENGTH).collect();
+        if !GLYPHS.contains(&self.glyph) {
+            self.glyph = Profile::default().glyph;
+        }
+        self
+    }
+}
+
+/// A side of the game as the app shows it.
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub name: String,
+    pub glyph: char,
+    /// `None` for the color of the text.
+    pub color: Option<iced::Color>,
+    /// The human without a name, addressed as "you" rather than by name.
+    pub you: bool,
+}
+
+/// Both sides of a game.
+#[derive(Debug, Clone)]
+pub struct Players {
+    pub human: Player,
+    pub computer: Player,
+}
+
+impl Players {
+    /// The human as `profile` describes them, against `opponent`, or the engine for `None`.
+    pub fn new(profile: &Profile, opponent: Option<Bot>, i18n: &I18n) -> Players {
+        let you = profile.name.is_empty();
+        Players {
+            human: Player {
+                name: if you {
+                    i18n.tr("player-you")
+                } else {
+                    profile.name.clone()
+                },
+                glyph: profile.glyph,
+                color: profile.color.iced(),
+                you,
+            },
+            computer: Player {
+                name: i18n.tr(opponent.map_or("player-computer", Bot::name)),
+                glyph: if profile.glyph == 'X' { 'O' } else { 'X' },
+                color: None,
+                you: false,
+            },
+        }
+    }
+
+    /// The sides of a game the computer plays against itself, `x` playing the computer's
+    /// side and `o` the human's, `None` standing for the engine.
+    pub fn spectated(x: Option<Bot>, o: Option<Bot>, i18n: &I18n) -> Players {
+        let player = |contender: Option<Bot>, glyph: char| Player {
+            name: i18n.tr_args(
+                "spectate-side",
+                [
+                    (
+                        "name",
+                        i18n.tr(contender.map_or("bot-engine", Bot::name)).into(),
+                    ),
+                    ("mark", glyph.to_string().into()),
+                ],
+            ),
+            glyph,
+            color: None,
+            you: false,
+        };
+        Players {
+            human: player(o, 'O'),
+            computer: player(x, 'X'),
+        }
+    }
+
+    pub fn get(&self, entity: Entity) -> &Player {
+        match entity {
+            Entity::Human => &self.human,
+            _ => &self.computer,
+        }
+    }
+
+    /// What a cell holding `entity` shows.
+    pub fn mark(&self, entity: Entity) -> String {
+        match entity {
+            Entity::Empty => entity.as_str().to_string(),
+            _ => self.get(entity).glyph.to_string(),
+        }
+    }
+
+    /// The player as the results address them: "you" for the human without a name, to pick
+    /// the form of the sentence, their name otherwise.
+    pub fn addressed(&self, entity: Entity) -> String {
+        let player = self.get(entity);
+        if player.you {
+            "you".to_string()
+        } else {
+            player.name.clone()
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::command::Command;
+
+/// Seeds the engine while recording and replaying: recordings only hold the inputs, so the
+/// engine has to pick the same moves among equally good ones again.
+pub const SEED: u64 = 0;
+
+/// A user input which can be recorded and replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Click(usize, usize),
+    /// A command run from anywhere, recorded by its id.
+    Command(Command),
+}
+
+impl Input {
+    fn parse(s: &str) -> Option<Input> {
+        let mut parts = s.split_whitespace();
+        let input = match parts.next()? {
+            "click" => Input::Click(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+            "command" => Input::Command(Command::from_id(parts.next()?)?),
+            // Recordings made before the commands were.
+            "reset" => Input::Command(Command::NewGame),
+            _ => return None,
+        };
+        parts.next().is_none().then_some(input)
+    }
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::Click(x, y) => write!(f, "click {} {}", x, y),
+            Input::Command(command) => write!(f, "command {}", command.id()),
+        }
+    }
+}
+
+/// Writes every input of the session to a file, one `<millis> <input>` line each.
+pub struct Recorder {
+    file: File,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Recorder> {
+        Ok(Recorder {
+            file: File::create(path)?,
+            started: Instant::now(),
+        })
+    }
+
+    pub fn record(&mut self, input: Input) -> io::Result<()> {
+        writeln!(
+            self.file,
+            "{} {}",
+            self.started.elapsed().as_millis(),
+            input
+        )
+    }
+}
+
+/// How much faster than recorded a replay can go, slower below 1.
+pub const SPEEDS: std::ops::RangeInclusive<f32> = 0.01..=100.0;
+
+/// Feeds back the inputs of a recording at their original pace, scaled by `speed`.
+pub struct Player {
+    inputs: Vec<(Duration, Input)>,
+    next: usize,
+    started: Instant,
+    speed: f32,
+}
+
+impl Player {
+    pub fn load(path: impl AsRef<Path>, speed: f32) -> io::Result<Player> {
+        let mut inputs = vec![];
+        for (number, line) in fs::read_to_string(path)?.lines().enumerate() {
+            let parsed = line
+                .split_once(' ')
+                .and_then(|(millis, input)| Some((millis.parse().ok()?, Input::parse(input)?)));
+            match parsed {
+                Some((millis, input)) => inputs.push((Duration::from_millis(millis), input)),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid input at line {}: {:?}", number + 1, line),
+                    ))
+                }
+            }
+        }
+        Ok(Player {
+            inputs,
+            next: 0,
+            started: Instant::now(),
+            speed: speed.clamp(*SPEEDS.start(), *SPEEDS.end()),
+        })
+    }
+
+    /// Returns the inputs whose time has come since the last call.
+    pub fn due(&mut self) -> Vec<Input> {
+        let elapsed = self.started.elapsed().as_secs_f32() * self.speed;
+        // Out of range only for a speed which isn't a number, everything is due then.
+        let elapsed = Duration::try_from_secs_f32(elapsed).unwrap_or(Duration::MAX);
+        let due = self.inputs[self.next..]
+            .iter()
+            .take_while(|(at, _)| *at <= elapsed)
+            .map(|(_, input)| *input)
+            .collect::<Vec<_>>();
+        self.next += due.len();
+        due
+    }
+
+    /// Returns every remaining input at once, ignoring their timestamps. Only rendering a
+    /// replay without a window needs that.
+    #[cfg(feature = "render")]
+    pub fn drain(&mut self) -> Vec<Input> {
+        let rest = self.inputs[self.next..]
+            .iter()
+            .map(|(_, input)| *input)
+            .collect();
+        self.next = self.inputs.len();
+        rest
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.inputs.len()
+    }
+}
@@ -0,0 +1,402 @@
+//! The `regression` command: the engine's regression suite. Positions with known results, the
+//! ones of `regression/positions.toml` and every position of the classic 3×3 game solved again
+//! by a plain search, are put to each engine backend. The command fails when a backend does
+//! worse than expected of it, so a release can be held back on it, and each backend has a test
+//! of its own so `cargo test` fails the same way.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    game::{has_line, parse_cell, Action, Board, Computer, Entity, Rules},
+    settings::Difficulty,
+};
+
+const POSITIONS: &str = include_str!("../regression/positions.toml");
+
+/// Percent of the positions a difficulty tier may give away besides its mistake rate, as its
+/// mistakes are rolled at random.
+const TOLERANCE: u32 = 10;
+
+/// Wrong positions printed for each backend, the first ones found.
+const SHOWN: usize = 5;
+
+#[derive(Deserialize)]
+struct Suite {
+    positions: Vec<Entry>,
+}
+
+/// A position as written in the file.
+#[derive(Deserialize)]
+struct Entry {
+    name: String,
+    board: Vec<String>,
+    to_move: String,
+    #[serde(default)]
+    rules: Rules,
+    winner: String,
+    best: Vec<String>,
+}
+
+/// A position and what best play gets out of it.
+struct Case {
+    name: String,
+    board: Board,
+    player: Entity,
+    rules: Rules,
+    /// `None` for a draw.
+    winner: Option<Entity>,
+    /// Every move keeping the result of `winner`.
+    best: Vec<(usize, usize)>,
+}
+
+/// An engine configuration put to the suite, and what's expected of it.
+struct Backend {
+    name: String,
+    engine: Computer,
+    /// The table is kept from one position to the next, as it is during a game, rather than
+    /// cleared before each.
+    warm: bool,
+    /// Percent of the positions whose move has to keep the best result, at least.
+    expected: u32,
+    /// Whether the result of each position has to be right too. The difficulty tiers only
+    /// play worse moves, they search the same.
+    values: bool,
+}
+
+/// Runs the suite, printing how each backend did. Returns whether every one of them met its
+/// expectations.
+pub fn run() -> bool {
+    let cases = match cases() {
+        Ok(cases) => cases,
+        Err(err) => {
+            eprintln!("Couldn't read the positions of the suite: {err}");
+            return false;
+        }
+    };
+    eprintln!("{} positions", cases.len());
+    let mut passed = true;
+    for mut backend in backends() {
+        passed &= check(&mut backend, &cases);
+    }
+    passed
+}
+
+fn backends() -> Vec<Backend> {
+    let mut backends = vec![search(false), search(true)];
+    #[cfg(feature = "tablebases")]
+    backends.push(tablebases());
+    backends.extend(Difficulty::ALL.map(tier));
+    backends
+}
+
+/// The search, its table cleared before each position unless `warm`.
+fn search(warm: bool) -> Backend {
+    Backend {
+        name: if warm { "search, table kept" } else { "search" }.to_string(),
+        engine: Computer::default(),
+        warm,
+        expected: 100,
+        values: true,
+    }
+}
+
+/// The search looking the 3×3 tablebase up.
+#[cfg(feature = "tablebases")]
+fn tablebases() -> Backend {
+    let mut engine = Computer::default();
+    engine.set_tablebases(vec![crate::game::tablebase::Tablebase::generate(
+        3,
+        3,
+        |_, _, _| {},
+    )]);
+    Backend {
+        name: "tablebases".to_string(),
+        engine,
+        warm: false,
+        expected: 100,
+        values: true,
+    }
+}
+
+/// The search making the mistakes of `difficulty`.
+fn tier(difficulty: Difficulty) -> Backend {
+    let rate = difficulty.mistake_rate();
+    let mut engine = Computer::default();
+    engine.set_mistake_rate(rate);
+    Backend {
+        name: format!("{difficulty:?} ({rate}% mistakes)"),
+        engine,
+        warm: true,
+        expected: match rate {
+            0 => 100,
+            rate => (100 - rate).saturating_sub(TOLERANCE),
+        },
+        values: false,
+    }
+}
+
+/// Puts every case to `backend`, printing the outcome and the first positions it got wrong.
+fn check(backend: &mut Backend, cases: &[Case]) -> bool {
+    let started = Instant::now();
+    let mut rules = None;
+    let mut wrong_values = vec![];
+    let mut wrong_moves = vec![];
+    for case in cases {
+        if !backend.warm || rules != Some(case.rules) {
+            backend.engine.new_game(case.rules);
+            rules = Some(case.rules);
+        }
+        if backend.values {
+            let assessment = backend.engine.assess(case.board, case.player);
+            if assessment.winner != case.winner {
+                wrong_values.push(case);
+            }
+        }
+        let keeps = match backend.engine.best_action(case.board, case.player) {
+            Some(Action::Place(x, y)) => case.best.contains(&(x, y)),
+            _ => false,
+        };
+        if !keeps {
+            wrong_moves.push(case);
+        }
+    }
+
+    let right = (cases.len() - wrong_moves.len()) * 100 / cases.len().max(1);
+    let passed = wrong_values.is_empty() && right >= backend.expected as usize;
+    let values = if backend.values {
+        format!("{} wrong results, ", wrong_values.len())
+    } else {
+        String::new()
+    };
+    eprintln!(
+        "{}: {values}best moves in {right}% of the positions, {}% expected, in {:.1} s: {}",
+        backend.name,
+        backend.expected,
+        started.elapsed().as_secs_f32(),
+        if passed { "ok" } else { "FAILED" }
+    );
+    if !passed {
+        for case in wrong_values.iter().take(SHOWN) {
+            eprintln!("  wrong result: {}", describe(case));
+        }
+        if right < backend.expected as usize {
+            for case in wrong_moves.iter().take(SHOWN) {
+                eprintln!("  gave the result away: {}", describe(case));
+            }
+        }
+    }
+    passed
+}
+
+/// The position of `case`, its rows separated by slashes, and its name.
+fn describe(case: &Case) -> String {
+    let rows: Vec<String> = case
+        .board
+        .rows()
+        .map(|row| row.iter().map(|entity| entity.as_str()).collect())
+        .collect();
+    format!(
+        "{} {}, {} to move under {:?}",
+        case.name,
+        rows.join("/"),
+        case.player.as_str(),
+        case.rules
+    )
+}
+
+/// The cases of the file, then every position of the classic 3×3 game either side opened.
+fn cases() -> Result<Vec<Case>, String> {
+    let suite: Suite = toml::from_str(POSITIONS).map_err(|err| err.to_string())?;
+    let mut cases = suite
+        .positions
+        .into_iter()
+        .map(Case::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // In the order they're first reached, the same from one run to the next, as the table
+    // kept by some backends depends on it.
+    let mut positions = vec![];
+    let mut seen = HashSet::new();
+    for opener in [Entity::Human, Entity::Computer] {
+        reachable(&mut Board::default(), opener, &mut seen, &mut positions);
+    }
+    let mut solved = HashMap::new();
+    for (mut board, player) in positions {
+        let winner = solve(&mut board, player, &mut solved);
+        let best = board
+            .playable()
+            .filter(|&(x, y)| {
+                let mut child = board;
+                child[x][y] = player;
+                outcome(&mut child, player, &mut solved) == winner
+            })
+            .collect();
+        cases.push(Case {
+            name: "solved".to_string(),
+            board,
+            player,
+            rules: Rules::Classic,
+            winner,
+            best,
+        });
+    }
+    Ok(cases)
+}
+
+impl Case {
+    fn parse(entry: Entry) -> Result<Case, String> {
+        let side = |name: &str| match name {
+            "X" => Ok(Some(Entity::Computer)),
+            "O" => Ok(Some(Entity::Human)),
+            "draw" => Ok(None),
+            _ => Err(format!("{}: unknown side {name:?}", entry.name)),
+        };
+        let player =
+            side(&entry.to_move)?.ok_or_else(|| format!("{}: a draw can't move", entry.name))?;
+        let winner = side(&entry.winner)?;
+        let mut board = Board::new(entry.board.len());
+        for (x, row) in entry.board.iter().enumerate() {
+            let cells: Vec<char> = row.chars().collect();
+            if cells.len() != board.size() {
+                return Err(format!("{}: rows need {} cells", entry.name, board.size()));
+            }
+            for (y, cell) in cells.into_iter().enumerate() {
+                board[x][y] = match cell {
+                    'X' => Entity::Computer,
+                    'O' => Entity::Human,
+                    '.' => Entity::Empty,
+                    _ => return Err(format!("{}: invalid cell {cell:?}", entry.name)),
+                };
+            }
+        }
+        let best = entry
+            .best
+            .iter()
+            .map(|name| {
+                parse_cell(name)
+                    .filter(|&(x, y)| x < board.size() && y < board.size())
+                    .ok_or_else(|| format!("{}: invalid cell {name:?}", entry.name))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Case {
+            name: entry.name,
+            board,
+            player,
+            rules: entry.rules,
+            winner,
+            best,
+        })
+    }
+}
+
+/// Adds every unfinished position reachable from `board` with `player` to move to
+/// `positions`, unless already `seen`.
+fn reachable(
+    board: &mut Board,
+    player: Entity,
+    seen: &mut HashSet<(Board, Entity)>,
+    positions: &mut Vec<(Board, Entity)>,
+) {
+    if has_line(board, !player) || board.is_full() || !seen.insert((*board, player)) {
+        return;
+    }
+    positions.push((*board, player));
+    for (x, y) in board.playable().collect::<Vec<_>>() {
+        board[x][y] = player;
+        reachable(board, !player, seen, positions);
+        board[x][y] = Entity::Empty;
+    }
+}
+
+/// Who wins `board` under the classic rules with `player` to move, `None` for a draw. A plain
+/// search of every move, sharing nothing with the engine so it can check it.
+fn solve(
+    board: &mut Board,
+    player: Entity,
+    solved: &mut HashMap<(Board, Entity), Option<Entity>>,
+) -> Option<Entity> {
+    if let Some(&winner) = solved.get(&(*board, player)) {
+        return winner;
+    }
+    let outcomes: Vec<_> = board
+        .playable()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|(x, y)| {
+            board[x][y] = player;
+            let outcome = outcome(board, player, solved);
+            board[x][y] = Entity::Empty;
+            outcome
+        })
+        .collect();
+    let winner = if outcomes.contains(&Some(player)) {
+        Some(player)
+    } else if outcomes.contains(&None) {
+        None
+    } else {
+        Some(!player)
+    };
+    solved.insert((*board, player), winner);
+    winner
+}
+
+/// Who wins once `player` made the move leading to `board`.
+fn outcome(
+    board: &mut Board,
+    player: Entity,
+    solved: &mut HashMap<(Board, Entity), Option<Entity>>,
+) -> Option<Entity> {
+    if has_line(board, player) {
+        Some(player)
+    } else if board.is_full() {
+        None
+    } else {
+        solve(board, !player, solved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passes(mut backend: Backend) -> bool {
+        let cases = cases().expect("the positions of the suite are valid");
+        check(&mut backend, &cases)
+    }
+
+    #[test]
+    fn search_from_scratch() {
+        assert!(passes(search(false)));
+    }
+
+    #[test]
+    fn search_with_table_kept() {
+        assert!(passes(search(true)));
+    }
+
+    #[cfg(feature = "tablebases")]
+    #[test]
+    fn tablebases_lookup() {
+        assert!(passes(tablebases()));
+    }
+
+    #[test]
+    fn easy() {
+        assert!(passes(tier(Difficulty::Easy)));
+    }
+
+    #[test]
+    fn medium() {
+        assert!(passes(tier(Difficulty::Medium)));
+    }
+
+    #[test]
+    fn hard() {
+        assert!(passes(tier(Difficulty::Hard)));
+    }
+}
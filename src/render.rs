@@ -0,0 +1,391 @@
+//! Headless renderer which draws the app screens into an image without a window or a GPU.
+//!
+//! Everything is rasterized on the CPU with a bundled font at the scale asked for rather than
+//! the one of the screen, so the same screen always produces the same pixels on every machine.
+//! Shapes and strokes are laid out in points, then scaled as a whole, so images rendered for
+//! HiDPI screens stay sharp rather than being enlarged afterwards.
+
+use ab_glyph::{Font, FontRef, OutlineCurve, ScaleFont};
+use tiny_skia::{Color, FillRule, LineCap, Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+use crate::{
+    game::{Board, Entity},
+    overlay,
+    ui::tokens::{Colors, RADIUS_S, SPACE_M, TEXT_BODY, TEXT_TITLE},
+};
+
+const FONT: &[u8] = include_bytes!("../assets/Lato-Regular.ttf");
+
+const CELL: f32 = 96.0;
+const SPACING: f32 = SPACE_M as f32;
+const PADDING: f32 = 32.0;
+const TEXT_SIZE: f32 = TEXT_BODY as f32;
+const LINE_HEIGHT: f32 = 30.0;
+const TITLE_SIZE: f32 = TEXT_TITLE as f32;
+const TITLE_HEIGHT: f32 = 56.0;
+
+/// Pixels per point images can be rendered at.
+pub const SCALES: std::ops::RangeInclusive<f32> = 1.0..=4.0;
+
+/// The colors of a screen, taken from the colors of the theme used by the app.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub background: iced::Color,
+    pub cell: iced::Color,
+    pub text: iced::Color,
+    pub human: iced::Color,
+    pub computer: iced::Color,
+}
+
+impl Palette {
+    pub fn new(colors: &Colors) -> Palette {
+        Palette {
+            background: colors.background,
+            cell: colors.surface(),
+            text: colors.text,
+            human: colors.human,
+            computer: colors.computer,
+        }
+    }
+
+    /// Dark cells on the background the overlay keys out.
+    pub fn chroma_key() -> Palette {
+        Palette {
+            background: overlay::CHROMA_KEY,
+            cell: Colors::DARK.background,
+            text: iced::Color::WHITE,
+            ..Palette::new(&Colors::DARK)
+        }
+    }
+}
+
+fn color(color: iced::Color) -> Color {
+    let [r, g, b, a] = color.into_rgba8();
+    Color::from_rgba8(r, g, b, a)
+}
+
+/// Draws the board followed by one line of text for each entry of `lines`, with `scale`
+/// pixels per point.
+pub fn screen(board: &Board, lines: &[String], palette: Palette, scale: f32) -> Pixmap {
+    draw(None, board, lines, palette, scale)
+}
+
+/// Draws a card to share a result: `title` in large letters above the board, followed by
+/// one line of text for each entry of `lines`, with `scale` pixels per point.
+pub fn card(title: &str, board: &Board, lines: &[String], palette: Palette, scale: f32) -> Pixmap {
+    draw(Some(title), board, lines, palette, scale)
+}
+
+fn draw(
+    title: Option<&str>,
+    board: &Board,
+    lines: &[String],
+    palette: Palette,
+    scale: f32,
+) -> Pixmap {
+    let scale = scale.clamp(*SCALES.start(), *SCALES.end());
+    let transform = Transform::from_scale(scale, scale);
+    let cells = board.size() as f32;
+    let board_size = cells * CELL + (cells - 1.0) * SPACING;
+    let header = if title.is_some() { TITLE_HEIGHT } else { 0.0 };
+    let width = PADDING * 2.0 + board_size;
+    let height = PADDING * 2.0 + header + board_size + lines.len() as f32 * LINE_HEIGHT;
+    let mut pixmap = Pixmap::new(
+        (width * scale).ceil() as u32,
+        (height * scale).ceil() as u32,
+    )
+    .expect("the screen is never empty");
+    pixmap.fill(color(palette.background));
+
+    for (x, row) in board.rows().enumerate() {
+        for (y, entity) in row.iter().enumerate() {
+            let left = PADDING + y as f32 * (CELL + SPACING);
+            let top = PADDING + header + x as f32 * (CELL + SPACING);
+            draw_cell(&mut pixmap, transform, *entity, left, top, palette);
+        }
+    }
+
+    let font = FontRef::try_from_slice(FONT).expect("the bundled font is valid");
+    if let Some(title) = title {
+        let baseline = PADDING + TITLE_SIZE;
+        draw_text(
+            &mut pixmap,
+            transform,
+            &font,
+            title,
+            TITLE_SIZE,
+            baseline,
+            color(palette.text),
+        );
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let baseline = PADDING + header + board_size + (i as f32 + 1.0) * LINE_HEIGHT - 8.0;
+        draw_text(
+            &mut pixmap,
+            transform,
+            &font,
+            line,
+            TEXT_SIZE,
+            baseline,
+            color(palette.text),
+        );
+    }
+
+    pixmap
+}
+
+fn paint(fill: iced::Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(color(fill));
+    paint.anti_alias = true;
+    paint
+}
+
+/// Draws a cell at `left` and `top` in points, `transform` scaling them to pixels.
+fn draw_cell(
+    pixmap: &mut Pixmap,
+    transform: Transform,
+    entity: Entity,
+    left: f32,
+    top: f32,
+    palette: Palette,
+) {
+    if let Some(cell) = rounded_rect(left, top, CELL, RADIUS_S) {
+        pixmap.fill_path(
+            &cell,
+            &paint(palette.cell),
+            FillRule::Winding,
+            transform,
+            None,
+        );
+    }
+
+    let stroke = Stroke {
+        width: 8.0,
+        line_cap: LineCap::Round,
+        ..Default::default()
+    };
+    let inset = CELL * 0.25;
+    let path = match entity {
+        Entity::Empty => return,
+        Entity::Human => {
+            PathBuilder::from_circle(left + CELL / 2.0, top + CELL / 2.0, CELL / 2.0 - inset)
+        }
+        Entity::Computer => {
+            let mut builder = PathBuilder::new();
+            builder.move_to(left + inset, top + inset);
+            builder.line_to(left + CELL - inset, top + CELL - inset);
+            builder.move_to(left + CELL - inset, top + inset);
+            builder.line_to(left + inset, top + CELL - inset);
+            builder.finish()
+        }
+    };
+    let mark = match entity {
+        Entity::Human => palette.human,
+        _ => palette.computer,
+    };
+    if let Some(path) = path {
+        pixmap.stroke_path(&path, &paint(mark), &stroke, transform, None);
+    }
+}
+
+/// A square `size` wide with its top left corner at `left` and `top`, its corners rounded
+/// like the cells of the app.
+fn rounded_rect(left: f32, top: f32, size: f32, radius: f32) -> Option<tiny_skia::Path> {
+    let (right, bottom) = (left + size, top + size);
+    let mut builder = PathBuilder::new();
+    builder.move_to(left + radius, top);
+    builder.line_to(right - radius, top);
+    builder.quad_to(right, top, right, top + radius);
+    builder.line_to(right, bottom - radius);
+    builder.quad_to(right, bottom, right - radius, bottom);
+    builder.line_to(left + radius, bottom);
+    builder.quad_to(left, bottom, left, bottom - radius);
+    builder.line_to(left, top + radius);
+    builder.quad_to(left, top, left + radius, top);
+    builder.close();
+    builder.finish()
+}
+
+/// Draws a single centered line of text by filling the glyph outlines, laid out in points
+/// then scaled to pixels by `transform`.
+fn draw_text(
+    pixmap: &mut Pixmap,
+    transform: Transform,
+    font: &FontRef,
+    text: &str,
+    size: f32,
+    baseline: f32,
+    color: Color,
+) {
+    let mut fill = Paint::default();
+    fill.set_color(color);
+    fill.anti_alias = true;
+    let scaled = font.as_scaled(size);
+    let factor = scaled.scale_factor();
+
+    let glyphs: Vec<_> = text.chars().map(|c| font.glyph_id(c)).collect();
+    let width: f32 = glyphs.iter().map(|id| scaled.h_advance(*id)).sum();
+
+    let mut builder = PathBuilder::new();
+    let mut pen = (pixmap.width() as f32 / transform.sx - width) / 2.0;
+    let mut previous = None;
+    for id in glyphs {
+        if let Some(previous) = previous {
+            pen += scaled.kern(previous, id);
+        }
+        if let Some(outline) = font.outline(id) {
+            let point = |p: ab_glyph::Point| {
+                (
+                    pen + p.x * factor.horizontal,
+                    baseline - p.y * factor.vertical,
+                )
+            };
+            let mut last = None;
+            for curve in outline.curves {
+                let (start, end) = match curve {
+                    OutlineCurve::Line(a, b)
+                    | OutlineCurve::Quad(a, _, b)
+                    | OutlineCurve::Cubic(a, _, _, b) => (a, b),
+                };
+                if last != Some(start) {
+                    let (x, y) = point(start);
+                    builder.move_to(x, y);
+                }
+                match curve {
+                    OutlineCurve::Line(_, b) => {
+                        let (x, y) = point(b);
+                        builder.line_to(x, y);
+                    }
+                    OutlineCurve::Quad(_, c, b) => {
+                        let ((cx, cy), (x, y)) = (point(c), point(b));
+                        builder.quad_to(cx, cy, x, y);
+                    }
+                    OutlineCurve::Cubic(_, c1, c2, b) => {
+                        let ((c1x, c1y), (c2x, c2y), (x, y)) = (point(c1), point(c2), point(b));
+                        builder.cubic_to(c1x, c1y, c2x, c2y, x, y);
+                    }
+                }
+                last = Some(end);
+            }
+        }
+        pen += scaled.h_advance(id);
+        previous = Some(id);
+    }
+
+    if let Some(path) = builder.finish() {
+        pixmap.fill_path(&path, &fill, FillRule::Winding, transform, None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Golden images: each screen is rendered from a fixed position and compared with the
+    //! reference PNG of `golden/`, which `UPDATE_GOLDEN=1 cargo test` writes again after a
+    //! change of the look. Channels may differ by [`TOLERANCE`], rasterizing curves with other
+    //! floating point instructions moving anti-aliased edges by a shade at most.
+
+    use std::path::PathBuf;
+
+    use super::*;
+
+    const TOLERANCE: u8 = 2;
+
+    fn position(size: usize) -> Board {
+        let mut board = Board::new(size);
+        board[0][0] = Entity::Computer;
+        board[1][1] = Entity::Human;
+        board[0][2] = Entity::Computer;
+        board[2][0] = Entity::Human;
+        board
+    }
+
+    fn finished() -> Board {
+        let mut board = position(3);
+        board[0][1] = Entity::Computer;
+        board
+    }
+
+    fn check(name: &str, pixmap: Pixmap) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("golden")
+            .join(format!("{name}.png"));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            pixmap.save_png(&path).expect("the golden image is written");
+            return;
+        }
+        let golden = Pixmap::load_png(&path)
+            .unwrap_or_else(|err| panic!("no golden image at {}: {err}", path.display()));
+        assert_eq!(
+            (pixmap.width(), pixmap.height()),
+            (golden.width(), golden.height()),
+            "{name} changed size"
+        );
+        let differing = pixmap
+            .data()
+            .iter()
+            .zip(golden.data())
+            .filter(|(a, b)| a.abs_diff(**b) > TOLERANCE)
+            .count();
+        assert_eq!(differing, 0, "{name} differs from its golden image");
+    }
+
+    #[test]
+    fn game() {
+        let lines = ["Your turn".to_string(), "You 1 - 0 Computer".to_string()];
+        check(
+            "game",
+            screen(&position(3), &lines, Palette::new(&Colors::LIGHT), 1.0),
+        );
+    }
+
+    #[test]
+    fn game_dark() {
+        let lines = ["Your turn".to_string()];
+        check(
+            "game-dark",
+            screen(&position(3), &lines, Palette::new(&Colors::DARK), 1.0),
+        );
+    }
+
+    #[test]
+    fn overlay() {
+        let lines = ["You 1 - 0 Computer".to_string()];
+        check(
+            "overlay",
+            screen(&position(3), &lines, Palette::chroma_key(), 1.0),
+        );
+    }
+
+    #[test]
+    fn bigger_board() {
+        check(
+            "board-5x5",
+            screen(&position(5), &[], Palette::new(&Colors::LIGHT), 1.0),
+        );
+    }
+
+    #[test]
+    fn result_card() {
+        let lines = ["You vs Engine".to_string(), "1 January 2024".to_string()];
+        check(
+            "card",
+            card(
+                "Computer won!",
+                &finished(),
+                &lines,
+                Palette::new(&Colors::LIGHT),
+                1.0,
+            ),
+        );
+    }
+
+    #[test]
+    fn hidpi() {
+        let lines = ["Your turn".to_string()];
+        check(
+            "game-2x",
+            screen(&position(3), &lines, Palette::new(&Colors::LIGHT), 2.0),
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{Event, Subscriber},
+    game::{Board, Entity, Game, GameState, Rules, MAX_SIZE},
+    ladder::Bot,
+    storage,
+};
+
+/// Results of the games played since the app was opened.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Score {
+    pub human: u32,
+    pub computer: u32,
+    pub draws: u32,
+}
+
+impl Score {
+    /// Counts the result of a finished game, ongoing games are ignored.
+    pub fn record(&mut self, state: &GameState) {
+        match state.winner() {
+            Some(Entity::Human) => self.human += 1,
+            Some(_) => self.computer += 1,
+            None if *state == GameState::Draw => self.draws += 1,
+            None => {}
+        }
+    }
+}
+
+/// How many of the human's moves kept the best result the position allowed.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Accuracy {
+    pub best: u32,
+    pub moves: u32,
+}
+
+impl Accuracy {
+    pub fn judge(&mut self, best: bool) {
+        self.moves += 1;
+        self.best += u32::from(best);
+    }
+
+    /// Share of best moves, `None` before any move was judged.
+    pub fn percent(&self) -> Option<u32> {
+        (self.moves > 0).then(|| self.best * 100 / self.moves)
+    }
+}
+
+impl std::ops::Add for Accuracy {
+    type Output = Accuracy;
+
+    fn add(self, other: Accuracy) -> Accuracy {
+        Accuracy {
+            best: self.best + other.best,
+            moves: self.moves + other.moves,
+        }
+    }
+}
+
+/// A finished game of the session.
+#[derive(Debug, Clone)]
+pub struct Record {
+    /// The bot played against, `None` for the engine.
+    pub opponent: Option<Bot>,
+    pub result: GameState,
+    pub moves: usize,
+    pub duration: Duration,
+    pub accuracy: Accuracy,
+}
+
+/// The game being played, until it's recorded in the history.
+#[derive(Debug, Clone)]
+pub struct Ongoing {
+    started: Instant,
+    /// Whether each move of the game kept the best result, by ply, `None` for the moves
+    /// which aren't judged, e.g. those of the computer.
+    judged: Vec<Option<bool>>,
+}
+
+impl Default for Ongoing {
+    fn default() -> Self {
+        Ongoing {
+            started: Instant::now(),
+            judged: vec![],
+        }
+    }
+}
+
+impl Ongoing {
+    /// Judges the move played at `ply`, forgetting the moves judged from there on, which were
+    /// taken back.
+    pub fn judge(&mut self, ply: usize, best: bool) {
+        self.judged.resize(ply, None);
+        self.judged.push(Some(best));
+    }
+
+    /// Forgets the judgements of the moves taken back, `plies` moves being left.
+    pub fn take_back(&mut self, plies: usize) {
+        self.judged.truncate(plies);
+    }
+
+    /// Accuracy of the human over the moves left on the board.
+    pub fn accuracy(&self) -> Accuracy {
+        let mut accuracy = Accuracy::default();
+        for &best in self.judged.iter().flatten() {
+            accuracy.judge(best);
+        }
+        accuracy
+    }
+}
+
+/// Everything played since the app was opened.
+#[derive(Default)]
+pub struct Session {
+    pub score: Score,
+    pub history: Vec<Record>,
+    pub current: Ongoing,
+}
+
+impl Session {
+    pub fn new_game(&mut self) {
+        self.current = Ongoing::default();
+    }
+
+    /// Consecutive wins of the human among the first `games` of the history, counting back
+    /// from the last of them.
+    pub fn streak(&self, games: usize) -> u32 {
+        self.history[..games]
+            .iter()
+            .rev()
+            .take_while(|record| record.result.winner() == Some(Entity::Human))
+            .count() as u32
+    }
+
+    /// Accuracy of the human over every finished game.
+    pub fn total_accuracy(&self) -> Accuracy {
+        self.history
+            .iter()
+            .fold(Accuracy::default(), |total, record| total + record.accuracy)
+    }
+
+    /// Records the result of `game` against `opponent` once it's finished.
+    fn finish(&mut self, game: &Game, opponent: Option<Bot>) {
+        let result = game.state();
+        if !result.is_finished() {
+            return;
+        }
+        self.score.record(&result);
+        self.history.push(Record {
+            opponent,
+            result,
+            moves: game.moves().len(),
+            duration: self.current.started.elapsed(),
+            accuracy: self.current.accuracy(),
+        });
+    }
+}
+
+impl Subscriber for Session {
+    fn notify(&mut self, event: &Event) {
+        let Event::Finished { game, opponent } = event;
+        self.finish(game, *opponent);
+    }
+}
+
+/// A game left in progress when the app was closed.
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Checkpoints saved before boards could be resized are 3×3.
+    #[serde(default = "Checkpoint::classic_size")]
+    size: usize,
+    /// Marks in a row needed to win, a whole side when missing.
+    #[serde(default)]
+    line: Option<usize>,
+    #[serde(default)]
+    gravity: bool,
+    #[serde(default)]
+    rules: Rules,
+    /// Marks each side places before sliding them.
+    #[serde(default)]
+    pieces: Option<usize>,
+    /// Whether the computer played the first move rather than the human.
+    #[serde(default)]
+    computer_started: bool,
+    /// Marks on the board before the first move, from a scrambled start.
+    #[serde(default)]
+    start: Vec<Placed>,
+    moves: Vec<Played>,
+    /// Time left to each side, missing in untimed games.
+    #[serde(default)]
+    clock: Option<Remaining>,
+}
+
+/// Time left to each side of a timed game, in milliseconds.
+#[derive(Serialize, Deserialize)]
+struct Remaining {
+    human: u64,
+    computer: u64,
+}
+
+/// A mark placed before the first move.
+#[derive(Serialize, Deserialize)]
+struct Placed {
+    x: usize,
+    y: usize,
+    computer: bool,
+}
+
+/// A saved move, `from` being the cell the mark slid from.
+#[derive(Serialize, Deserialize)]
+struct Played {
+    x: usize,
+    y: usize,
+    from: Option<(usize, usize)>,
+}
+
+impl Checkpoint {
+    pub const FILE: &'static str = "checkpoint";
+
+    pub fn new(game: &Game) -> Checkpoint {
+        Checkpoint {
+            size: game.board().size(),
+            line: Some(game.board().line()),
+            gravity: game.board().has_gravity(),
+            rules: game.rules(),
+            pieces: game.board().pieces(),
+            computer_started: game.starter() == Entity::Computer,
+            start: game
+                .initial()
+                .cells()
+                .filter(|&(_, entity)| entity != Entity::Empty)
+                .map(|((x, y), entity)| Placed {
+                    x,
+                    y,
+                    computer: entity == Entity::Computer,
+                })
+                .collect(),
+            moves: game
+                .moves()
+                .iter()
+                .map(|played| Played {
+                    x: played.x,
+                    y: played.y,
+                    from: played.from,
+                })
+                .collect(),
+            clock: game
+                .remaining(Entity::Human)
+                .zip(game.remaining(Entity::Computer))
+                .map(|(human, computer)| Remaining {
+                    human: human.as_millis() as u64,
+                    computer: computer.as_millis() as u64,
+                }),
+        }
+    }
+
+    fn classic_size() -> usize {
+        3
+    }
+
+    /// Plays the saved moves again on a new game.
+    pub fn restore(&self) -> Game {
+        let size = self.size.clamp(3, MAX_SIZE);
+        let board = Board::new(size)
+            .with_line(self.line.unwrap_or(size))
+            .with_gravity(self.gravity)
+            .with_pieces(self.pieces);
+        let mut start = board;
+        for placed in self
+            .start
+            .iter()
+            .filter(|placed| placed.x < size && placed.y < size)
+        {
+            start[placed.x][placed.y] = if placed.computer {
+                Entity::Computer
+            } else {
+                Entity::Human
+            };
+        }
+        let starter = if self.computer_started {
+            Entity::Computer
+        } else {
+            Entity::Human
+        };
+        let mut game = Game::new(board, self.rules).with_starter(starter);
+        if let Some(clock) = &self.clock {
+            // Replaying the moves takes next to no time off.
+            game = game.with_remaining(
+                Duration::from_millis(clock.human),
+                Duration::from_millis(clock.computer),
+            );
+        }
+        game.start((!self.start.is_empty()).then_some(start));
+        for played in &self.moves {
+            let result = match played.from {
+                Some(from) => game.slide(from, (played.x, played.y)),
+                None => game.try_play(played.x, played.y),
+            };
+            // Whatever follows a move which can't be played doesn't make sense either.
+            if let Err(err) = result {
+                tracing::warn!("stopped restoring the game at a refused move: {:?}", err);
+                break;
+            }
+        }
+        game
+    }
+}
+
+impl storage::Versioned for Checkpoint {
+    const VERSION: u32 = 3;
+
+    /// Version 2 saves moves as tables to record slides, they used to be `[x, y]` pairs.
+    /// Version 3 adds the clock, older checkpoints restore untimed as they were saved.
+    fn migrate(from: u32, table: &mut toml::Table) -> Result<(), String> {
+        if from != 1 {
+            return Ok(());
+        }
+        let Some(toml::Value::Array(moves)) = table.get_mut("moves") else {
+            return Ok(());
+        };
+        for played in moves.iter_mut() {
+            let toml::Value::Array(pair) = played else {
+                return Err(format!("invalid move {}", played));
+            };
+            let [x, y] = pair.as_slice() else {
+                return Err(format!("invalid move {}", played));
+            };
+            let mut cell = toml::Table::new();
+            cell.insert("x".to_string(), x.clone());
+            cell.insert("y".to_string(), y.clone());
+            *played = toml::Value::Table(cell);
+        }
+        Ok(())
+    }
+}
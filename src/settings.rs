@@ -0,0 +1,680 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use iced::{
+    futures::{channel::mpsc, SinkExt, StreamExt},
+    subscription,
+    widget::{column, pick_list, radio, row, text, text_input},
+    Alignment, Element, Subscription,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    autosave::Autosave,
+    features,
+    game::{Board, Entity, Game, Rules, DEFAULT_TABLE_MEMORY},
+    i18n::{I18n, Language},
+    player::{self, Profile},
+    storage,
+    ui::{
+        components::{destructive, labeled_slider, modal, primary, secondary, toggle},
+        focus::focusable,
+        tokens::{Colors, SPACE_L, SPACE_M, TEXT_HEADING, TEXT_TITLE},
+    },
+    Message,
+};
+
+/// Number of cells on each side of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BoardSize(pub usize);
+
+impl Default for BoardSize {
+    fn default() -> Self {
+        BoardSize(3)
+    }
+}
+
+impl BoardSize {
+    pub const ALL: [BoardSize; 4] = [BoardSize(3), BoardSize(4), BoardSize(5), BoardSize(6)];
+}
+
+impl std::fmt::Display for BoardSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{0}\u{d7}{0}", self.0)
+    }
+}
+
+/// What the app keeps on disk besides the settings and the saved replays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Privacy {
+    /// Offers to save the game in progress when closing the app.
+    pub checkpoints: bool,
+    /// Remembers the ladder climbed and the lessons completed.
+    pub progress: bool,
+}
+
+impl Default for Privacy {
+    fn default() -> Self {
+        Privacy {
+            checkpoints: true,
+            progress: true,
+        }
+    }
+}
+
+/// The choices of marks in a row needed to win, `None` standing for a whole side.
+const LINE_LENGTHS: [Option<usize>; 4] = [None, Some(3), Some(4), Some(5)];
+
+/// Bounds of the zoom, in percent of the normal size.
+pub const ZOOM_RANGE: std::ops::RangeInclusive<u32> = 50..=200;
+pub const ZOOM_STEP: u32 = 10;
+
+/// The colors of the app.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub fn colors(self) -> &'static Colors {
+        match self {
+            Theme::Dark => &Colors::DARK,
+            Theme::Light => &Colors::LIGHT,
+        }
+    }
+
+    pub fn iced(self) -> iced::Theme {
+        self.colors().theme()
+    }
+}
+
+/// Who plays first in the next game of a match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Starter {
+    /// Always the human.
+    Human,
+    /// Each side in turn.
+    #[default]
+    Alternate,
+    /// The loser of the previous game, each side in turn after a draw.
+    Loser,
+}
+
+impl Starter {
+    pub const ALL: [Starter; 3] = [Starter::Human, Starter::Alternate, Starter::Loser];
+
+    /// Id of the translated description of the option.
+    pub fn label(self) -> &'static str {
+        match self {
+            Starter::Human => "starter-human",
+            Starter::Alternate => "starter-alternate",
+            Starter::Loser => "starter-loser",
+        }
+    }
+
+    /// The side opening the game after `previous`. A game left before any move doesn't count,
+    /// its starter opens again.
+    pub fn next(self, previous: &Game) -> Entity {
+        let starter = previous.starter();
+        if self == Starter::Human {
+            return Entity::Human;
+        }
+        if previous.moves().is_empty() {
+            return starter;
+        }
+        match previous.state().winner() {
+            Some(winner) if self == Starter::Loser => !winner,
+            _ => !starter,
+        }
+    }
+}
+
+/// Presets of the computer's mistake rate, offered besides its slider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    /// Id of the translated name of the tier.
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty-easy",
+            Difficulty::Medium => "difficulty-medium",
+            Difficulty::Hard => "difficulty-hard",
+        }
+    }
+
+    /// Percent of its moves the computer gets wrong on purpose. Tuned with the calibration
+    /// screen, `--calibration`, against the win rates it reports.
+    pub fn mistake_rate(self) -> u32 {
+        match self {
+            Difficulty::Easy => 40,
+            Difficulty::Medium => 15,
+            Difficulty::Hard => 0,
+        }
+    }
+}
+
+/// The choices of minutes on the clock of each side, `None` standing for untimed games.
+const CLOCKS: [Option<u64>; 4] = [None, Some(1), Some(3), Some(5)];
+
+/// Bounds of the sliders of the seconds left when the clock warns.
+const LOW_TIME_RANGE: std::ops::RangeInclusive<u32> = 0..=60;
+
+/// Dwell times, in milliseconds, offered by the settings screen.
+const DWELL_TIME_RANGE: std::ops::RangeInclusive<u32> = 0..=3000;
+
+/// Pauses between the moves of a spectated game offered by its screen, in milliseconds.
+pub const SPECTATE_DELAY_RANGE: std::ops::RangeInclusive<u32> = 0..=3000;
+
+/// Bounds of the slider of the computer's mistake rate, in percent of its moves.
+const MISTAKE_RATE_RANGE: std::ops::RangeInclusive<u32> = 0..=100;
+
+/// Bounds of the engine memory slider, in KiB.
+const ENGINE_MEMORY_RANGE: std::ops::RangeInclusive<u32> = 64..=65536;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Memory the engine may use for its transposition table, in KiB.
+    pub engine_memory: u32,
+    /// Percent of its moves the computer plays worse than its best on purpose.
+    pub mistake_rate: u32,
+    /// Lets the engine search its replies while the human is thinking.
+    pub ponder: bool,
+    /// Shows the frame rate and how long updates, views and engine searches take, over the
+    /// window.
+    pub perf_hud: bool,
+    /// Asks for confirmation before a move which loses by force.
+    pub blunder_alarm: bool,
+    /// Offers to take a losing move back once the computer punished it.
+    pub practice: bool,
+    /// Explains each move of the human which gives away a better result, from the line the
+    /// engine expects after it.
+    pub tutor: bool,
+    pub language: Language,
+    /// Which finished games get their replay saved to the library.
+    pub autosave: Autosave,
+    /// Size of the board, from the next game on.
+    pub board_size: BoardSize,
+    /// Marks in a row needed to win, a whole side when `None`. From the next game on.
+    pub line_length: Option<usize>,
+    /// Marks fall to the bottom of their column, from the next game on.
+    pub gravity: bool,
+    /// Each side places as many marks as make a line, then slides them, from the next game on.
+    pub morris: bool,
+    /// Rules of the game, from the next game on.
+    pub rules: Rules,
+    /// Minutes each side has for the whole game, untimed when `None`. From the next game on.
+    pub clock: Option<u64>,
+    /// Seconds left to the human below which their clock turns red and beeps once.
+    pub low_time: u32,
+    /// Seconds left to the human below which their clock ticks every second.
+    pub ticking_time: u32,
+    /// Who opens each new game.
+    pub starter: Starter,
+    /// Starts new games with one or two random marks already placed, from the next game on.
+    pub scramble: bool,
+    /// The name, mark and color of the human.
+    pub player: Profile,
+    /// Silences every sound.
+    pub mute: bool,
+    /// Keeps highlights still rather than animated, e.g. a steady red clock instead of a
+    /// pulsing one.
+    pub reduce_motion: bool,
+    /// Draws the cheapest way, for old integrated GPUs and remote desktops: square corners,
+    /// nothing animated and no redraws besides the ones needed.
+    pub low_spec: bool,
+    /// Milliseconds the pointer stays on a cell of the board before clicking it, never when 0.
+    pub dwell_time: u32,
+    /// Milliseconds between the moves of a game the computer plays against itself.
+    pub spectate_delay: u32,
+    pub privacy: Privacy,
+    pub theme: Theme,
+    /// Size of the whole window content, in percent.
+    pub zoom: u32,
+    /// Keys bound to commands instead of their default, by command id. An empty key unbinds it.
+    pub bindings: BTreeMap<String, String>,
+    /// Keys bringing the window to the front from any app, e.g. `Ctrl+Shift+T`, none when
+    /// empty. Pressed again while the window is in front, they switch the mini mode.
+    pub summon_key: String,
+    /// Program printing each phrase it hears on a line, with its arguments, listened to for
+    /// voice commands. None when empty.
+    pub voice_command: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            engine_memory: (DEFAULT_TABLE_MEMORY / 1024) as u32,
+            mistake_rate: 0,
+            ponder: false,
+            perf_hud: false,
+            blunder_alarm: false,
+            practice: false,
+            tutor: false,
+            language: Language::default(),
+            autosave: Autosave::default(),
+            board_size: BoardSize::default(),
+            line_length: None,
+            gravity: false,
+            morris: false,
+            rules: Rules::default(),
+            clock: None,
+            low_time: 10,
+            ticking_time: 10,
+            starter: Starter::default(),
+            scramble: false,
+            player: Profile::default(),
+            mute: false,
+            reduce_motion: false,
+            low_spec: false,
+            dwell_time: 0,
+            spectate_delay: 800,
+            privacy: Privacy::default(),
+            theme: Theme::default(),
+            zoom: 100,
+            bindings: BTreeMap::new(),
+            summon_key: String::new(),
+            voice_command: String::new(),
+        }
+    }
+}
+
+impl Settings {
+    pub const FILE: &'static str = "settings";
+
+    /// Brings values edited by hand back within what the settings screen allows.
+    pub fn validate(mut self) -> Settings {
+        self.engine_memory = self
+            .engine_memory
+            .clamp(*ENGINE_MEMORY_RANGE.start(), *ENGINE_MEMORY_RANGE.end());
+        if !BoardSize::ALL.contains(&self.board_size) {
+            self.board_size = BoardSize::default();
+        }
+        if !LINE_LENGTHS.contains(&self.line_length) {
+            self.line_length = None;
+        }
+        if !CLOCKS.contains(&self.clock) {
+            self.clock = None;
+        }
+        self.mistake_rate = self.mistake_rate.min(*MISTAKE_RATE_RANGE.end());
+        self.low_time = self.low_time.min(*LOW_TIME_RANGE.end());
+        self.ticking_time = self.ticking_time.min(*LOW_TIME_RANGE.end());
+        self.dwell_time = self.dwell_time.min(*DWELL_TIME_RANGE.end());
+        self.spectate_delay = self.spectate_delay.min(*SPECTATE_DELAY_RANGE.end());
+        self.zoom = self.zoom.clamp(*ZOOM_RANGE.start(), *ZOOM_RANGE.end());
+        self.player = self.player.validate();
+        self.summon_key = self.summon_key.trim().to_string();
+        self.voice_command = self.voice_command.trim().to_string();
+        self
+    }
+
+    /// The empty board new games start from.
+    pub fn board(&self) -> Board {
+        let size = self.board_size.0;
+        let line = self.line_length.unwrap_or(size).min(size);
+        Board::new(size)
+            .with_line(line)
+            .with_gravity(self.gravity)
+            // Under Notakto nobody owns marks, so there are none to slide.
+            .with_pieces((self.morris && self.rules != Rules::Notakto).then_some(line))
+    }
+
+    /// A new game opened by `starter`, on the board and under the rules and clock picked.
+    pub fn game(&self, starter: Entity) -> Game {
+        let game = Game::new(self.board(), self.rules).with_starter(starter);
+        match self.clock {
+            Some(minutes) => game.with_clock(Duration::from_secs(minutes * 60)),
+            None => game,
+        }
+    }
+
+    pub fn engine_memory_bytes(&self) -> usize {
+        self.engine_memory as usize * 1024
+    }
+
+    pub fn view(&self, i18n: &I18n) -> Element<'_, Message> {
+        // Applied once submitted, a half typed combination isn't registered along the way.
+        let summon: Element<'_, Message> = if features::HOTKEY {
+            row![
+                text(i18n.tr("settings-summon-key")),
+                text_input("Ctrl+Shift+T", &self.summon_key)
+                    .on_input(Message::SummonKey)
+                    .on_submit(Message::ApplySummonKey)
+                    .width(200)
+            ]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M)
+            .into()
+        } else {
+            column![].into()
+        };
+        // Started once submitted too, rather than once per key typed.
+        let voice: Element<'_, Message> = if features::VOICE {
+            row![
+                text(i18n.tr("settings-voice-command")),
+                text_input("vosk-listen --lines", &self.voice_command)
+                    .on_input(Message::VoiceCommand)
+                    .on_submit(Message::ApplyVoiceCommand)
+                    .width(300)
+            ]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M)
+            .into()
+        } else {
+            column![].into()
+        };
+        // Only timed games have anything to warn about.
+        let low_time: Element<'_, Message> = match self.clock {
+            Some(_) => {
+                let mut sliders = row![labeled_slider(
+                    i18n.tr_args("settings-low-time", [("seconds", self.low_time.into())]),
+                    LOW_TIME_RANGE,
+                    self.low_time,
+                    1,
+                    Message::LowTime
+                )]
+                .spacing(SPACE_L);
+                if features::SOUND {
+                    sliders = sliders.push(labeled_slider(
+                        i18n.tr_args(
+                            "settings-ticking-time",
+                            [("seconds", self.ticking_time.into())],
+                        ),
+                        LOW_TIME_RANGE,
+                        self.ticking_time,
+                        1,
+                        Message::TickingTime,
+                    ));
+                }
+                sliders.into()
+            }
+            None => column![].into(),
+        };
+        let mute: Element<'_, Message> = if features::SOUND {
+            toggle(i18n.tr("settings-mute"), self.mute, Message::Mute)
+        } else {
+            column![].into()
+        };
+        column![
+            text(i18n.tr("settings")).size(TEXT_TITLE),
+            row![
+                text(i18n.tr("settings-language")),
+                focusable(pick_list(
+                    &Language::ALL[..],
+                    Some(i18n.language()),
+                    Message::Language
+                ))
+            ]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M),
+            row![
+                text(i18n.tr("settings-name")),
+                text_input(&i18n.tr("player-you"), &self.player.name)
+                    .on_input(Message::PlayerName)
+                    .width(200),
+                text(i18n.tr("settings-mark")),
+                focusable(pick_list(
+                    &player::GLYPHS[..],
+                    Some(self.player.glyph),
+                    Message::PlayerGlyph
+                ))
+            ]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M),
+            row(player::Color::ALL
+                .into_iter()
+                .map(|color| {
+                    focusable(radio(
+                        i18n.tr(color.label()),
+                        color,
+                        Some(self.player.color),
+                        Message::PlayerColor,
+                    ))
+                })
+                .collect())
+            .spacing(SPACE_L),
+            row![
+                text(i18n.tr("settings-board-size")),
+                focusable(pick_list(
+                    &BoardSize::ALL[..],
+                    Some(self.board_size),
+                    Message::BoardSize
+                ))
+            ]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M),
+            row(LINE_LENGTHS
+                .into_iter()
+                .map(|length| {
+                    let label = match length {
+                        Some(count) => i18n.tr_args("line-length", [("count", count.into())]),
+                        None => i18n.tr("line-length-side"),
+                    };
+                    focusable(radio(
+                        label,
+                        length,
+                        Some(self.line_length),
+                        Message::LineLength,
+                    ))
+                })
+                .collect())
+            .spacing(SPACE_L),
+            toggle(i18n.tr("settings-gravity"), self.gravity, Message::Gravity),
+            toggle(i18n.tr("settings-morris"), self.morris, Message::Morris),
+            row(Rules::ALL
+                .into_iter()
+                .map(|rules| {
+                    focusable(radio(
+                        i18n.tr(rules.label()),
+                        rules,
+                        Some(self.rules),
+                        Message::Rules,
+                    ))
+                })
+                .collect())
+            .spacing(SPACE_L),
+            row(CLOCKS
+                .into_iter()
+                .map(|clock| {
+                    let label = match clock {
+                        Some(minutes) => i18n.tr_args("clock-minutes", [("count", minutes.into())]),
+                        None => i18n.tr("clock-off"),
+                    };
+                    focusable(radio(label, clock, Some(self.clock), Message::Clock))
+                })
+                .collect())
+            .spacing(SPACE_L),
+            low_time,
+            row(Starter::ALL
+                .into_iter()
+                .map(|starter| {
+                    focusable(radio(
+                        i18n.tr(starter.label()),
+                        starter,
+                        Some(self.starter),
+                        Message::Starter,
+                    ))
+                })
+                .collect())
+            .spacing(SPACE_L),
+            row(Difficulty::ALL
+                .into_iter()
+                .map(|difficulty| {
+                    focusable(radio(
+                        i18n.tr(difficulty.label()),
+                        difficulty.mistake_rate(),
+                        Some(self.mistake_rate),
+                        Message::MistakeRate,
+                    ))
+                })
+                .collect())
+            .spacing(SPACE_L),
+            labeled_slider(
+                i18n.tr_args(
+                    "settings-mistake-rate",
+                    [("percent", i18n.percent(self.mistake_rate).into())]
+                ),
+                MISTAKE_RATE_RANGE,
+                self.mistake_rate,
+                5,
+                Message::MistakeRate
+            ),
+            toggle(
+                i18n.tr("settings-scramble"),
+                self.scramble,
+                Message::Scramble
+            ),
+            toggle(
+                i18n.tr("settings-blunder-alarm"),
+                self.blunder_alarm,
+                Message::BlunderAlarm
+            ),
+            toggle(
+                i18n.tr("settings-practice"),
+                self.practice,
+                Message::Practice
+            ),
+            toggle(i18n.tr("settings-tutor"), self.tutor, Message::Tutor),
+            mute,
+            toggle(
+                i18n.tr("settings-reduce-motion"),
+                self.reduce_motion,
+                Message::ReduceMotion
+            ),
+            toggle(
+                i18n.tr("settings-low-spec"),
+                self.low_spec,
+                Message::LowSpec
+            ),
+            labeled_slider(
+                match self.dwell_time {
+                    0 => i18n.tr("settings-dwell-off"),
+                    millis => i18n.tr_args(
+                        "settings-dwell-time",
+                        [(
+                            "seconds",
+                            i18n.decimal(f64::from(millis) / 1000.0, 1).into()
+                        )]
+                    ),
+                },
+                DWELL_TIME_RANGE,
+                self.dwell_time,
+                100,
+                Message::DwellTime
+            ),
+            focusable(primary(i18n.tr("privacy")).on_press(Message::TogglePrivacy)),
+            text(i18n.tr("settings-advanced")).size(TEXT_HEADING),
+            labeled_slider(
+                i18n.tr_args(
+                    "settings-engine-memory",
+                    [("kib", i18n.integer(self.engine_memory.into()).into())]
+                ),
+                ENGINE_MEMORY_RANGE,
+                self.engine_memory,
+                64,
+                Message::EngineMemory
+            ),
+            toggle(i18n.tr("settings-ponder"), self.ponder, Message::Ponder),
+            toggle(
+                i18n.tr("settings-perf-hud"),
+                self.perf_hud,
+                Message::PerfHud
+            ),
+            summon,
+            voice,
+            focusable(secondary(i18n.tr("back")).on_press(Message::ToggleSettings))
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M)
+        .into()
+    }
+}
+
+impl Privacy {
+    /// The privacy page, `wiping` while the deletion of every file waits for confirmation.
+    pub fn view<'a>(&self, autosave: Autosave, wiping: bool, i18n: &I18n) -> Element<'a, Message> {
+        let wipe: Element<'a, Message> = if wiping {
+            modal(
+                i18n.tr("privacy-wipe-prompt"),
+                [
+                    destructive(i18n.tr("privacy-wipe-confirm")).on_press(Message::ConfirmWipe),
+                    secondary(i18n.tr("privacy-wipe-cancel")).on_press(Message::CancelWipe),
+                ],
+            )
+        } else {
+            focusable(destructive(i18n.tr("privacy-wipe")).on_press(Message::WipeData))
+        };
+        column![
+            text(i18n.tr("privacy")).size(TEXT_TITLE),
+            text(i18n.tr("settings-autosave")),
+            row(Autosave::ALL
+                .into_iter()
+                .map(|option| {
+                    focusable(radio(
+                        i18n.tr(option.label()),
+                        option,
+                        Some(autosave),
+                        Message::Autosave,
+                    ))
+                })
+                .collect())
+            .spacing(SPACE_L),
+            toggle(
+                i18n.tr("privacy-checkpoints"),
+                self.checkpoints,
+                Message::Checkpoints
+            ),
+            toggle(
+                i18n.tr("privacy-progress"),
+                self.progress,
+                Message::SaveProgress
+            ),
+            wipe,
+            focusable(secondary(i18n.tr("back")).on_press(Message::TogglePrivacy))
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M)
+        .into()
+    }
+}
+
+impl storage::Versioned for Settings {
+    const VERSION: u32 = 1;
+}
+
+/// Reloads the settings whenever their file is edited outside the app.
+pub fn watch() -> Subscription<Result<Settings, String>> {
+    subscription::channel("settings", 100, |mut output| async move {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let _watcher = match storage::watch(Settings::FILE, move || {
+            let _ = sender.unbounded_send(());
+        }) {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                tracing::warn!("settings won't be reloaded when edited: {}", err);
+                None
+            }
+        };
+        loop {
+            if receiver.next().await.is_none() {
+                // The watcher couldn't start, nothing will ever change.
+                iced::futures::future::pending::<()>().await;
+            }
+            let settings = storage::reload::<Settings>(Settings::FILE)
+                .map(|settings| settings.unwrap_or_default().validate())
+                .map_err(|err| err.to_string());
+            let _ = output.send(settings).await;
+        }
+    })
+}
@@ -0,0 +1,49 @@
+//! Short cues played by the app, synthesized rather than read from files.
+
+use std::time::Duration;
+
+use rodio::{source::SineWave, OutputStream, OutputStreamHandle, Source};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    /// Each of the last seconds on the clock.
+    Tick,
+    /// The clock running low.
+    Warning,
+}
+
+impl Cue {
+    /// Pitch in hertz and length of the tone.
+    fn tone(self) -> (f32, Duration) {
+        match self {
+            Cue::Tick => (1200.0, Duration::from_millis(30)),
+            Cue::Warning => (880.0, Duration::from_millis(250)),
+        }
+    }
+}
+
+/// The default sound output of the system.
+pub struct Speaker {
+    // Every sound stops once the stream is dropped.
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+}
+
+impl Speaker {
+    pub fn new() -> Result<Speaker, String> {
+        let (stream, handle) = OutputStream::try_default().map_err(|err| err.to_string())?;
+        Ok(Speaker {
+            _stream: stream,
+            handle,
+        })
+    }
+
+    /// Starts playing `cue` without waiting for it to end.
+    pub fn play(&self, cue: Cue) {
+        let (pitch, length) = cue.tone();
+        let tone = SineWave::new(pitch).take_duration(length).amplify(0.2);
+        if let Err(err) = self.handle.play_raw(tone) {
+            tracing::warn!("couldn't play a sound: {}", err);
+        }
+    }
+}
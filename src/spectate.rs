@@ -0,0 +1,127 @@
+//! Watching the computer play itself: the engine and the bots of the ladder, two of them
+//! taking turns with a pause between moves so the game can be followed and their strategies
+//! compared.
+
+use iced::{
+    widget::{column, radio, row, text},
+    Alignment, Element,
+};
+
+use crate::{
+    features,
+    game::Entity,
+    i18n::I18n,
+    ladder::Bot,
+    settings::SPECTATE_DELAY_RANGE,
+    ui::{
+        components::{labeled_slider, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_L, SPACE_M, TEXT_TITLE},
+    },
+    Message,
+};
+
+/// Who can play a side, `None` standing for the engine.
+fn contenders() -> impl Iterator<Item = Option<Bot>> {
+    let network = features::RL.then_some(Bot::Network);
+    std::iter::once(None)
+        .chain(Bot::ALL.map(Some))
+        .chain(network.map(Some))
+}
+
+/// The two sides watched, X playing the computer's side and O the human's.
+#[derive(Default)]
+pub struct Spectate {
+    pub x: Option<Bot>,
+    pub o: Option<Bot>,
+    /// Whether the game is under way, rather than the sides being picked.
+    pub watching: bool,
+}
+
+/// Id of the translated name of `contender`.
+pub fn name(contender: Option<Bot>) -> &'static str {
+    contender.map_or("bot-engine", Bot::name)
+}
+
+impl Spectate {
+    /// Who plays the marks of `side`.
+    pub fn contender(&self, side: Entity) -> Option<Bot> {
+        match side {
+            Entity::Human => self.o,
+            _ => self.x,
+        }
+    }
+
+    /// The sides to pick before the game, or the game watched on `board` with `status` below
+    /// it, e.g. its result.
+    pub fn view<'a>(
+        &self,
+        board: Element<'a, Message>,
+        status: String,
+        delay: u32,
+        i18n: &I18n,
+    ) -> Element<'a, Message> {
+        let title = text(i18n.tr("spectate")).size(TEXT_TITLE);
+        if self.watching {
+            let matchup = i18n.tr_args(
+                "spectate-matchup",
+                [
+                    ("x", i18n.tr(name(self.x)).into()),
+                    ("o", i18n.tr(name(self.o)).into()),
+                ],
+            );
+            return column![
+                title,
+                text(matchup),
+                board,
+                text(status),
+                row![
+                    focusable(primary(i18n.tr("spectate-again")).on_press(Message::WatchSpectate)),
+                    focusable(
+                        secondary(i18n.tr("spectate-stop")).on_press(Message::ToggleSpectate)
+                    )
+                ]
+                .spacing(SPACE_M)
+            ]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M)
+            .into();
+        }
+        let side = |side: Entity, picked: Option<Bot>| {
+            row(contenders()
+                .map(|contender| {
+                    focusable(radio(
+                        i18n.tr(name(contender)),
+                        contender,
+                        Some(picked),
+                        move |contender| Message::SpectateSide(side, contender),
+                    ))
+                })
+                .collect())
+            .spacing(SPACE_L)
+        };
+        let seconds = i18n.decimal(f64::from(delay) / 1000.0, 1);
+        column![
+            title,
+            text(i18n.tr("spectate-x")),
+            side(Entity::Computer, self.x),
+            text(i18n.tr("spectate-o")),
+            side(Entity::Human, self.o),
+            labeled_slider(
+                i18n.tr_args("spectate-delay", [("seconds", seconds.into())]),
+                SPECTATE_DELAY_RANGE,
+                delay,
+                100,
+                Message::SpectateDelay
+            ),
+            row![
+                focusable(primary(i18n.tr("spectate-watch")).on_press(Message::WatchSpectate)),
+                focusable(secondary(i18n.tr("back")).on_press(Message::ToggleSpectate))
+            ]
+            .spacing(SPACE_M)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M)
+        .into()
+    }
+}
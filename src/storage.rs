@@ -0,0 +1,217 @@
+//! Files kept between sessions, stored as TOML in the user's config directory.
+//!
+//! Every file records the version of its format, files written by older releases are migrated
+//! when loaded. Files which can't be read are moved to the `quarantine` folder rather than
+//! being overwritten by the next save.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+fn dir() -> io::Result<PathBuf> {
+    dirs::config_dir()
+        .map(|dir| dir.join("tic-tac-toe-iced"))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))
+}
+
+fn path(name: &str) -> io::Result<PathBuf> {
+    Ok(dir()?.join(name).with_extension("toml"))
+}
+
+/// Key holding the version of the format in every file.
+const VERSION_KEY: &str = "version";
+
+/// A value saved between sessions whose format may change from one release to the next.
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Version of the format written by this release. Files saved before formats were
+    /// versioned count as version 0.
+    const VERSION: u32;
+
+    /// Upgrades `table`, saved with the format `from`, to the format `from + 1`.
+    fn migrate(from: u32, table: &mut toml::Table) -> Result<(), String> {
+        let _ = (from, table);
+        Ok(())
+    }
+}
+
+fn invalid(err: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+fn parse<T: Versioned>(contents: &str) -> Result<T, String> {
+    let mut table: toml::Table = contents
+        .parse()
+        .map_err(|err: toml::de::Error| err.to_string())?;
+    let version = match table.remove(VERSION_KEY) {
+        None => 0,
+        Some(toml::Value::Integer(version)) => {
+            u32::try_from(version).map_err(|err| err.to_string())?
+        }
+        Some(other) => return Err(format!("invalid version {}", other)),
+    };
+    if version > T::VERSION {
+        return Err(format!(
+            "saved by a newer release (version {}, this one reads up to {})",
+            version,
+            T::VERSION
+        ));
+    }
+    for from in version..T::VERSION {
+        T::migrate(from, &mut table)?;
+    }
+    table
+        .try_into()
+        .map_err(|err: toml::de::Error| err.to_string())
+}
+
+/// Reads the file called `name`, `None` when it was never saved.
+///
+/// A file which can't be read is moved to the quarantine folder, so it's still around for
+/// the user and isn't overwritten when saving again.
+pub fn load<T: Versioned>(name: &str) -> io::Result<Option<T>> {
+    match reload(name) {
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            let moved = quarantine(name)?;
+            Err(invalid(format!("{} (moved to {})", err, moved.display())))
+        }
+        loaded => loaded,
+    }
+}
+
+/// Reads the file called `name` like [`load`], but leaves it in place when it can't be read,
+/// e.g. because it's being edited.
+pub fn reload<T: Versioned>(name: &str) -> io::Result<Option<T>> {
+    let contents = match fs::read_to_string(path(name)?) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    parse(&contents).map(Some).map_err(invalid)
+}
+
+/// Moves the file called `name` out of the way, returns where it went.
+fn quarantine(name: &str) -> io::Result<PathBuf> {
+    let folder = folder("quarantine")?;
+    fs::create_dir_all(&folder)?;
+    let target = stamped(&folder, name, "toml");
+    fs::rename(path(name)?, &target)?;
+    tracing::warn!("moved the unreadable {} file to {}", name, target.display());
+    Ok(target)
+}
+
+pub fn save<T: Versioned>(name: &str, value: &T) -> io::Result<()> {
+    fs::create_dir_all(dir()?)?;
+    save_file(&path(name)?, value)
+}
+
+/// Saves `value` at any `path` in the same format as [`save`], e.g. in the library.
+pub fn save_file<T: Versioned>(path: &Path, value: &T) -> io::Result<()> {
+    let contents = format!(
+        "{} = {}\n{}",
+        VERSION_KEY,
+        T::VERSION,
+        toml::to_string(value).map_err(invalid)?
+    );
+    // Written next to the file first, so a crash can't leave it half written.
+    let partial = path.with_extension("toml.partial");
+    fs::write(&partial, contents)?;
+    fs::rename(partial, path)
+}
+
+/// Reads a file written by [`save_file`], which is left in place when it can't be read.
+pub fn load_file<T: Versioned>(path: &Path) -> io::Result<T> {
+    parse(&fs::read_to_string(path)?).map_err(invalid)
+}
+
+/// Path of the folder called `name`, which may not exist yet.
+pub fn folder(name: &str) -> io::Result<PathBuf> {
+    Ok(dir()?.join(name))
+}
+
+/// Path of a new file in the library of saved games, named after `prefix` and the time. The
+/// library is created if needed.
+pub fn library_file(prefix: &str, extension: &str) -> io::Result<PathBuf> {
+    let library = folder("library")?;
+    fs::create_dir_all(&library)?;
+    Ok(stamped(&library, prefix, extension))
+}
+
+/// A path in `folder` no file has yet, named after `prefix` and the time. Files saved within
+/// the same second get a `-1`, `-2`… suffix.
+fn stamped(folder: &Path, prefix: &str, extension: &str) -> PathBuf {
+    let stamp = crate::timestamp();
+    (0..)
+        .map(|i| match i {
+            0 => folder.join(format!("{}-{}.{}", prefix, stamp, extension)),
+            i => folder.join(format!("{}-{}-{}.{}", prefix, stamp, i, extension)),
+        })
+        .find(|target| !target.exists())
+        .expect("some name is free")
+}
+
+/// Calls `changed` whenever the file called `name` is written, replaced or removed, until the
+/// returned watcher is dropped.
+pub fn watch(
+    name: &str,
+    changed: impl Fn() + Send + 'static,
+) -> notify::Result<notify::RecommendedWatcher> {
+    use notify::Watcher;
+
+    let file = path(name)?;
+    // Editors often save by replacing the file, so the whole directory is watched.
+    let dir = dir()?;
+    fs::create_dir_all(&dir)?;
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if !event.kind.is_access() && event.paths.contains(&file) => changed(),
+            Ok(_) => {}
+            Err(err) => tracing::warn!("couldn't watch the config directory: {}", err),
+        })?;
+    watcher.watch(&dir, notify::RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+/// Deletes every file kept between sessions, the library of saved games included.
+pub fn wipe() -> io::Result<()> {
+    match fs::remove_dir_all(dir()?) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+pub fn remove(name: &str) -> io::Result<()> {
+    match fs::remove_file(path(name)?) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Checkpoint;
+
+    #[test]
+    fn migrates_pairs_of_moves() {
+        let checkpoint: Checkpoint = parse("version = 1\nmoves = [[0, 0], [1, 1]]\n").unwrap();
+        let game = checkpoint.restore();
+        let cells: Vec<_> = game
+            .moves()
+            .iter()
+            .map(|played| (played.x, played.y))
+            .collect();
+        assert_eq!(cells, [(0, 0), (1, 1)]);
+        assert!(game.moves().iter().all(|played| played.from.is_none()));
+        assert!(!game.is_timed());
+    }
+
+    #[test]
+    fn refuses_newer_versions() {
+        let contents = format!("version = {}\nmoves = []\n", Checkpoint::VERSION + 1);
+        let err = parse::<Checkpoint>(&contents).err().unwrap();
+        assert!(err.contains("newer"), "{err}");
+    }
+}
@@ -0,0 +1,84 @@
+//! Several games kept open at once, only the one of the selected tab is on the board.
+
+use iced::{
+    widget::{button, row, text},
+    Element,
+};
+
+use crate::{
+    game::{Action, Board, Game},
+    i18n::I18n,
+    ladder::Bot,
+    session::Ongoing,
+    ui::{components::button_style, focus::focusable, tokens::SPACE_M},
+    Message,
+};
+
+/// A game put aside while another tab is selected.
+#[derive(Default)]
+pub struct Tab {
+    pub game: Game,
+    /// The bot played against, `None` for the engine.
+    pub opponent: Option<Bot>,
+    pub text: String,
+    pub ongoing: Ongoing,
+    pub draft: Draft,
+}
+
+/// What the human left half done on the board of a game, kept apart from the game itself so
+/// leaving its tab doesn't lose it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Draft {
+    /// A losing move waiting for confirmation.
+    pub blunder: Option<(usize, usize)>,
+    /// A mark of the human picked up to slide it.
+    pub picked: Option<(usize, usize)>,
+    /// A cell clicked during the computer's turn, played as soon as it's the human's again
+    /// unless the computer took it.
+    pub premove: Option<(usize, usize)>,
+    /// The move the engine recommends to the human, for a moment or until they play.
+    pub hint: Option<Action>,
+    /// A losing move of the human the computer just punished, offered to be taken back in
+    /// practice mode.
+    pub mistake: Option<(usize, usize)>,
+    /// A losing move was taken back, the human looks for one which holds.
+    pub retrying: bool,
+    /// The move of the human the tutor explains once the engine searched its line, with the
+    /// board it was played on.
+    pub tutoring: Option<(Board, (usize, usize))>,
+}
+
+/// One button per tab to select it, each with a button to close it, followed by a button to
+/// open a new tab.
+pub fn bar<'a>(tabs: usize, selected: usize, i18n: &I18n) -> Element<'a, Message> {
+    let mut bar = row![].spacing(SPACE_M);
+    for index in 0..tabs {
+        let style = if index == selected {
+            button_style(iced::theme::Button::Primary)
+        } else {
+            button_style(iced::theme::Button::Secondary)
+        };
+        let mut tab = row![focusable(
+            button(text(i18n.tr_args("tab", [("number", (index + 1).into())])))
+                .style(style)
+                .on_press(Message::SelectTab(index))
+                .padding([5, SPACE_M])
+        )];
+        if tabs > 1 {
+            tab = tab.push(focusable(
+                button(text("x"))
+                    .style(button_style(iced::theme::Button::Text))
+                    .on_press(Message::CloseTab(index))
+                    .padding([5, SPACE_M]),
+            ));
+        }
+        bar = bar.push(tab);
+    }
+    bar.push(focusable(
+        button(text("+"))
+            .style(button_style(iced::theme::Button::Secondary))
+            .on_press(Message::NewTab)
+            .padding([5, SPACE_M]),
+    ))
+    .into()
+}
@@ -0,0 +1,280 @@
+//! Drills the answers to the openings of the computer on the classic 3×3 board. A position
+//! answered right comes back twice as late as the time before, a missed one the same day.
+
+use std::collections::BTreeMap;
+
+use iced::{
+    widget::{button, column, row, text},
+    Alignment, Element,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{Board, Computer, Entity},
+    i18n::I18n,
+    storage,
+    ui::{
+        components::{button_style, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_M, TEXT_SMALL, TEXT_TITLE},
+    },
+    Message,
+};
+
+/// Days between reviews from which a position counts as learned.
+const LEARNED: u32 = 7;
+
+/// A position to answer, the computer having played X and the human O.
+#[derive(Clone)]
+pub struct Card {
+    /// Canonical, so positions which only differ by a rotation or a mirror are asked once.
+    board: Board,
+    /// Every move keeping the best result the position allows.
+    answers: Vec<(usize, usize)>,
+}
+
+impl Card {
+    /// The position as rows of `X`, `O` and `.`, the way the schedule names it.
+    fn key(&self) -> String {
+        self.board
+            .cells()
+            .map(|(_, entity)| match entity {
+                Entity::Computer => 'X',
+                Entity::Human => 'O',
+                Entity::Empty => '.',
+            })
+            .collect()
+    }
+}
+
+/// Every position the trainer asks about: each first move of the computer, then each of its
+/// second moves after an answer which holds.
+pub fn cards(engine: &mut Computer) -> Vec<Card> {
+    let mut cards: Vec<Card> = vec![];
+    let mut ask = |engine: &mut Computer, board: Board| {
+        let board = board.canonical();
+        if cards.iter().any(|card| card.board == board) {
+            return None;
+        }
+        let assessment = engine.assess(board, Entity::Human);
+        let answers: Vec<_> = assessment
+            .moves
+            .iter()
+            .filter(|(_, winner)| *winner == assessment.winner)
+            .map(|(cell, _)| *cell)
+            .collect();
+        cards.push(Card {
+            board,
+            answers: answers.clone(),
+        });
+        Some((board, answers))
+    };
+    for opening in Board::default().playable().collect::<Vec<_>>() {
+        let mut board = Board::default();
+        board[opening.0][opening.1] = Entity::Computer;
+        let Some((board, answers)) = ask(engine, board) else {
+            continue;
+        };
+        for (x, y) in answers {
+            let mut answered = board;
+            answered[x][y] = Entity::Human;
+            for (x, y) in answered.playable().collect::<Vec<_>>() {
+                let mut second = answered;
+                second[x][y] = Entity::Computer;
+                ask(engine, second);
+            }
+        }
+    }
+    cards
+}
+
+/// When each position comes back, kept between runs along with how the reviews went.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Schedule {
+    reviews: BTreeMap<String, Review>,
+    /// Answers given so far, and how many of them were right.
+    answered: u32,
+    right: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Review {
+    /// Days until the position is asked again, 0 once missed.
+    interval: u32,
+    /// The day it's asked again, counted from 1970-01-01.
+    due: u64,
+}
+
+impl Schedule {
+    pub const FILE: &'static str = "openings";
+
+    /// The day a position never reviewed is due, right away.
+    fn due(&self, card: &Card, today: u64) -> u64 {
+        self.reviews
+            .get(&card.key())
+            .map_or(today, |review| review.due)
+    }
+
+    /// Schedules `card` again after an answer given `today`.
+    fn review(&mut self, card: &Card, right: bool, today: u64) {
+        let review = self.reviews.entry(card.key()).or_insert(Review {
+            interval: 0,
+            due: today,
+        });
+        review.interval = if right {
+            (review.interval * 2).max(1)
+        } else {
+            0
+        };
+        review.due = today + u64::from(review.interval);
+        self.answered += 1;
+        self.right += u32::from(right);
+    }
+}
+
+impl storage::Versioned for Schedule {
+    const VERSION: u32 = 1;
+}
+
+/// A sitting of the trainer, asking one due position after another.
+pub struct Trainer {
+    cards: Vec<Card>,
+    /// The position asked, `None` once none is due.
+    current: Option<usize>,
+    /// The move played on the position asked, once answered.
+    answer: Option<(usize, usize)>,
+    today: u64,
+}
+
+impl Trainer {
+    pub fn new(cards: Vec<Card>, schedule: &Schedule, today: u64) -> Trainer {
+        let mut trainer = Trainer {
+            cards,
+            current: None,
+            answer: None,
+            today,
+        };
+        trainer.next(schedule);
+        trainer
+    }
+
+    /// Asks the position due the earliest, another one than the position just asked when
+    /// there's a choice.
+    pub fn next(&mut self, schedule: &Schedule) {
+        let previous = self.current;
+        self.answer = None;
+        self.current = (0..self.cards.len())
+            .filter(|&index| schedule.due(&self.cards[index], self.today) <= self.today)
+            .min_by_key(|&index| {
+                (
+                    Some(index) == previous,
+                    schedule.due(&self.cards[index], self.today),
+                )
+            });
+    }
+
+    /// Answers the position asked with `(x, y)`, recording how it went in `schedule`.
+    pub fn click(&mut self, x: usize, y: usize, schedule: &mut Schedule) {
+        let Some(card) = self.current.map(|index| &self.cards[index]) else {
+            return;
+        };
+        if self.answer.is_some() {
+            return;
+        }
+        schedule.review(card, card.answers.contains(&(x, y)), self.today);
+        self.answer = Some((x, y));
+    }
+
+    pub fn view(&self, schedule: &Schedule, i18n: &I18n) -> Element<'_, Message> {
+        let due = self
+            .cards
+            .iter()
+            .filter(|card| schedule.due(card, self.today) <= self.today)
+            .count();
+        let learned = self
+            .cards
+            .iter()
+            .filter(|card| {
+                schedule
+                    .reviews
+                    .get(&card.key())
+                    .is_some_and(|review| review.interval >= LEARNED)
+            })
+            .count();
+        let retention = match schedule.answered {
+            0 => 100,
+            answered => schedule.right * 100 / answered,
+        };
+        let stats = i18n.tr_args(
+            "trainer-stats",
+            [
+                ("due", due.into()),
+                ("learned", learned.into()),
+                ("total", self.cards.len().into()),
+                ("retention", i18n.percent(retention).into()),
+            ],
+        );
+        let header = column![
+            text(i18n.tr("trainer")).size(TEXT_TITLE),
+            text(stats).size(TEXT_SMALL)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M);
+        let back = focusable(secondary(i18n.tr("back")).on_press(Message::ToggleTrainer));
+
+        let Some(card) = self.current.map(|index| &self.cards[index]) else {
+            return column![header, text(i18n.tr("trainer-done")), back]
+                .align_items(Alignment::Center)
+                .spacing(SPACE_M)
+                .into();
+        };
+        let board = column(
+            (0..3)
+                .map(|x| {
+                    row((0..3)
+                        .map(|y| {
+                            let entity = match self.answer {
+                                Some(answer) if answer == (x, y) => &Entity::Human,
+                                _ => &card.board[x][y],
+                            };
+                            // Once answered, the moves which hold are shown.
+                            let style = if self.answer.is_some() && card.answers.contains(&(x, y)) {
+                                button_style(iced::theme::Button::Primary)
+                            } else {
+                                button_style(iced::theme::Button::Text)
+                            };
+                            let mut cell = button(entity.as_str()).style(style).padding(SPACE_M);
+                            if self.answer.is_none() && *entity == Entity::Empty {
+                                cell = cell.on_press(Message::TrainerClicked(x, y));
+                            }
+                            focusable(cell)
+                        })
+                        .collect())
+                    .spacing(SPACE_M)
+                    .into()
+                })
+                .collect(),
+        )
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M);
+
+        let (say, actions) = match self.answer {
+            None => (i18n.tr("trainer-ask"), row![back]),
+            Some(answer) => {
+                let say = if card.answers.contains(&answer) {
+                    "trainer-right"
+                } else {
+                    "trainer-wrong"
+                };
+                let next =
+                    focusable(primary(i18n.tr("lesson-next")).on_press(Message::TrainerNext));
+                (i18n.tr(say), row![next, back])
+            }
+        };
+        column![header, board, text(say), actions.spacing(SPACE_M)]
+            .align_items(Alignment::Center)
+            .spacing(SPACE_M)
+            .into()
+    }
+}
@@ -0,0 +1,229 @@
+//! The `tune` command: adjusts the weights the engine estimates positions past its horizon
+//! with, by simulated annealing over matches the engine plays against itself on a board too
+//! big to search to the end. Weights which beat the defaults are saved in the config
+//! directory, where the engine reads them from at startup.
+
+use std::path::PathBuf;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    game::{has_line, Board, Computer, Entity, Rules, Weights, MAX_RUN_WORTH, MAX_SIZE},
+    storage,
+};
+
+/// The file the weights are saved to.
+pub const FILE: &str = "weights";
+
+impl storage::Versioned for Weights {
+    const VERSION: u32 = 1;
+}
+
+/// Moves played at random to open each game, so the matches don't replay the same game.
+const OPENING: usize = 2;
+
+/// Temperature of the first step, in share of the points of a match: a candidate scoring 10%
+/// less than the weights it challenges is first taken a third of the time.
+const START_TEMPERATURE: f64 = 0.1;
+
+/// Temperature of the last step, by then only candidates at least as good are taken.
+const END_TEMPERATURE: f64 = 0.005;
+
+/// The options of the command.
+struct Options {
+    iterations: u32,
+    games: u32,
+    size: usize,
+    line: usize,
+    output: Option<PathBuf>,
+}
+
+/// The `tune [--iterations <count>] [--games <count>] [--size 4|5|6] [--line <marks>]
+/// [--output <file>]` command. Each iteration changes one weight of the current ones and
+/// keeps the change if it does about as well over a match, less and less likely as the
+/// temperature drops when it does worse.
+pub fn run(mut args: impl Iterator<Item = String>) {
+    let mut options = Options {
+        iterations: 30,
+        games: 16,
+        size: 6,
+        line: 4,
+        output: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut number = || {
+            args.next()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0)
+        };
+        match arg.as_str() {
+            "--iterations" => options.iterations = number() as u32,
+            "--games" => options.games = number() as u32,
+            "--size" => options.size = number(),
+            "--line" => options.line = number(),
+            "--output" => options.output = args.next().map(PathBuf::from),
+            _ => {}
+        }
+    }
+    let fail = |message: String| -> ! {
+        eprintln!("{message}");
+        std::process::exit(1);
+    };
+    if !(4..=MAX_SIZE).contains(&options.size) {
+        fail(format!(
+            "Only boards from 4×4 to {MAX_SIZE}×{MAX_SIZE} are estimated"
+        ));
+    }
+    if !(3..=options.size).contains(&options.line) {
+        fail(format!("Lines are 3 to {} marks long", options.size));
+    }
+    if options.iterations == 0 || options.games < 2 {
+        fail("--iterations needs 1 or more, --games 2 or more".to_string());
+    }
+    let board = Board::new(options.size).with_line(options.line);
+    let mut rng = rand::thread_rng();
+
+    eprintln!(
+        "Tuning on {0}×{0} with {1} in a row, {2} games a match",
+        options.size, options.line, options.games
+    );
+    let mut current = Weights::default();
+    for iteration in 0..options.iterations {
+        let progress = f64::from(iteration) / f64::from(options.iterations);
+        let temperature = START_TEMPERATURE * (END_TEMPERATURE / START_TEMPERATURE).powf(progress);
+        let candidate = neighbour(current, options.line, &mut rng);
+        let score = match_score(candidate, current, board, options.games, &mut rng);
+        let gain = score - 0.5;
+        let taken = gain >= 0.0 || rng.gen_bool((gain / temperature).exp());
+        eprintln!(
+            "[{}/{}] {:?} scored {:.0}% against {:?}: {}",
+            iteration + 1,
+            options.iterations,
+            runs(&candidate, options.line),
+            score * 100.0,
+            runs(&current, options.line),
+            if taken { "taken" } else { "dropped" }
+        );
+        if taken {
+            current = candidate;
+        }
+    }
+
+    if current == Weights::default() {
+        eprintln!("Nothing beat the default weights, nothing written");
+        return;
+    }
+    // The last match is longer, it decides what's written.
+    let score = match_score(
+        current,
+        Weights::default(),
+        board,
+        options.games * 2,
+        &mut rng,
+    );
+    eprintln!(
+        "{:?} scored {:.0}% against the defaults",
+        runs(&current, options.line),
+        score * 100.0
+    );
+    if score <= 0.5 {
+        eprintln!("The default weights held up, nothing written");
+        return;
+    }
+    let saved = match &options.output {
+        Some(path) => storage::save_file(path, &current).map(|()| path.display().to_string()),
+        None => storage::save(FILE, &current).map(|()| format!("the {FILE} file")),
+    };
+    match saved {
+        Ok(saved) => eprintln!("Written to {saved}"),
+        Err(err) => fail(format!("Couldn't write the weights: {err}")),
+    }
+}
+
+/// The worth of the runs `line` marks long can have, short of a whole line.
+fn runs(weights: &Weights, line: usize) -> &[i32] {
+    &weights.runs[1..line]
+}
+
+/// `weights` with the worth of one run, picked at random, scaled by up to twice or half.
+fn neighbour(mut weights: Weights, line: usize, rng: &mut impl Rng) -> Weights {
+    let marks = rng.gen_range(1..line);
+    let worth = weights.runs[marks];
+    let scaled = (f64::from(worth.max(1)) * 2f64.powf(rng.gen_range(-1.0..1.0))).round() as i32;
+    // Small worths only move by rounding, nudged a step so the candidate differs.
+    let scaled = if scaled != worth {
+        scaled
+    } else if worth > 1 && rng.gen_bool(0.5) {
+        worth - 1
+    } else {
+        worth + 1
+    };
+    weights.runs[marks] = scaled.clamp(1, MAX_RUN_WORTH);
+    weights
+}
+
+/// Share of the points `challenger` makes against `holder` over `games` games on `board`, a
+/// win being worth a point and a draw half. Each opening is played twice, once by each side
+/// first.
+fn match_score(
+    challenger: Weights,
+    holder: Weights,
+    board: Board,
+    games: u32,
+    rng: &mut impl Rng,
+) -> f64 {
+    let mut engines = (Computer::default(), Computer::default());
+    engines.0.set_weights(challenger);
+    engines.1.set_weights(holder);
+    let mut points = 0.0;
+    let mut opening = vec![];
+    for game in 0..games {
+        if game % 2 == 0 {
+            opening = board.playable().collect();
+            opening.shuffle(rng);
+            opening.truncate(OPENING);
+        }
+        let starter = if game % 2 == 0 {
+            Entity::Computer
+        } else {
+            Entity::Human
+        };
+        points += match play(&mut engines, board, &opening, starter) {
+            Some(Entity::Computer) => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+    }
+    points / f64::from(games)
+}
+
+/// A game of the first engine, playing the computer's side, against the second one from the
+/// empty `board`, the cells of `opening` played first. The winner, `None` for a draw.
+fn play(
+    (challenger, holder): &mut (Computer, Computer),
+    mut board: Board,
+    opening: &[(usize, usize)],
+    starter: Entity,
+) -> Option<Entity> {
+    challenger.new_game(Rules::Classic);
+    holder.new_game(Rules::Classic);
+    let mut player = starter;
+    let mut opening = opening.iter().copied();
+    loop {
+        let (x, y) = match opening.next() {
+            Some(cell) => cell,
+            // Both engines play the computer's side, the second one sees the board the other
+            // way around. An engine left without a cell to place on ends the game drawn.
+            None if player == Entity::Computer => challenger.best_play(board)?,
+            None => holder.best_play(board.swapped())?,
+        };
+        board[x][y] = player;
+        if has_line(&board, player) {
+            return Some(player);
+        }
+        if board.is_full() {
+            return None;
+        }
+        player = !player;
+    }
+}
@@ -0,0 +1,434 @@
+//! Lessons written as TOML scripts, see `lessons/basics.toml` for the format. Besides the
+//! bundled ones, scripts dropped in the `lessons` folder of the config directory are picked up
+//! without any code change.
+
+use std::{collections::HashMap, fs, io};
+
+use iced::{
+    widget::{button, column, row, text},
+    Alignment, Element,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    command::Command,
+    game::{parse_cell, winning_moves, Board, Entity},
+    i18n::I18n,
+    storage,
+    ui::{
+        components::{button_style, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_M, TEXT_TITLE},
+    },
+    Message,
+};
+
+const BUNDLED: [&str; 4] = [
+    include_str!("../lessons/basics.toml"),
+    include_str!("../lessons/double-threats.toml"),
+    include_str!("../lessons/forks.toml"),
+    include_str!("../lessons/corner-traps.toml"),
+];
+
+/// A lesson as written in its script.
+#[derive(Deserialize)]
+struct Script {
+    title: String,
+    steps: Vec<StepScript>,
+}
+
+#[derive(Deserialize)]
+struct StepScript {
+    #[serde(default)]
+    say: String,
+    board: Option<[String; 3]>,
+    #[serde(default)]
+    highlight: Vec<String>,
+    #[serde(default)]
+    expect: Vec<String>,
+    check: Option<Check>,
+    wrong: Option<String>,
+    reply: Option<String>,
+    command: Option<String>,
+}
+
+/// A quiz question, answered by any move with the asked property rather than a fixed cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Check {
+    /// Completes a line of the human.
+    Win,
+    /// Stops the computer from completing a line.
+    Block,
+    /// Makes two threats at once, which can't both be blocked.
+    Fork,
+}
+
+impl Check {
+    fn accepts(self, board: &Board, x: usize, y: usize) -> bool {
+        match self {
+            Check::Win => winning_moves(board, Entity::Human).contains(&(x, y)),
+            Check::Block => winning_moves(board, Entity::Computer).contains(&(x, y)),
+            Check::Fork => {
+                let mut after = *board;
+                after[x][y] = Entity::Human;
+                winning_moves(&after, Entity::Human).len() >= 2
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Lesson {
+    pub title: String,
+    steps: Vec<Step>,
+}
+
+#[derive(Clone)]
+struct Step {
+    say: String,
+    /// The position shown by the step, the previous one is kept when `None`.
+    board: Option<Board>,
+    highlight: Vec<(usize, usize)>,
+    /// Cells the human can play to move on, the step waits for "next" when empty and there's
+    /// no `check`.
+    expect: Vec<(usize, usize)>,
+    check: Option<Check>,
+    /// Said when the human plays a cell which isn't expected.
+    wrong: Option<String>,
+    /// The computer's answer to the expected move.
+    reply: Option<(usize, usize)>,
+    /// The step waits for this command to run, from wherever it's run.
+    command: Option<Command>,
+}
+
+impl Lesson {
+    pub fn parse(script: &str) -> Result<Lesson, String> {
+        let script: Script = toml::from_str(script).map_err(|err| err.to_string())?;
+        if script.steps.is_empty() {
+            return Err("a lesson needs at least one step".to_string());
+        }
+        // Lessons are played on the classic 3×3 board.
+        let cell = |name: &String| {
+            parse_cell(name)
+                .filter(|&(x, y)| x < 3 && y < 3)
+                .ok_or_else(|| format!("invalid cell {}", name))
+        };
+        let cells = |names: &[String]| names.iter().map(cell).collect::<Result<Vec<_>, _>>();
+        let steps = script
+            .steps
+            .into_iter()
+            .map(|step| {
+                Ok(Step {
+                    board: step.board.as_ref().map(parse_board).transpose()?,
+                    highlight: cells(&step.highlight)?,
+                    expect: cells(&step.expect)?,
+                    check: step.check,
+                    reply: step.reply.as_ref().map(cell).transpose()?,
+                    command: step
+                        .command
+                        .map(|id| {
+                            Command::from_id(&id).ok_or_else(|| format!("unknown command {}", id))
+                        })
+                        .transpose()?,
+                    say: step.say,
+                    wrong: step.wrong,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Lesson {
+            title: script.title,
+            steps,
+        })
+    }
+
+    /// Loads the bundled lessons followed by the ones in the config directory. Scripts which
+    /// can't be read are skipped.
+    pub fn all() -> Vec<Lesson> {
+        let mut lessons: Vec<Lesson> = BUNDLED
+            .iter()
+            .map(|script| Lesson::parse(script).expect("the bundled lessons are valid"))
+            .collect();
+        let mut paths = match storage::folder("lessons").and_then(fs::read_dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                .collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => vec![],
+            Err(err) => {
+                tracing::warn!("couldn't list the lessons: {}", err);
+                vec![]
+            }
+        };
+        paths.sort();
+        for path in paths {
+            let lesson = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|script| Lesson::parse(&script));
+            match lesson {
+                Ok(lesson) => lessons.push(lesson),
+                Err(err) => tracing::warn!("skipped the lesson {}: {}", path.display(), err),
+            }
+        }
+        lessons
+    }
+}
+
+/// Parses rows such as `"XO."`, `X` being the computer, `O` the human and `.` an empty cell.
+fn parse_board(rows: &[String; 3]) -> Result<Board, String> {
+    let mut board = Board::default();
+    for (x, line) in rows.iter().enumerate() {
+        let cells: Vec<char> = line.chars().collect();
+        if cells.len() != 3 {
+            return Err(format!("rows need 3 cells, got {:?}", line));
+        }
+        for (y, cell) in cells.into_iter().enumerate() {
+            board[x][y] = match cell.to_ascii_uppercase() {
+                'X' => Entity::Computer,
+                'O' => Entity::Human,
+                '.' => Entity::Empty,
+                _ => return Err(format!("invalid cell {:?} in {:?}", cell, line)),
+            };
+        }
+    }
+    Ok(board)
+}
+
+impl Step {
+    /// Whether the step waits for a move.
+    fn waits_for_move(&self) -> bool {
+        !self.expect.is_empty() || self.check.is_some()
+    }
+
+    /// Whether the step waits for a move or a command rather than for the "next" button.
+    fn waits(&self) -> bool {
+        self.waits_for_move() || self.command.is_some()
+    }
+
+    fn accepts(&self, board: &Board, x: usize, y: usize) -> bool {
+        match self.check {
+            Some(check) => check.accepts(board, x, y),
+            None => self.expect.contains(&(x, y)),
+        }
+    }
+}
+
+/// A lesson being followed.
+pub struct Tutorial {
+    lesson: Lesson,
+    step: usize,
+    board: Board,
+    /// Shown after a wrong move.
+    feedback: Option<String>,
+    /// Quiz questions answered so far, and how many of them on the first try.
+    questions: u32,
+    right: u32,
+    /// The current question was already missed once.
+    missed: bool,
+}
+
+impl Tutorial {
+    pub fn new(lesson: Lesson) -> Tutorial {
+        let mut tutorial = Tutorial {
+            lesson,
+            step: 0,
+            board: Board::default(),
+            feedback: None,
+            questions: 0,
+            right: 0,
+            missed: false,
+        };
+        tutorial.enter();
+        tutorial
+    }
+
+    fn current(&self) -> &Step {
+        &self.lesson.steps[self.step]
+    }
+
+    fn enter(&mut self) {
+        self.feedback = None;
+        self.missed = false;
+        if let Some(board) = self.current().board {
+            self.board = board;
+        }
+    }
+
+    pub fn is_last_step(&self) -> bool {
+        self.step + 1 == self.lesson.steps.len()
+    }
+
+    /// Moves on to the next step, returns `false` once the lesson is over.
+    pub fn next(&mut self) -> bool {
+        if self.is_last_step() {
+            return false;
+        }
+        self.step += 1;
+        self.enter();
+        true
+    }
+
+    pub fn click(&mut self, x: usize, y: usize) {
+        let step = self.current();
+        if !step.accepts(&self.board, x, y) {
+            self.feedback = Some(step.wrong.clone().unwrap_or_default());
+            self.missed = true;
+            return;
+        }
+        let (quiz, reply) = (step.check.is_some(), step.reply);
+        if quiz {
+            self.questions += 1;
+            self.right += u32::from(!self.missed);
+        }
+        self.board[x][y] = Entity::Human;
+        if let Some((x, y)) = reply {
+            self.board[x][y] = Entity::Computer;
+        }
+        self.next();
+    }
+
+    /// Moves on when the step waited for `command`, which just ran.
+    pub fn ran(&mut self, command: Command) {
+        if self.current().command == Some(command) {
+            self.next();
+        }
+    }
+
+    pub fn view(&self, i18n: &I18n) -> Element<'_, Message> {
+        let step = self.current();
+        let board = column(
+            (0..3)
+                .map(|x| {
+                    row((0..3)
+                        .map(|y| {
+                            let style = if step.highlight.contains(&(x, y)) {
+                                button_style(iced::theme::Button::Primary)
+                            } else {
+                                button_style(iced::theme::Button::Text)
+                            };
+                            let mut cell = button(self.board[x][y].as_str())
+                                .style(style)
+                                .padding(SPACE_M);
+                            if step.waits_for_move() && self.board[x][y] == Entity::Empty {
+                                cell = cell.on_press(Message::LessonClicked(x, y));
+                            }
+                            focusable(cell)
+                        })
+                        .collect())
+                    .spacing(SPACE_M)
+                    .into()
+                })
+                .collect(),
+        )
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M);
+
+        let mut actions = row![].spacing(SPACE_M);
+        if !step.waits() {
+            let label = if self.is_last_step() {
+                "lesson-finish"
+            } else {
+                "lesson-next"
+            };
+            actions = actions.push(focusable(
+                primary(i18n.tr(label)).on_press(Message::LessonNext),
+            ));
+        }
+        actions = actions.push(focusable(
+            secondary(i18n.tr("lesson-leave")).on_press(Message::LeaveLesson),
+        ));
+
+        let score = if self.is_last_step() && self.questions > 0 {
+            i18n.tr_args(
+                "lesson-score",
+                [
+                    ("right", self.right.into()),
+                    ("questions", self.questions.into()),
+                ],
+            )
+        } else {
+            String::new()
+        };
+        column![
+            text(&self.lesson.title).size(TEXT_TITLE),
+            board,
+            text(&step.say),
+            text(self.feedback.as_deref().unwrap_or_default()),
+            text(score),
+            actions
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M)
+        .into()
+    }
+}
+
+/// Best quiz result of every completed lesson, by title.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    completed: HashMap<String, Score>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Score {
+    right: u32,
+    questions: u32,
+}
+
+impl Progress {
+    pub const FILE: &'static str = "lessons";
+
+    /// Records that `tutorial` was followed to the end, keeping the best quiz result.
+    pub fn complete(&mut self, tutorial: &Tutorial) {
+        let score = Score {
+            right: tutorial.right,
+            questions: tutorial.questions,
+        };
+        self.completed
+            .entry(tutorial.lesson.title.clone())
+            .and_modify(|best| {
+                if score.right >= best.right {
+                    *best = score
+                }
+            })
+            .or_insert(score);
+    }
+}
+
+impl storage::Versioned for Progress {
+    const VERSION: u32 = 1;
+}
+
+/// The list of lessons to pick from, with the result of those already completed.
+pub fn menu<'a>(lessons: &'a [Lesson], progress: &Progress, i18n: &I18n) -> Element<'a, Message> {
+    let mut menu = column![text(i18n.tr("lessons")).size(TEXT_TITLE)]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M);
+    for (index, lesson) in lessons.iter().enumerate() {
+        let label = match progress.completed.get(&lesson.title) {
+            Some(score) if score.questions > 0 => i18n.tr_args(
+                "lesson-completed-quiz",
+                [
+                    ("title", lesson.title.as_str().into()),
+                    ("right", score.right.into()),
+                    ("questions", score.questions.into()),
+                ],
+            ),
+            Some(_) => i18n.tr_args(
+                "lesson-completed",
+                [("title", lesson.title.as_str().into())],
+            ),
+            None => lesson.title.clone(),
+        };
+        menu = menu.push(focusable(
+            primary(label)
+                .style(button_style(iced::theme::Button::Secondary))
+                .on_press(Message::StartLesson(index)),
+        ));
+    }
+    menu.push(focusable(
+        secondary(i18n.tr("back")).on_press(Message::ToggleLessons),
+    ))
+    .into()
+}
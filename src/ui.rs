@@ -0,0 +1,6 @@
+//! The building blocks screens are made of, so they all look alike.
+
+pub mod components;
+pub mod dwell;
+pub mod focus;
+pub mod tokens;
@@ -0,0 +1,154 @@
+//! Styled widgets for the screens, replacing one-off styling at each call site.
+
+use iced::{
+    theme,
+    widget::{button, checkbox, column, container, row, slider, text, Button},
+    Alignment, Element, Length, Theme,
+};
+
+use super::{
+    focus::{self, focusable},
+    tokens::{
+        corners, BUTTON_PADDING, CELL_PADDING, PANEL_PADDING, RADIUS_M, RADIUS_S, SPACE_M, SPACE_S,
+    },
+};
+use crate::Message;
+
+/// The button of the main action of a screen or a prompt.
+pub fn primary<'a>(label: impl ToString) -> Button<'a, Message> {
+    button(text(label.to_string()))
+        .style(button_style(theme::Button::Primary))
+        .padding(BUTTON_PADDING)
+}
+
+/// The button of an action besides the main one, e.g. going back or cancelling.
+pub fn secondary<'a>(label: impl ToString) -> Button<'a, Message> {
+    primary(label).style(button_style(theme::Button::Secondary))
+}
+
+/// The button of an action which can't be undone, e.g. deleting data.
+pub fn destructive<'a>(label: impl ToString) -> Button<'a, Message> {
+    primary(label).style(button_style(theme::Button::Destructive))
+}
+
+/// A cell of a board, showing `content` and sending `press` when `enabled`.
+pub fn cell<'a>(
+    content: impl Into<Element<'a, Message>>,
+    press: Message,
+    enabled: bool,
+) -> Button<'a, Message> {
+    let cell = button(content)
+        .style(button_style(theme::Button::Text))
+        .padding(CELL_PADDING);
+    if enabled {
+        cell.on_press(press)
+    } else {
+        cell
+    }
+}
+
+/// A box setting `content` apart from the rest of the screen.
+pub fn panel<'a>(content: impl Into<Element<'a, Message>>) -> container::Container<'a, Message> {
+    container(content)
+        .style(panel_style())
+        .padding(PANEL_PADDING)
+}
+
+/// A question waiting for one of `actions` before anything else happens, keeping the focus on
+/// them.
+pub fn modal<'a>(
+    question: impl ToString,
+    actions: impl IntoIterator<Item = Button<'a, Message>>,
+) -> Element<'a, Message> {
+    panel(
+        column![
+            text(question.to_string()),
+            row(actions.into_iter().map(focusable).collect()).spacing(SPACE_M)
+        ]
+        .align_items(Alignment::Center)
+        .spacing(SPACE_M),
+    )
+    .id(focus::modal())
+    .into()
+}
+
+/// An option switched on or off.
+pub fn toggle<'a>(
+    label: impl ToString,
+    enabled: bool,
+    on_toggle: impl Fn(bool) -> Message + 'a,
+) -> Element<'a, Message> {
+    focusable(checkbox(label.to_string(), enabled, on_toggle).spacing(SPACE_M))
+}
+
+/// A slider with its label above it, the label usually showing the value.
+pub fn labeled_slider<'a>(
+    label: impl ToString,
+    range: std::ops::RangeInclusive<u32>,
+    value: u32,
+    step: u32,
+    on_change: impl Fn(u32) -> Message + 'a,
+) -> Element<'a, Message> {
+    column![
+        text(label.to_string()),
+        slider(range, value, on_change).step(step).width(300)
+    ]
+    .align_items(Alignment::Center)
+    .spacing(SPACE_S)
+    .width(Length::Shrink)
+    .into()
+}
+
+/// The colors of `role` in the theme, with the corners of the tokens.
+pub fn button_style(role: theme::Button) -> theme::Button {
+    theme::Button::Custom(Box::new(Rounded(role)))
+}
+
+/// The colors of a panel in the theme, with the corners of the tokens.
+pub fn panel_style() -> theme::Container {
+    theme::Container::Custom(Box::new(Panel))
+}
+
+struct Rounded(theme::Button);
+
+impl Rounded {
+    fn round(appearance: button::Appearance) -> button::Appearance {
+        button::Appearance {
+            border_radius: corners(RADIUS_S),
+            ..appearance
+        }
+    }
+}
+
+impl button::StyleSheet for Rounded {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> button::Appearance {
+        Rounded::round(button::StyleSheet::active(theme, &self.0))
+    }
+
+    fn hovered(&self, theme: &Theme) -> button::Appearance {
+        Rounded::round(button::StyleSheet::hovered(theme, &self.0))
+    }
+
+    fn pressed(&self, theme: &Theme) -> button::Appearance {
+        Rounded::round(button::StyleSheet::pressed(theme, &self.0))
+    }
+
+    fn disabled(&self, theme: &Theme) -> button::Appearance {
+        Rounded::round(button::StyleSheet::disabled(theme, &self.0))
+    }
+}
+
+struct Panel;
+
+impl container::StyleSheet for Panel {
+    type Style = Theme;
+
+    fn appearance(&self, theme: &Theme) -> container::Appearance {
+        container::Appearance {
+            border_radius: corners(RADIUS_M),
+            ..container::StyleSheet::appearance(theme, &theme::Container::Box)
+        }
+    }
+}
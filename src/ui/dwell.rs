@@ -0,0 +1,239 @@
+//! Clicking by keeping the pointer still, for switch and eye-tracker users who can point but
+//! not click. A ring grows over the widget hovered until it circles it and the click happens.
+//! In the low-spec mode the ring is drawn whole from the start, redrawing only to click.
+
+use std::time::{Duration, Instant};
+
+use iced::{Element, Theme};
+use iced_native::{
+    event, layout, mouse, overlay, renderer,
+    widget::{operation::Operation, tree, Tree},
+    window, Clipboard, Color, Event, Layout, Length, Point, Rectangle, Shell, Widget,
+};
+
+use super::tokens::{corners, low_spec};
+
+/// Width of the ring.
+const RING: f32 = 3.0;
+
+/// `content` sending `on_dwell` once the pointer stays on it for `time`.
+pub fn dwell<'a, Message: Clone + 'a>(
+    content: impl Into<Element<'a, Message>>,
+    on_dwell: Message,
+    time: Duration,
+) -> Element<'a, Message> {
+    Dwell {
+        content: content.into(),
+        on_dwell,
+        time,
+    }
+    .into()
+}
+
+struct Dwell<'a, Message, Renderer> {
+    content: iced_native::Element<'a, Message, Renderer>,
+    on_dwell: Message,
+    time: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    /// When the pointer came on the widget, `None` while it's elsewhere.
+    since: Option<Instant>,
+    /// Whether the click happened, so it happens once until the pointer leaves.
+    clicked: bool,
+}
+
+impl State {
+    /// How far the ring has grown, from 0 to 1, `None` when there's no ring to draw.
+    fn progress(&self, time: Duration) -> Option<f32> {
+        let since = self.since.filter(|_| !self.clicked)?;
+        if low_spec() {
+            return Some(1.0);
+        }
+        Some((since.elapsed().as_secs_f32() / time.as_secs_f32()).min(1.0))
+    }
+
+    /// The next redraw while the ring grows, every frame or only when it clicks.
+    fn next_redraw(&self, time: Duration) -> Option<window::RedrawRequest> {
+        let since = self.since.filter(|_| !self.clicked)?;
+        Some(if low_spec() {
+            window::RedrawRequest::At(since + time)
+        } else {
+            window::RedrawRequest::NextFrame
+        })
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Dwell<'a, Message, Renderer>
+where
+    Message: Clone,
+    Renderer: iced_native::Renderer<Theme = Theme>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content))
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if !layout.bounds().contains(position) {
+                    *state = State::default();
+                } else if state.since.is_none() {
+                    state.since = Some(Instant::now());
+                    if let Some(redraw) = state.next_redraw(self.time) {
+                        shell.request_redraw(redraw);
+                    }
+                }
+            }
+            Event::Mouse(mouse::Event::CursorLeft) => *state = State::default(),
+            // Each frame grows the ring, until the last one clicks.
+            Event::Window(window::Event::RedrawRequested(now)) => {
+                if let Some(since) = state.since.filter(|_| !state.clicked) {
+                    if now.saturating_duration_since(since) >= self.time {
+                        state.clicked = true;
+                        shell.publish(self.on_dwell.clone());
+                    } else if let Some(redraw) = state.next_redraw(self.time) {
+                        shell.request_redraw(redraw);
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+        let Some(progress) = tree.state.downcast_ref::<State>().progress(self.time) else {
+            return;
+        };
+        let bounds = layout.bounds();
+        let center = bounds.center();
+        let diameter = bounds.width.min(bounds.height) * progress;
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    x: center.x - diameter / 2.0,
+                    y: center.y - diameter / 2.0,
+                    width: diameter,
+                    height: diameter,
+                },
+                border_radius: corners(diameter / 2.0).into(),
+                border_width: RING.min(diameter / 2.0),
+                border_color: theme.palette().primary,
+            },
+            Color::TRANSPARENT,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<Dwell<'a, Message, Renderer>>
+    for iced_native::Element<'a, Message, Renderer>
+where
+    Message: Clone + 'a,
+    Renderer: iced_native::Renderer<Theme = Theme> + 'a,
+{
+    fn from(dwell: Dwell<'a, Message, Renderer>) -> Self {
+        iced_native::Element::new(dwell)
+    }
+}
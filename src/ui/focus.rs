@@ -0,0 +1,319 @@
+//! Moving the keyboard focus with Tab and Shift+Tab. Widgets wrapped with [`focusable`] take
+//! the focus in turn with text inputs, show a ring while they have it and act as if clicked
+//! when Enter or Space is pressed. While a modal is shown, the focus stays on its widgets.
+
+use iced::{Command, Element, Theme};
+use iced_native::{
+    event, keyboard, layout, mouse, overlay, renderer, touch,
+    widget::{
+        self, container,
+        operation::{self, Operation, Outcome},
+        tree, Tree,
+    },
+    Clipboard, Color, Event, Layout, Length, Point, Rectangle, Shell, Widget,
+};
+
+use super::tokens::{corners, RADIUS_S};
+
+/// Width of the ring around the focused widget, drawn just outside of it.
+const RING: f32 = 2.0;
+
+/// Id of the container of modals.
+const MODAL: &str = "modal";
+
+/// The container of modals, which keeps the focus while it is shown.
+pub fn modal() -> container::Id {
+    container::Id::new(MODAL)
+}
+
+/// `content` taking the focus in turn with the other focusable widgets.
+pub fn focusable<'a, Message: 'a>(
+    content: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    Focus {
+        content: content.into(),
+    }
+    .into()
+}
+
+/// Moves the focus to the next focusable widget, or the previous one when `backwards`, going
+/// around at either end.
+pub fn step<Message: 'static>(backwards: bool) -> Command<Message> {
+    Command::widget(Survey {
+        backwards,
+        in_modal: false,
+        focusables: Vec::new(),
+    })
+}
+
+struct Focus<'a, Message, Renderer> {
+    content: iced_native::Element<'a, Message, Renderer>,
+}
+
+#[derive(Default)]
+struct State {
+    focused: bool,
+}
+
+impl operation::Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Focus<'a, Message, Renderer>
+where
+    Renderer: iced_native::Renderer<Theme = Theme>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content))
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        operation.focusable(tree.state.downcast_mut::<State>(), None);
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        match event {
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: keyboard::KeyCode::Enter | keyboard::KeyCode::Space,
+                ..
+            }) if state.focused => {
+                // A click in the middle of the content, so buttons, checkboxes and radios all
+                // react the way they do to the mouse.
+                let center = layout.bounds().center();
+                for click in [
+                    mouse::Event::ButtonPressed(mouse::Button::Left),
+                    mouse::Event::ButtonReleased(mouse::Button::Left),
+                ] {
+                    self.content.as_widget_mut().on_event(
+                        &mut tree.children[0],
+                        Event::Mouse(click),
+                        layout,
+                        center,
+                        renderer,
+                        clipboard,
+                        shell,
+                    );
+                }
+                return event::Status::Captured;
+            }
+            // Pointing somewhere moves the focus away, as it does from text inputs.
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => state.focused = false,
+            _ => {}
+        }
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+        if tree.state.downcast_ref::<State>().focused {
+            let bounds = layout.bounds();
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle {
+                        x: bounds.x - RING,
+                        y: bounds.y - RING,
+                        width: bounds.width + 2.0 * RING,
+                        height: bounds.height + 2.0 * RING,
+                    },
+                    border_radius: corners(RADIUS_S + RING).into(),
+                    border_width: RING,
+                    border_color: theme.palette().primary,
+                },
+                Color::TRANSPARENT,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<Focus<'a, Message, Renderer>>
+    for iced_native::Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: iced_native::Renderer<Theme = Theme> + 'a,
+{
+    fn from(focus: Focus<'a, Message, Renderer>) -> Self {
+        iced_native::Element::new(focus)
+    }
+}
+
+/// Finds which widgets can take the focus, which of them has it and which are in a modal,
+/// then moves the focus with [`FocusNth`].
+struct Survey {
+    backwards: bool,
+    /// Whether the widgets being visited are in a modal.
+    in_modal: bool,
+    /// For each focusable widget in order, whether it is in a modal and whether it has the focus.
+    focusables: Vec<(bool, bool)>,
+}
+
+impl<T> Operation<T> for Survey {
+    fn container(
+        &mut self,
+        id: Option<&widget::Id>,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        let in_modal = self.in_modal;
+        self.in_modal |= id == Some(&widget::Id::new(MODAL));
+        operate_on_children(self);
+        self.in_modal = in_modal;
+    }
+
+    fn focusable(&mut self, state: &mut dyn operation::Focusable, _id: Option<&widget::Id>) {
+        self.focusables.push((self.in_modal, state.is_focused()));
+    }
+
+    fn finish(&self) -> Outcome<T> {
+        let trapped = self.focusables.iter().any(|&(in_modal, _)| in_modal);
+        let candidates: Vec<usize> = (0..self.focusables.len())
+            .filter(|&i| !trapped || self.focusables[i].0)
+            .collect();
+        if candidates.is_empty() {
+            return Outcome::None;
+        }
+        let count = candidates.len();
+        let current = candidates.iter().position(|&i| self.focusables[i].1);
+        let next = match (current, self.backwards) {
+            (Some(current), false) => (current + 1) % count,
+            (Some(current), true) => (current + count - 1) % count,
+            (None, false) => 0,
+            (None, true) => count - 1,
+        };
+        Outcome::Chain(Box::new(FocusNth {
+            target: candidates[next],
+            current: 0,
+        }))
+    }
+}
+
+/// Focuses the focusable widget at `target` in the order of the tree, taking the focus away
+/// from the others.
+struct FocusNth {
+    target: usize,
+    current: usize,
+}
+
+impl<T> Operation<T> for FocusNth {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        operate_on_children(self)
+    }
+
+    fn focusable(&mut self, state: &mut dyn operation::Focusable, _id: Option<&widget::Id>) {
+        if self.current == self.target {
+            state.focus();
+        } else {
+            state.unfocus();
+        }
+        self.current += 1;
+    }
+}
@@ -0,0 +1,129 @@
+//! The measures and colors components share: spacing, padding, corner radii, text sizes and
+//! the colors of each theme by what they're for. Screens and the headless renderer use these
+//! rather than values of their own, so a change of theme or zoom affects all of them alike.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use iced::{theme::palette, Color};
+
+/// Space between items which belong together, e.g. the entries of a list.
+pub const SPACE_S: u16 = 4;
+/// Space between the items of a screen or a row of buttons.
+pub const SPACE_M: u16 = 10;
+/// Space between groups of items, e.g. options side by side.
+pub const SPACE_L: u16 = 20;
+
+/// Padding of buttons, vertical then horizontal.
+pub const BUTTON_PADDING: [u16; 2] = [10, 20];
+/// Padding of the cells of a board.
+pub const CELL_PADDING: u16 = 10;
+/// Padding inside panels and modals.
+pub const PANEL_PADDING: u16 = 20;
+
+/// Corners of buttons and board cells.
+pub const RADIUS_S: f32 = 4.0;
+/// Corners of panels and modals.
+pub const RADIUS_M: f32 = 8.0;
+
+/// Whether the low-spec mode of the settings is on, for the style sheets and widgets, which
+/// screens build without the settings at hand.
+static LOW_SPEC: AtomicBool = AtomicBool::new(false);
+
+pub fn set_low_spec(enabled: bool) {
+    LOW_SPEC.store(enabled, Ordering::Relaxed);
+}
+
+pub fn low_spec() -> bool {
+    LOW_SPEC.load(Ordering::Relaxed)
+}
+
+/// Corners of `radius`, square in the low-spec mode since they're cheaper to draw.
+pub fn corners(radius: f32) -> f32 {
+    if low_spec() {
+        0.0
+    } else {
+        radius
+    }
+}
+
+/// Size of the title of a screen.
+pub const TEXT_TITLE: u16 = 32;
+/// Size of headings and of the result of a game.
+pub const TEXT_HEADING: u16 = 24;
+/// Size of body text, the default of iced.
+pub const TEXT_BODY: u16 = 20;
+/// Size of secondary text, e.g. shortcuts and notes.
+pub const TEXT_SMALL: u16 = 14;
+/// Size of marks filling a whole board, e.g. a won board of ultimate.
+pub const TEXT_DISPLAY: u16 = 64;
+
+/// The colors of a theme, by role rather than by hue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Colors {
+    pub background: Color,
+    pub text: Color,
+    /// Main actions and highlights.
+    pub accent: Color,
+    pub success: Color,
+    /// Actions which can't be undone.
+    pub danger: Color,
+    /// Marks of the human on images, where the text color alone wouldn't tell them apart.
+    pub human: Color,
+    /// Marks of the computer on images.
+    pub computer: Color,
+}
+
+impl Colors {
+    pub const DARK: Colors = Colors {
+        background: rgb(0x20, 0x22, 0x25),
+        text: rgb(0xe6, 0xe6, 0xe6),
+        accent: rgb(0x5e, 0x7c, 0xe2),
+        success: rgb(0x12, 0x66, 0x4f),
+        danger: rgb(0xc3, 0x42, 0x3f),
+        human: rgb(0x58, 0x65, 0xf2),
+        computer: rgb(0xed, 0x42, 0x45),
+    };
+
+    pub const LIGHT: Colors = Colors {
+        background: rgb(0xff, 0xff, 0xff),
+        text: rgb(0x00, 0x00, 0x00),
+        accent: rgb(0x5e, 0x7c, 0xe2),
+        success: rgb(0x12, 0x66, 0x4f),
+        danger: rgb(0xc3, 0x42, 0x3f),
+        human: rgb(0x3b, 0x4a, 0xd9),
+        computer: rgb(0xc9, 0x2a, 0x2d),
+    };
+
+    /// Panels and board cells, set apart from the background as iced derives it.
+    #[cfg_attr(not(feature = "render"), allow(dead_code))]
+    pub fn surface(&self) -> Color {
+        palette::Extended::generate(self.palette())
+            .background
+            .weak
+            .color
+    }
+
+    /// The iced theme drawing the widgets with these colors.
+    pub fn theme(&self) -> iced::Theme {
+        iced::Theme::custom(self.palette())
+    }
+
+    fn palette(&self) -> palette::Palette {
+        palette::Palette {
+            background: self.background,
+            text: self.text,
+            primary: self.accent,
+            success: self.success,
+            danger: self.danger,
+        }
+    }
+}
+
+const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color {
+        r: r as f32 / 255.0,
+        g: g as f32 / 255.0,
+        b: b as f32 / 255.0,
+        a: 1.0,
+    }
+}
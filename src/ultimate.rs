@@ -0,0 +1,107 @@
+//! The screen of the Ultimate variant, a grid of small boards played against the computer.
+
+use iced::{
+    widget::{button, column, container, row, text},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    game::{ultimate::Ultimate, Entity, GameState, MoveError},
+    i18n::I18n,
+    player::Players,
+    ui::{
+        components::{button_style, panel_style, primary, secondary},
+        focus::focusable,
+        tokens::{SPACE_L, SPACE_M, SPACE_S, TEXT_DISPLAY, TEXT_TITLE},
+    },
+    Message,
+};
+
+/// Side of a cell of a small board.
+const CELL: u16 = 32;
+
+pub fn view<'a>(
+    game: &'a Ultimate,
+    refused: Option<MoveError>,
+    players: &Players,
+    i18n: &I18n,
+) -> Element<'a, Message> {
+    let small_board = |outer: (usize, usize)| -> Element<'a, Message> {
+        let side = 3 * CELL + 2 * SPACE_S;
+        let owner = game.owner(outer);
+        if owner != Entity::Empty {
+            return container(text(owner.as_str()).size(TEXT_DISPLAY))
+                .width(side)
+                .height(side)
+                .center_x()
+                .center_y()
+                .into();
+        }
+        let open = game.is_open(outer);
+        let cells = column(
+            (0..3)
+                .map(|x| {
+                    row((0..3)
+                        .map(|y| {
+                            let entity = game.board(outer)[x][y];
+                            let mut cell = button(
+                                text(entity.as_str())
+                                    .width(Length::Fill)
+                                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                            )
+                            .style(button_style(iced::theme::Button::Text))
+                            .width(CELL)
+                            .height(CELL);
+                            if open && entity == Entity::Empty {
+                                cell = cell.on_press(Message::UltimateClicked(outer, (x, y)));
+                            }
+                            focusable(cell)
+                        })
+                        .collect())
+                    .spacing(SPACE_S)
+                    .into()
+                })
+                .collect(),
+        )
+        .spacing(SPACE_S);
+        let mut board = container(cells).padding(SPACE_S);
+        if open {
+            board = board.style(panel_style());
+        }
+        board.into()
+    };
+    let board = column(
+        (0..3)
+            .map(|x| {
+                row((0..3).map(|y| small_board((x, y))).collect())
+                    .spacing(SPACE_L)
+                    .into()
+            })
+            .collect(),
+    )
+    .spacing(SPACE_L);
+
+    let status = match game.state() {
+        GameState::Draw => i18n.tr("result-draw"),
+        GameState::Win(winner) => {
+            i18n.tr_args("result-win", [("winner", players.addressed(winner).into())])
+        }
+        _ => match refused {
+            Some(err) => i18n.tr(err.label()),
+            None => i18n.tr("ultimate-rules"),
+        },
+    };
+    column![
+        text(i18n.tr("ultimate")).size(TEXT_TITLE),
+        board,
+        text(status),
+        row![
+            focusable(primary(i18n.tr("reset")).on_press(Message::UltimateReset)),
+            focusable(secondary(i18n.tr("back")).on_press(Message::ToggleUltimate))
+        ]
+        .spacing(SPACE_M)
+    ]
+    .align_items(Alignment::Center)
+    .spacing(SPACE_M)
+    .into()
+}
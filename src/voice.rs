@@ -0,0 +1,192 @@
+//! Experimental voice commands. The speech recognition is left to a local program of the
+//! user's choice printing each phrase it hears on a line, e.g. a script around Vosk or
+//! whisper.cpp, so no audio leaves the machine and the app links no recognizer.
+
+use iced::Subscription;
+
+use crate::{command::Command, game::parse_cell, i18n::I18n};
+
+// Without the `voice` feature nothing listens to a recognizer.
+#[cfg_attr(not(feature = "voice"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// The recognizer heard a phrase.
+    Heard(String),
+    /// The recognizer couldn't start or exited, with the reason.
+    Stopped(String),
+}
+
+/// What a phrase asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intent {
+    Cell(usize, usize),
+    Command(Command),
+}
+
+/// Words naming a row, from the top, or a column, from the left, in the languages of the app.
+const FIRST: [&str; 6] = ["top", "upper", "left", "arriba", "superior", "izquierda"];
+const MIDDLE: [&str; 4] = ["center", "centre", "middle", "centro"];
+const LAST: [&str; 6] = ["bottom", "lower", "right", "abajo", "inferior", "derecha"];
+const ROWS: [&str; 6] = ["top", "upper", "bottom", "lower", "arriba", "abajo"];
+
+/// Digits as recognizers tend to spell them out.
+const NUMBERS: [(&str, char); 12] = [
+    ("one", '1'),
+    ("two", '2'),
+    ("three", '3'),
+    ("four", '4'),
+    ("five", '5'),
+    ("six", '6'),
+    ("uno", '1'),
+    ("dos", '2'),
+    ("tres", '3'),
+    ("cuatro", '4'),
+    ("cinco", '5'),
+    ("seis", '6'),
+];
+
+/// Lowercase words without punctuation, e.g. `"Top-left!"` becomes `["top", "left"]`.
+fn words(phrase: &str) -> Vec<String> {
+    phrase
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Makes sense of `phrase` on a board `size` cells wide: a cell named by its place, such as
+/// "top left" or "center", or by its name, such as "b2" or "b two", or a command by its id or
+/// its name in the language of the app, such as "undo" or "new game".
+pub fn understand(phrase: &str, size: usize, i18n: &I18n) -> Option<Intent> {
+    let words = words(phrase);
+    if words.is_empty() {
+        return None;
+    }
+    let command = Command::all().find(|command| {
+        words == self::words(command.id()) || words == self::words(&i18n.tr(command.label()))
+    });
+    if let Some(command) = command {
+        return Some(Intent::Command(command));
+    }
+    cell_by_place(&words, size)
+        .or_else(|| cell_by_name(&words))
+        .filter(|&(x, y)| x < size && y < size)
+        .map(|(x, y)| Intent::Cell(x, y))
+}
+
+/// A cell named by up to a row and a column word, a missing one standing for the middle.
+fn cell_by_place(words: &[String], size: usize) -> Option<(usize, usize)> {
+    let place = |word: &str| {
+        if FIRST.contains(&word) {
+            Some(0)
+        } else if LAST.contains(&word) {
+            Some(size - 1)
+        } else if MIDDLE.contains(&word) {
+            // Even boards have no middle cell.
+            (size % 2 == 1).then_some(size / 2)
+        } else {
+            None
+        }
+    };
+    let (rows, columns): (Vec<&String>, Vec<&String>) =
+        words.iter().partition(|word| ROWS.contains(&word.as_str()));
+    let (row, column) = match (&rows[..], &columns[..]) {
+        ([row], [column]) => (place(row)?, place(column)?),
+        ([], [row, column]) if MIDDLE.contains(&row.as_str()) => (place(row)?, place(column)?),
+        ([row], []) => (place(row)?, place("center")?),
+        ([], [column]) => (place("center")?, place(column)?),
+        _ => return None,
+    };
+    Some((row, column))
+}
+
+/// A cell named by its column letter and row number, however the recognizer split them.
+fn cell_by_name(words: &[String]) -> Option<(usize, usize)> {
+    let name: String = words
+        .iter()
+        .map(
+            |word| match NUMBERS.iter().find(|(number, _)| number == word) {
+                Some((_, digit)) => digit.to_string(),
+                None => word.clone(),
+            },
+        )
+        .collect();
+    parse_cell(&name)
+}
+
+/// Runs `command`, the recognizer, and listens to the phrases it prints. Only builds with the
+/// `voice` feature do.
+pub fn listen(command: String) -> Subscription<Event> {
+    #[cfg(feature = "voice")]
+    return recognizer::listen(command);
+    #[cfg(not(feature = "voice"))]
+    {
+        let _ = command;
+        Subscription::none()
+    }
+}
+
+#[cfg(feature = "voice")]
+mod recognizer {
+    use std::{
+        io::{BufRead, BufReader},
+        process::{Command, Stdio},
+        thread,
+    };
+
+    use iced::{
+        futures::{channel::mpsc, SinkExt, StreamExt},
+        subscription, Subscription,
+    };
+
+    use super::Event;
+
+    pub fn listen(command: String) -> Subscription<Event> {
+        subscription::channel(command.clone(), 100, move |mut output| {
+            let command = command.clone();
+            async move {
+                let (sender, mut receiver) = mpsc::unbounded();
+                // Reading the output of the recognizer blocks, on a thread of its own.
+                thread::spawn(move || {
+                    let reason = match run(&command, &sender) {
+                        Ok(()) => "exited".to_string(),
+                        Err(err) => err.to_string(),
+                    };
+                    let _ = sender.unbounded_send(Event::Stopped(reason));
+                });
+                loop {
+                    match receiver.next().await {
+                        Some(event) => {
+                            let _ = output.send(event).await;
+                        }
+                        // A recognizer which stopped isn't started again until the settings
+                        // change it.
+                        None => iced::futures::future::pending::<()>().await,
+                    }
+                }
+            }
+        })
+    }
+
+    /// Starts `command`, split on whitespace into the program and its arguments, and sends
+    /// every line it prints until it exits.
+    fn run(command: &str, sender: &mpsc::UnboundedSender<Event>) -> std::io::Result<()> {
+        let mut words = command.split_whitespace();
+        let program = words.next().unwrap_or_default();
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdout = child.stdout.take().expect("stdout is piped");
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                let _ = sender.unbounded_send(Event::Heard(line));
+            }
+        }
+        child.wait()?;
+        Ok(())
+    }
+}